@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use miden_protocol::{
+    assembly::{DefaultSourceManager, Library, Module, ModuleKind, Path as LibraryPath},
+    transaction::TransactionKernel,
+};
+
+use crate::oracle::get_fresh_median_procedure_hash;
+
+const CONSUMER_MASM_TEMPLATE: &str = include_str!("consumer.masm");
+
+/// Returns the consumer MASM code with the oracle's get_fresh_median hash injected.
+pub fn get_consumer_masm() -> String {
+    let get_fresh_median_hash = get_fresh_median_procedure_hash();
+    CONSUMER_MASM_TEMPLATE.replace("{GET_FRESH_MEDIAN_HASH}", &get_fresh_median_hash)
+}
+
+/// Library reading prices from the oracle, to be linked statically into the
+/// account components (or tx scripts) of the protocols consuming them.
+///
+/// Its procedures live under `oracle_consumer::consumer_module`.
+pub fn get_consumer_library() -> Arc<Library> {
+    let source_manager = Arc::new(DefaultSourceManager::default());
+    let consumer_masm = get_consumer_masm();
+    let consumer_module = Module::parser(ModuleKind::Library)
+        .parse_str(
+            LibraryPath::new("oracle_consumer::consumer_module"),
+            &consumer_masm,
+            source_manager.clone(),
+        )
+        .unwrap();
+
+    TransactionKernel::assembler_with_source_manager(source_manager)
+        .assemble_library([consumer_module])
+        .expect("assembly should succeed")
+}
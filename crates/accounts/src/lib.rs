@@ -1,3 +1,6 @@
+pub mod consumer;
+pub mod notes;
 pub mod oracle;
 pub mod publisher;
+pub mod receiver;
 pub mod utils;
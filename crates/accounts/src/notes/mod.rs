@@ -0,0 +1,146 @@
+use miden_client::{account::AccountId, Word};
+use miden_protocol::note::{
+    Note, NoteAssets, NoteRecipient, NoteScript, NoteStorage, NoteTag, NoteType,
+    PartialNoteMetadata,
+};
+use miden_standards::code_builder::CodeBuilder;
+
+use crate::oracle::get_oracle_component_library;
+use crate::receiver::get_price_receiver_component_library;
+
+const LEAVE_NOTE_MASM_TEMPLATE: &str = include_str!("leave.masm");
+const REGISTRATION_NOTE_MASM_TEMPLATE: &str = include_str!("registration.masm");
+const PRICE_PUSH_NOTE_MASM_TEMPLATE: &str = include_str!("price_push.masm");
+
+/// Median snapshot carried by a price-push note.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceSnapshot {
+    pub faucet_id_prefix: u64,
+    pub faucet_id_suffix: u64,
+    pub median: u64,
+    pub decimals: u32,
+    /// Timestamp of the newest entry behind the median, which orders the
+    /// snapshots a receiver accepts.
+    pub timestamp: u64,
+}
+
+/// Returns the note MASM code with the target oracle id injected.
+fn inject_oracle_id(template: &str, oracle_id: AccountId) -> String {
+    template
+        .replace(
+            "{ORACLE_ID_PREFIX}",
+            &oracle_id.prefix().as_u64().to_string(),
+        )
+        .replace("{ORACLE_ID_SUFFIX}", &oracle_id.suffix().to_string())
+}
+
+/// Compiles a note script calling into the oracle component.
+fn compile_oracle_note_script(note_masm: String) -> anyhow::Result<NoteScript> {
+    CodeBuilder::default()
+        .with_dynamically_linked_library(get_oracle_component_library())
+        .map_err(|e| anyhow::anyhow!("Error while setting up the component library: {e:?}"))?
+        .compile_note_script(note_masm)
+        .map_err(|e| anyhow::anyhow!("Error while compiling the note script: {e:?}"))
+}
+
+/// Builds a public note sent by `sender` and tagged for `target`.
+fn create_public_note(
+    sender: AccountId,
+    target: AccountId,
+    serial_num: Word,
+    script: NoteScript,
+) -> Note {
+    let recipient = NoteRecipient::new(serial_num, script, NoteStorage::default());
+    let metadata = PartialNoteMetadata::new(sender, NoteType::Public)
+        .with_tag(NoteTag::with_account_target(target));
+    Note::new(NoteAssets::default(), metadata, recipient)
+}
+
+/// Compiles the script of the note a publisher sends to leave `oracle_id`.
+///
+/// The oracle id is part of the script, so the script root identifies the leave
+/// notes targeting a given oracle.
+pub fn get_leave_note_script(oracle_id: AccountId) -> anyhow::Result<NoteScript> {
+    compile_oracle_note_script(inject_oracle_id(LEAVE_NOTE_MASM_TEMPLATE, oracle_id))
+}
+
+/// Builds the public note `publisher_id` sends to be removed from `oracle_id`.
+///
+/// Once consumed by the oracle, `get_median` skips the publisher like any slot
+/// soft-deleted by `remove_publisher`.
+pub fn create_leave_note(
+    publisher_id: AccountId,
+    oracle_id: AccountId,
+    serial_num: Word,
+) -> anyhow::Result<Note> {
+    let script = get_leave_note_script(oracle_id)?;
+    Ok(create_public_note(
+        publisher_id,
+        oracle_id,
+        serial_num,
+        script,
+    ))
+}
+
+/// Compiles the script of the note a publisher sends to join `oracle_id`.
+///
+/// Like the leave note, the oracle id is part of the script, so the script root
+/// identifies the registration requests targeting a given oracle.
+pub fn get_registration_note_script(oracle_id: AccountId) -> anyhow::Result<NoteScript> {
+    compile_oracle_note_script(inject_oracle_id(REGISTRATION_NOTE_MASM_TEMPLATE, oracle_id))
+}
+
+/// Builds the public note `publisher_id` sends to request its registration
+/// into `oracle_id`.
+///
+/// The request is accepted when the oracle owner consumes the note, which runs
+/// `register_publisher` for the note sender.
+pub fn create_registration_request_note(
+    publisher_id: AccountId,
+    oracle_id: AccountId,
+    serial_num: Word,
+) -> anyhow::Result<Note> {
+    let script = get_registration_note_script(oracle_id)?;
+    Ok(create_public_note(
+        publisher_id,
+        oracle_id,
+        serial_num,
+        script,
+    ))
+}
+
+/// Compiles the script of a price-push note carrying `snapshot`.
+pub fn get_price_push_note_script(snapshot: &PriceSnapshot) -> anyhow::Result<NoteScript> {
+    let note_masm = PRICE_PUSH_NOTE_MASM_TEMPLATE
+        .replace("{FAUCET_ID_PREFIX}", &snapshot.faucet_id_prefix.to_string())
+        .replace("{FAUCET_ID_SUFFIX}", &snapshot.faucet_id_suffix.to_string())
+        .replace("{MEDIAN}", &snapshot.median.to_string())
+        .replace("{DECIMALS}", &snapshot.decimals.to_string())
+        .replace("{TIMESTAMP}", &snapshot.timestamp.to_string());
+    CodeBuilder::default()
+        .with_dynamically_linked_library(get_price_receiver_component_library())
+        .map_err(|e| anyhow::anyhow!("Error while setting up the component library: {e:?}"))?
+        .compile_note_script(note_masm)
+        .map_err(|e| anyhow::anyhow!("Error while compiling the note script: {e:?}"))
+}
+
+/// Builds the public note `oracle_id` sends to push `snapshot` to `consumer_id`.
+///
+/// The note must be created by a transaction executed against the oracle
+/// account: the consumer's price receiver rejects any snapshot whose note
+/// sender is not the oracle it trusts, as well as snapshots older than the one
+/// it already stores.
+pub fn create_price_push_note(
+    oracle_id: AccountId,
+    consumer_id: AccountId,
+    snapshot: &PriceSnapshot,
+    serial_num: Word,
+) -> anyhow::Result<Note> {
+    let script = get_price_push_note_script(snapshot)?;
+    Ok(create_public_note(
+        oracle_id,
+        consumer_id,
+        serial_num,
+        script,
+    ))
+}
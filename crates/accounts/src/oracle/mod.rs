@@ -9,7 +9,7 @@ use miden_client::{
     },
     auth::AuthSecretKey,
     crypto::rpo_falcon512::SecretKey,
-    keystore::{FilesystemKeyStore, Keystore},
+    keystore::Keystore,
     Client, Felt, Word, ZERO,
 };
 use miden_protocol::{
@@ -22,14 +22,26 @@ use miden_protocol::{
 
 use miden_protocol::assembly::mast::MastNodeExt;
 
-use crate::publisher::get_entry_procedure_hash;
+use pm_utils_cli::{open_keystore, PragmaKeyStore};
+
+use crate::publisher::{
+    get_entry_procedure_hash, get_feed_procedure_hash, get_history_entry_procedure_hash,
+    get_randomness_procedure_hash,
+};
 
 const ORACLE_ACCOUNT_MASM_TEMPLATE: &str = include_str!("oracle.masm");
 
-/// Returns the oracle MASM code with the publisher's get_entry hash injected.
+/// Returns the oracle MASM code with the publisher's get_entry,
+/// get_history_entry, get_feed and get_randomness hashes injected.
 fn get_oracle_masm() -> String {
-    let get_entry_hash = get_entry_procedure_hash();
-    ORACLE_ACCOUNT_MASM_TEMPLATE.replace("{GET_ENTRY_HASH}", &get_entry_hash)
+    ORACLE_ACCOUNT_MASM_TEMPLATE
+        .replace("{GET_ENTRY_HASH}", &get_entry_procedure_hash())
+        .replace(
+            "{GET_HISTORY_ENTRY_HASH}",
+            &get_history_entry_procedure_hash(),
+        )
+        .replace("{GET_FEED_HASH}", &get_feed_procedure_hash())
+        .replace("{GET_RANDOMNESS_HASH}", &get_randomness_procedure_hash())
 }
 
 pub fn oracle_storage_slots() -> Vec<StorageSlot> {
@@ -39,6 +51,13 @@ pub fn oracle_storage_slots() -> Vec<StorageSlot> {
             [Felt::from(2u32), ZERO, ZERO, ZERO].into(),
         ),
         StorageSlot::with_empty_map(StorageSlotName::new("pragma::oracle::publishers").unwrap()),
+        StorageSlot::with_value(
+            StorageSlotName::new("pragma::oracle::next_asset_index").unwrap(),
+            [ZERO, ZERO, ZERO, ZERO].into(),
+        ),
+        StorageSlot::with_empty_map(StorageSlotName::new("pragma::oracle::assets").unwrap()),
+        StorageSlot::with_empty_map(StorageSlotName::new("pragma::oracle::asset_infos").unwrap()),
+        StorageSlot::with_empty_map(StorageSlotName::new("pragma::oracle::feed_configs").unwrap()),
     ]
 }
 
@@ -83,6 +102,36 @@ pub fn get_median_procedure_hash() -> String {
         .join(".")
 }
 
+/// Returns the hash of the `get_fresh_median` procedure as a dot-separated string
+/// of felt integers, in the order expected by `push` before a Foreign Procedure
+/// Invocation. This is used by the consumer library to call the oracle.
+pub fn get_fresh_median_procedure_hash() -> String {
+    let lib = get_oracle_component_library();
+    let export = lib
+        .exports()
+        .find(|e| {
+            let path = e.path();
+            let path_str = path.as_ref().as_str();
+            path_str.ends_with("::get_fresh_median") || path_str == "get_fresh_median"
+        })
+        .expect("get_fresh_median procedure not found in oracle library");
+
+    let node_id = lib.get_export_node_id(export.path());
+    let digest = lib
+        .mast_forest()
+        .get_node_by_id(node_id)
+        .expect("node not found")
+        .digest();
+
+    digest
+        .as_elements()
+        .iter()
+        .rev()
+        .map(|f| f.as_canonical_u64().to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
 pub fn get_oracle_component() -> AccountComponent {
     let library = get_oracle_component_library();
     let library = Arc::try_unwrap(library).unwrap_or_else(|arc| (*arc).clone());
@@ -92,7 +141,7 @@ pub fn get_oracle_component() -> AccountComponent {
 }
 
 pub struct OracleAccountBuilder<'a> {
-    client: Option<&'a mut Client<FilesystemKeyStore>>,
+    client: Option<&'a mut Client<PragmaKeyStore>>,
     account_type: ClientAccountType,
     storage_slots: Vec<StorageSlot>,
     keystore_path: String,
@@ -122,7 +171,7 @@ impl<'a> OracleAccountBuilder<'a> {
         self
     }
 
-    pub fn with_client(mut self, client: &'a mut Client<FilesystemKeyStore>) -> Self {
+    pub fn with_client(mut self, client: &'a mut Client<PragmaKeyStore>) -> Self {
         self.client = Some(client);
         self
     }
@@ -155,7 +204,7 @@ impl<'a> OracleAccountBuilder<'a> {
         let account_seed = account.seed().expect("New account should have seed");
         client.add_account(&account, true).await.unwrap();
 
-        let keystore = FilesystemKeyStore::new(self.keystore_path.into()).unwrap();
+        let keystore = open_keystore(Some(self.keystore_path)).unwrap();
         keystore
             .add_key(
                 &AuthSecretKey::Falcon512Poseidon2(private_key),
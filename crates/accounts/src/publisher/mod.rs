@@ -8,8 +8,8 @@ use miden_client::{
         Account,
     },
     auth::AuthSecretKey,
-    keystore::{FilesystemKeyStore, Keystore},
-    Client, Word,
+    keystore::Keystore,
+    Client, Word, ZERO,
 };
 
 use miden_protocol::{
@@ -19,24 +19,70 @@ use miden_protocol::{
     },
     assembly::{DefaultSourceManager, Library, Module, ModuleKind, Path as LibraryPath},
     transaction::TransactionKernel,
+    Hasher,
 };
 
 use miden_protocol::assembly::mast::MastNodeExt;
 
-pub const PUBLISHER_ACCOUNT_MASM: &str = include_str!("publisher.masm");
+use pm_utils_cli::{open_keystore, PragmaKeyStore};
+
+const PUBLISHER_ACCOUNT_MASM_TEMPLATE: &str = include_str!("publisher.masm");
+
+/// Returns the publisher MASM code with the storage slot of the public key of
+/// the auth component injected, which `rotate_key` overwrites.
+fn get_publisher_masm() -> String {
+    PUBLISHER_ACCOUNT_MASM_TEMPLATE.replace(
+        "{AUTH_PUBLIC_KEY_SLOT}",
+        AuthSingleSig::public_key_slot().as_str(),
+    )
+}
 
 /// Returns the hash of the `get_entry` procedure as a dot-separated string of felt integers.
 /// This is used by the oracle to call the publisher's get_entry procedure.
 pub fn get_entry_procedure_hash() -> String {
+    procedure_hash("get_entry")
+}
+
+/// Returns the hash of the `get_history_entry` procedure, in the same format as
+/// [`get_entry_procedure_hash`]. This is used by the oracle to read the
+/// publisher's history when computing volatilities.
+pub fn get_history_entry_procedure_hash() -> String {
+    procedure_hash("get_history_entry")
+}
+
+/// Returns the hash of the `get_feed` procedure, in the same format as
+/// [`get_entry_procedure_hash`]. This is used by the oracle to aggregate the
+/// values published for generic feeds.
+pub fn get_feed_procedure_hash() -> String {
+    procedure_hash("get_feed")
+}
+
+/// Returns the hash of the `get_randomness` procedure, in the same format as
+/// [`get_entry_procedure_hash`]. This is used by the oracle to combine the
+/// randomness revealed by the publishers.
+pub fn get_randomness_procedure_hash() -> String {
+    procedure_hash("get_randomness")
+}
+
+/// Commitment a publisher stores before revealing `value` with
+/// `publish_randomness`: `hash(value, value)`.
+///
+/// `value` is the randomness as stored by the publisher, i.e. as it is on the
+/// stack once pushed.
+pub fn randomness_commitment(value: Word) -> Word {
+    Hasher::merge(&[value, value])
+}
+
+fn procedure_hash(name: &str) -> String {
     let lib = get_publisher_component_library();
     let export = lib
         .exports()
         .find(|e| {
             let path = e.path();
             let path_str = path.as_ref().as_str();
-            path_str.ends_with("::get_entry") || path_str == "get_entry"
+            path_str.ends_with(&format!("::{name}")) || path_str == name
         })
-        .expect("get_entry procedure not found in publisher library");
+        .unwrap_or_else(|| panic!("{name} procedure not found in publisher library"));
 
     let node_id = lib.get_export_node_id(export.path());
     let digest = lib
@@ -59,7 +105,7 @@ pub fn get_publisher_component_library() -> Arc<Library> {
     let publisher_component_module = Module::parser(ModuleKind::Library)
         .parse_str(
             LibraryPath::new("publisher_component::publisher_module"),
-            PUBLISHER_ACCOUNT_MASM,
+            get_publisher_masm(),
             source_manager.clone(),
         )
         .unwrap();
@@ -69,17 +115,39 @@ pub fn get_publisher_component_library() -> Arc<Library> {
         .expect("assembly should succeed")
 }
 
+pub fn publisher_storage_slots() -> Vec<StorageSlot> {
+    vec![
+        StorageSlot::with_empty_map(StorageSlotName::new("pragma::publisher::entries").unwrap()),
+        StorageSlot::with_value(
+            StorageSlotName::new("pragma::publisher::last_heartbeat").unwrap(),
+            [ZERO, ZERO, ZERO, ZERO].into(),
+        ),
+        StorageSlot::with_empty_map(StorageSlotName::new("pragma::publisher::history").unwrap()),
+        StorageSlot::with_empty_map(
+            StorageSlotName::new("pragma::publisher::history_counts").unwrap(),
+        ),
+        StorageSlot::with_empty_map(StorageSlotName::new("pragma::publisher::feeds").unwrap()),
+        StorageSlot::with_empty_map(
+            StorageSlotName::new("pragma::publisher::randomness_commitments").unwrap(),
+        ),
+        StorageSlot::with_empty_map(StorageSlotName::new("pragma::publisher::randomness").unwrap()),
+        StorageSlot::with_value(
+            StorageSlotName::new("pragma::publisher::proposed_key").unwrap(),
+            [ZERO, ZERO, ZERO, ZERO].into(),
+        ),
+    ]
+}
+
 pub fn get_publisher_component() -> AccountComponent {
     let library = get_publisher_component_library();
     let library = Arc::try_unwrap(library).unwrap_or_else(|arc| (*arc).clone());
-    let storage_slot =
-        StorageSlot::with_empty_map(StorageSlotName::new("pragma::publisher::entries").unwrap());
     let metadata = AccountComponentMetadata::new("pragma::publisher");
-    AccountComponent::new(library, vec![storage_slot], metadata).expect("assembly should succeed")
+    AccountComponent::new(library, publisher_storage_slots(), metadata)
+        .expect("assembly should succeed")
 }
 
 pub struct PublisherAccountBuilder<'a> {
-    client: Option<&'a mut Client<FilesystemKeyStore>>,
+    client: Option<&'a mut Client<PragmaKeyStore>>,
     account_type: AccountType,
     storage_slots: Vec<StorageSlot>,
     keystore_path: String,
@@ -87,9 +155,7 @@ pub struct PublisherAccountBuilder<'a> {
 
 impl<'a> PublisherAccountBuilder<'a> {
     pub fn new() -> Self {
-        let default_storage_slots = vec![StorageSlot::with_empty_map(
-            StorageSlotName::new("pragma::publisher::entries").unwrap(),
-        )];
+        let default_storage_slots = publisher_storage_slots();
         Self {
             client: None,
             // 0.15: AccountType only encodes visibility (Public/Private); code
@@ -110,7 +176,7 @@ impl<'a> PublisherAccountBuilder<'a> {
         self
     }
 
-    pub fn with_client(mut self, client: &'a mut Client<FilesystemKeyStore>) -> Self {
+    pub fn with_client(mut self, client: &'a mut Client<PragmaKeyStore>) -> Self {
         self.client = Some(client);
         self
     }
@@ -141,7 +207,7 @@ impl<'a> PublisherAccountBuilder<'a> {
         let account_seed = account.seed().expect("New account should have seed");
 
         client.add_account(&account, true).await.unwrap();
-        let keystore = FilesystemKeyStore::new(self.keystore_path.into()).unwrap();
+        let keystore = open_keystore(Some(self.keystore_path)).unwrap();
         keystore.add_key(&auth_key, account.id()).await.unwrap();
 
         (account, account_seed)
@@ -168,4 +234,22 @@ mod tests {
             part.parse::<u64>().expect("should be valid u64");
         }
     }
+
+    #[test]
+    fn test_history_entry_procedure_hash_differs_from_get_entry() {
+        assert_ne!(
+            get_history_entry_procedure_hash(),
+            get_entry_procedure_hash()
+        );
+    }
+
+    #[test]
+    fn test_feed_procedure_hash_differs_from_get_entry() {
+        assert_ne!(get_feed_procedure_hash(), get_entry_procedure_hash());
+    }
+
+    #[test]
+    fn test_randomness_procedure_hash_differs_from_get_feed() {
+        assert_ne!(get_randomness_procedure_hash(), get_feed_procedure_hash());
+    }
 }
@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use miden_client::{account::AccountId, ZERO};
+use miden_protocol::{
+    account::{AccountComponent, AccountComponentMetadata, StorageSlot, StorageSlotName},
+    assembly::{DefaultSourceManager, Library, Module, ModuleKind, Path as LibraryPath},
+    transaction::TransactionKernel,
+};
+
+pub const PRICE_RECEIVER_MASM: &str = include_str!("receiver.masm");
+
+/// Storage of a price receiver trusting the snapshots pushed by `oracle_id`.
+///
+/// The oracle id is stored as [prefix, suffix, 0, 0], the same layout as the
+/// oracle publisher registry.
+pub fn price_receiver_storage_slots(oracle_id: AccountId) -> Vec<StorageSlot> {
+    vec![
+        StorageSlot::with_value(
+            StorageSlotName::new("pragma::price_receiver::oracle").unwrap(),
+            [oracle_id.prefix().as_felt(), oracle_id.suffix(), ZERO, ZERO].into(),
+        ),
+        StorageSlot::with_empty_map(
+            StorageSlotName::new("pragma::price_receiver::prices").unwrap(),
+        ),
+    ]
+}
+
+pub fn get_price_receiver_component_library() -> Arc<Library> {
+    let source_manager = Arc::new(DefaultSourceManager::default());
+    let receiver_component_module = Module::parser(ModuleKind::Library)
+        .parse_str(
+            LibraryPath::new("price_receiver_component::price_receiver_module"),
+            PRICE_RECEIVER_MASM,
+            source_manager.clone(),
+        )
+        .unwrap();
+
+    TransactionKernel::assembler_with_source_manager(source_manager)
+        .assemble_library([receiver_component_module])
+        .expect("assembly should succeed")
+}
+
+/// Component to add to a consumer account so it can consume the price-push
+/// notes sent by `oracle_id`.
+pub fn get_price_receiver_component(oracle_id: AccountId) -> AccountComponent {
+    let library = get_price_receiver_component_library();
+    let library = Arc::try_unwrap(library).unwrap_or_else(|arc| (*arc).clone());
+    let metadata = AccountComponentMetadata::new("pragma::price_receiver");
+    AccountComponent::new(library, price_receiver_storage_slots(oracle_id), metadata)
+        .expect("assembly should succeed")
+}
@@ -0,0 +1,217 @@
+//! Oracle asset registry tests against an in-process MockChain.
+//!
+//! `register_asset` is checked through the resulting account delta, and
+//! `list_assets` through assertions inside the tx script itself.
+
+use std::str::FromStr;
+
+use anyhow::Result;
+use miden_client::transaction::TransactionScript;
+use miden_protocol::account::{auth::AuthScheme, Account, StorageSlotName};
+use miden_protocol::errors::MasmError;
+use miden_protocol::{Felt, Word, ZERO};
+use miden_standards::code_builder::CodeBuilder;
+use miden_testing::{assert_transaction_executor_error, Auth, MockChain, MockChainBuilder};
+
+use pm_accounts::{
+    oracle::{get_oracle_component, get_oracle_component_library},
+    utils::word_to_masm,
+};
+use pm_types::{AssetInfoWord, AssetKey, Pair};
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+const ERR_EMPTY_ASSET_NAME: MasmError = MasmError::from_static_str("asset name must not be empty");
+
+fn falcon_auth() -> Auth {
+    Auth::BasicAuth {
+        auth_scheme: AuthScheme::Falcon512Poseidon2,
+    }
+}
+
+fn register_asset_script(asset_key: AssetKey, asset_info: Word) -> Result<TransactionScript> {
+    let tx_script_code = format!(
+        "
+        use oracle_component::oracle_module
+        use miden::core::sys
+
+        begin
+            push.{asset_info}
+            push.{asset_key}
+            call.oracle_module::register_asset
+            exec.sys::truncate_stack
+        end
+        ",
+        asset_info = word_to_masm(asset_info),
+        asset_key = word_to_masm(asset_key.to_word()),
+    );
+    Ok(CodeBuilder::default()
+        .with_statically_linked_library(&get_oracle_component_library())?
+        .compile_tx_script(tx_script_code)?)
+}
+
+async fn register_asset(
+    mock_chain: &mut MockChain,
+    oracle: &mut Account,
+    asset_key: AssetKey,
+    pair: &str,
+    decimals: u32,
+) -> Result<()> {
+    let asset_info = AssetInfoWord::new(&Pair::from_str(pair)?, decimals)?;
+    let tx = mock_chain
+        .build_tx_context(oracle.id(), &[], &[])?
+        .tx_script(register_asset_script(asset_key, asset_info.to_push_word())?)
+        .build()?;
+    let executed = tx.execute().await?;
+    oracle.apply_delta(executed.account_delta())?;
+    mock_chain.add_pending_executed_transaction(&executed)?;
+    mock_chain.prove_next_block()?;
+    Ok(())
+}
+
+fn asset_count(oracle: &Account) -> u64 {
+    let slot = StorageSlotName::new("pragma::oracle::next_asset_index").unwrap();
+    oracle.storage().get_item(&slot).unwrap()[0].as_canonical_u64()
+}
+
+fn asset_at(oracle: &Account, index: u64) -> Word {
+    let slot = StorageSlotName::new("pragma::oracle::assets").unwrap();
+    let key: Word = [Felt::new(index).unwrap(), ZERO, ZERO, ZERO].into();
+    oracle.storage().get_map_item(&slot, key).unwrap()
+}
+
+fn asset_info(oracle: &Account, asset_key: AssetKey) -> AssetInfoWord {
+    let slot = StorageSlotName::new("pragma::oracle::asset_infos").unwrap();
+    AssetInfoWord::from_word(
+        oracle
+            .storage()
+            .get_map_item(&slot, asset_key.to_storage_key())
+            .unwrap(),
+    )
+}
+
+// ============================================================================
+// Tests: register_asset
+// ============================================================================
+
+#[tokio::test]
+async fn test_oracle_register_asset() -> Result<()> {
+    let mut builder = MockChainBuilder::new();
+    let mut oracle =
+        builder.add_existing_account_from_components(falcon_auth(), [get_oracle_component()])?;
+    let mut mock_chain = builder.build()?;
+
+    let btc = AssetKey::new(1, 0)?;
+    let eth = AssetKey::new(2, 0)?;
+    register_asset(&mut mock_chain, &mut oracle, btc, "BTC/USD", 8).await?;
+    register_asset(&mut mock_chain, &mut oracle, eth, "ETH/USD", 18).await?;
+
+    assert_eq!(asset_count(&oracle), 2, "both assets must be listed");
+    assert_eq!(asset_at(&oracle, 0), btc.to_storage_key());
+    assert_eq!(asset_at(&oracle, 1), eth.to_storage_key());
+
+    let btc_info = asset_info(&oracle, btc);
+    assert_eq!(btc_info.pair()?, Pair::from_str("BTC/USD")?);
+    assert_eq!(btc_info.decimals()?, 8);
+    let eth_info = asset_info(&oracle, eth);
+    assert_eq!(eth_info.pair()?, Pair::from_str("ETH/USD")?);
+    assert_eq!(eth_info.decimals()?, 18);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_oracle_register_asset_again_updates_it() -> Result<()> {
+    let mut builder = MockChainBuilder::new();
+    let mut oracle =
+        builder.add_existing_account_from_components(falcon_auth(), [get_oracle_component()])?;
+    let mut mock_chain = builder.build()?;
+
+    let asset_key = AssetKey::new(3, 0)?;
+    register_asset(&mut mock_chain, &mut oracle, asset_key, "WBTC/USD", 8).await?;
+    register_asset(&mut mock_chain, &mut oracle, asset_key, "SOL/USD", 6).await?;
+
+    assert_eq!(asset_count(&oracle), 1, "an asset must be listed only once");
+    let info = asset_info(&oracle, asset_key);
+    assert_eq!(info.pair()?, Pair::from_str("SOL/USD")?);
+    assert_eq!(info.decimals()?, 6);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_oracle_register_asset_fails_with_empty_name() -> Result<()> {
+    let mut builder = MockChainBuilder::new();
+    let oracle =
+        builder.add_existing_account_from_components(falcon_auth(), [get_oracle_component()])?;
+    let mock_chain = builder.build()?;
+
+    let empty_info: Word = [Felt::from(8u32), ZERO, ZERO, ZERO].into();
+    let tx = mock_chain
+        .build_tx_context(oracle.id(), &[], &[])?
+        .tx_script(register_asset_script(AssetKey::new(1, 0)?, empty_info)?)
+        .build()?;
+    let result = tx.execute().await;
+
+    assert_transaction_executor_error!(result, ERR_EMPTY_ASSET_NAME);
+
+    Ok(())
+}
+
+// ============================================================================
+// Tests: list_assets
+// ============================================================================
+
+#[tokio::test]
+async fn test_oracle_list_assets() -> Result<()> {
+    let mut builder = MockChainBuilder::new();
+    let mut oracle =
+        builder.add_existing_account_from_components(falcon_auth(), [get_oracle_component()])?;
+    let mut mock_chain = builder.build()?;
+
+    let asset_key = AssetKey::new(1, 0)?;
+    register_asset(&mut mock_chain, &mut oracle, asset_key, "BTC/USD", 8).await?;
+    let info = AssetInfoWord::new(&Pair::from_str("BTC/USD")?, 8)?;
+
+    // The listed words come back in their pushed form, so they are compared
+    // against the very same words pushed in the same order.
+    let tx_script_code = format!(
+        "
+        use oracle_component::oracle_module
+        use miden::core::sys
+
+        begin
+            push.0
+            call.oracle_module::list_assets
+            # => [nb_of_assets, ASSET_KEY, ASSET_INFO]
+            push.1 assert_eq.err=\"wrong number of assets\"
+            push.{asset_key} assert_eqw.err=\"wrong asset key\"
+            push.{asset_info} assert_eqw.err=\"wrong asset info\"
+
+            push.1
+            call.oracle_module::list_assets
+            push.1 assert_eq.err=\"wrong number of assets\"
+            padw assert_eqw.err=\"asset key past the end must be empty\"
+            padw assert_eqw.err=\"asset info past the end must be empty\"
+
+            exec.sys::truncate_stack
+        end
+        ",
+        asset_key = word_to_masm(asset_key.to_word()),
+        asset_info = word_to_masm(info.to_push_word()),
+    );
+    let script = CodeBuilder::default()
+        .with_statically_linked_library(&get_oracle_component_library())?
+        .compile_tx_script(tx_script_code)?;
+
+    mock_chain
+        .build_tx_context(oracle.id(), &[], &[])?
+        .tx_script(script)
+        .build()?
+        .execute()
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,234 @@
+//! Consumer library tests against an in-process MockChain.
+//!
+//! Each test builds a fresh chain with two publishers seeded with an entry, an
+//! oracle registering both, and a consumer account. The consumer runs a tx
+//! script linking the consumer library statically, which reads the median
+//! from the oracle through FPI (the oracle itself FPIs the publishers).
+
+use anyhow::Result;
+use miden_client::account::AccountId;
+use miden_client::transaction::TransactionScript;
+use miden_protocol::account::{
+    auth::AuthScheme, AccountComponent, AccountComponentMetadata, StorageMap, StorageMapKey,
+    StorageSlot, StorageSlotName,
+};
+use miden_protocol::errors::MasmError;
+use miden_protocol::{Felt, Word, ZERO};
+use miden_standards::code_builder::CodeBuilder;
+use miden_testing::{
+    assert_transaction_executor_error, Auth, MockChain, MockChainBuilder, TransactionContext,
+};
+
+use pm_accounts::{
+    consumer::get_consumer_library,
+    oracle::{get_oracle_component, get_oracle_component_library},
+    publisher::get_publisher_component_library,
+    receiver::get_price_receiver_component,
+};
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+const ERR_ORACLE_PRICE_STALE: MasmError =
+    MasmError::from_static_str("no oracle entry is fresh enough");
+const ERR_ORACLE_QUORUM_NOT_REACHED: MasmError =
+    MasmError::from_static_str("not enough fresh oracle entries to reach the quorum");
+
+const NOW_TS: u32 = 2_000_000_000;
+
+fn falcon_auth() -> Auth {
+    Auth::BasicAuth {
+        auth_scheme: AuthScheme::Falcon512Poseidon2,
+    }
+}
+
+fn btc_key() -> Word {
+    [Felt::new(1).unwrap(), ZERO, ZERO, ZERO].into()
+}
+
+fn publisher_component_with_entry(price: u64, timestamp: u64) -> AccountComponent {
+    let library = (*get_publisher_component_library()).clone();
+    let entry: Word = [
+        Felt::new(timestamp).unwrap(),
+        Felt::new(8).unwrap(),
+        Felt::new(price).unwrap(),
+        ZERO,
+    ]
+    .into();
+    let storage_slots = vec![
+        StorageSlot::with_map(
+            StorageSlotName::new("pragma::publisher::entries").unwrap(),
+            StorageMap::with_entries(vec![(StorageMapKey::new(btc_key()), entry)]).unwrap(),
+        ),
+        StorageSlot::with_value(
+            StorageSlotName::new("pragma::publisher::last_heartbeat").unwrap(),
+            [ZERO, ZERO, ZERO, ZERO].into(),
+        ),
+    ];
+    let metadata = AccountComponentMetadata::new("pragma::publisher");
+    AccountComponent::new(library, storage_slots, metadata)
+        .expect("publisher component should assemble")
+}
+
+fn register_publisher_script(publisher_id: AccountId) -> Result<TransactionScript> {
+    let tx_script_code = format!(
+        "
+        use oracle_component::oracle_module
+        use miden::core::sys
+
+        begin
+            push.0.0
+            push.{suffix} push.{prefix}
+            call.oracle_module::register_publisher
+            exec.sys::truncate_stack
+        end
+        ",
+        prefix = publisher_id.prefix().as_u64(),
+        suffix = publisher_id.suffix(),
+    );
+    Ok(CodeBuilder::default()
+        .with_statically_linked_library(&get_oracle_component_library())?
+        .compile_tx_script(tx_script_code)?)
+}
+
+/// Reads the BTC median through the consumer library and asserts it equals
+/// `expected_median`.
+fn read_median_script(
+    oracle_id: AccountId,
+    max_age: u64,
+    min_publishers: u64,
+    expected_median: u64,
+) -> Result<TransactionScript> {
+    let tx_script_code = format!(
+        "
+        use oracle_consumer::consumer_module
+        use miden::core::sys
+
+        begin
+            push.{min_publishers}.{max_age}.0.1.{oracle_suffix}.{oracle_prefix}
+            exec.consumer_module::get_median
+            push.{expected_median} assert_eq.err=\"unexpected median\"
+            exec.sys::truncate_stack
+        end
+        ",
+        oracle_prefix = oracle_id.prefix().as_u64(),
+        oracle_suffix = oracle_id.suffix(),
+    );
+    Ok(CodeBuilder::default()
+        .with_statically_linked_library(&get_consumer_library())?
+        .compile_tx_script(tx_script_code)?)
+}
+
+struct Setup {
+    mock_chain: MockChain,
+    oracle_id: AccountId,
+    publisher_ids: Vec<AccountId>,
+    consumer_id: AccountId,
+}
+
+/// Two publishers at 50_000 and 52_000 with entries published at `entry_ts`,
+/// both registered into the oracle, and the chain advanced to `NOW_TS`.
+async fn setup(entry_ts: u64) -> Result<Setup> {
+    let mut builder = MockChainBuilder::new();
+    let pub_a = builder.add_existing_account_from_components(
+        falcon_auth(),
+        [publisher_component_with_entry(50_000_000_000, entry_ts)],
+    )?;
+    let pub_b = builder.add_existing_account_from_components(
+        falcon_auth(),
+        [publisher_component_with_entry(52_000_000_000, entry_ts)],
+    )?;
+    let oracle =
+        builder.add_existing_account_from_components(falcon_auth(), [get_oracle_component()])?;
+    let consumer = builder.add_existing_account_from_components(
+        falcon_auth(),
+        [get_price_receiver_component(oracle.id())],
+    )?;
+    let mut mock_chain = builder.build()?;
+
+    for publisher_id in [pub_a.id(), pub_b.id()] {
+        let executed = mock_chain
+            .build_tx_context(oracle.id(), &[], &[])?
+            .tx_script(register_publisher_script(publisher_id)?)
+            .build()?
+            .execute()
+            .await?;
+        mock_chain.add_pending_executed_transaction(&executed)?;
+        mock_chain.prove_next_block()?;
+    }
+    mock_chain.prove_next_block_at(NOW_TS)?;
+
+    Ok(Setup {
+        mock_chain,
+        oracle_id: oracle.id(),
+        publisher_ids: vec![pub_a.id(), pub_b.id()],
+        consumer_id: consumer.id(),
+    })
+}
+
+fn read_median_tx(
+    setup: &Setup,
+    max_age: u64,
+    min_publishers: u64,
+    expected_median: u64,
+) -> Result<TransactionContext> {
+    let foreign_inputs = std::iter::once(setup.oracle_id)
+        .chain(setup.publisher_ids.iter().copied())
+        .map(|id| setup.mock_chain.get_foreign_account_inputs(id))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    setup
+        .mock_chain
+        .build_tx_context(setup.consumer_id, &[], &[])?
+        .foreign_accounts(foreign_inputs)
+        .tx_script(read_median_script(
+            setup.oracle_id,
+            max_age,
+            min_publishers,
+            expected_median,
+        )?)
+        .build()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_consumer_reads_median_when_quorum_is_reached() -> Result<()> {
+    let setup = setup(NOW_TS as u64).await?;
+
+    read_median_tx(&setup, 3600, 2, 51_000_000_000)?
+        .execute()
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_consumer_rejects_median_below_quorum() -> Result<()> {
+    let setup = setup(NOW_TS as u64).await?;
+
+    let result = read_median_tx(&setup, 3600, 3, 51_000_000_000)?
+        .execute()
+        .await;
+
+    assert_transaction_executor_error!(result, ERR_ORACLE_QUORUM_NOT_REACHED);
+
+    Ok(())
+}
+
+/// Entries 10 minutes old are still used by the oracle's own `get_median`, but
+/// a consumer requiring 5 minutes of freshness must refuse them.
+#[tokio::test]
+async fn test_consumer_rejects_stale_median() -> Result<()> {
+    let setup = setup((NOW_TS - 600) as u64).await?;
+
+    let result = read_median_tx(&setup, 300, 1, 51_000_000_000)?
+        .execute()
+        .await;
+
+    assert_transaction_executor_error!(result, ERR_ORACLE_PRICE_STALE);
+
+    Ok(())
+}
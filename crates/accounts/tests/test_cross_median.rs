@@ -0,0 +1,304 @@
+//! `get_cross_median` tests against an in-process MockChain.
+//!
+//! Values are read through `execute_code` (invoking the proc by MAST root), as
+//! in the `get_median` value tests of `test_oracle.rs`.
+
+use std::str::FromStr;
+
+use anyhow::Result;
+use miden_client::account::AccountId;
+use miden_client::transaction::TransactionScript;
+use miden_protocol::account::{
+    auth::AuthScheme, AccountComponent, AccountComponentMetadata, StorageMap, StorageMapKey,
+    StorageSlot, StorageSlotName,
+};
+use miden_protocol::errors::MasmError;
+use miden_protocol::{Felt, Word, ZERO};
+use miden_standards::code_builder::CodeBuilder;
+use miden_testing::{assert_transaction_executor_error, Auth, MockChain, MockChainBuilder};
+
+use pm_accounts::{
+    oracle::{get_oracle_component, get_oracle_component_library},
+    publisher::get_publisher_component_library,
+    utils::word_to_masm,
+};
+use pm_types::{AssetInfoWord, AssetKey, Pair};
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+const ERR_CROSS_ASSET_NOT_REGISTERED: MasmError =
+    MasmError::from_static_str("cross pair asset not registered");
+
+const NOW_TS: u32 = 2_000_000_000;
+
+fn falcon_auth() -> Auth {
+    Auth::BasicAuth {
+        auth_scheme: AuthScheme::Falcon512Poseidon2,
+    }
+}
+
+/// Stored form of a publisher entry, see `onchain_entry` in `test_oracle.rs`.
+fn onchain_entry(price: u64, decimals: u64) -> Word {
+    [
+        Felt::new(NOW_TS as u64).unwrap(),
+        Felt::new(decimals).unwrap(),
+        Felt::new(price).unwrap(),
+        ZERO,
+    ]
+    .into()
+}
+
+/// Builds a publisher AccountComponent seeded with an entry per asset.
+fn publisher_component_with_entries(entries: &[(AssetKey, Word)]) -> AccountComponent {
+    let library = (*get_publisher_component_library()).clone();
+    let entries = entries
+        .iter()
+        .map(|(asset_key, entry)| (StorageMapKey::new(asset_key.to_storage_key()), *entry))
+        .collect::<Vec<_>>();
+    let storage_slots = vec![
+        StorageSlot::with_map(
+            StorageSlotName::new("pragma::publisher::entries").unwrap(),
+            StorageMap::with_entries(entries).unwrap(),
+        ),
+        StorageSlot::with_value(
+            StorageSlotName::new("pragma::publisher::last_heartbeat").unwrap(),
+            [ZERO, ZERO, ZERO, ZERO].into(),
+        ),
+    ];
+    let metadata = AccountComponentMetadata::new("pragma::publisher");
+    AccountComponent::new(library, storage_slots, metadata)
+        .expect("publisher component should assemble")
+}
+
+fn compile_oracle_script(tx_script_code: String) -> Result<TransactionScript> {
+    Ok(CodeBuilder::default()
+        .with_statically_linked_library(&get_oracle_component_library())?
+        .compile_tx_script(tx_script_code)?)
+}
+
+async fn execute_oracle_script(
+    mock_chain: &mut MockChain,
+    oracle_id: AccountId,
+    tx_script_code: String,
+) -> Result<()> {
+    let tx = mock_chain
+        .build_tx_context(oracle_id, &[], &[])?
+        .tx_script(compile_oracle_script(tx_script_code)?)
+        .build()?;
+    let executed = tx.execute().await?;
+    mock_chain.add_pending_executed_transaction(&executed)?;
+    mock_chain.prove_next_block()?;
+    Ok(())
+}
+
+async fn register_publisher(
+    mock_chain: &mut MockChain,
+    oracle_id: AccountId,
+    publisher_id: AccountId,
+) -> Result<()> {
+    let tx_script_code = format!(
+        "
+        use oracle_component::oracle_module
+        use miden::core::sys
+
+        begin
+            push.0.0
+            push.{suffix} push.{prefix}
+            call.oracle_module::register_publisher
+            exec.sys::truncate_stack
+        end
+        ",
+        prefix = publisher_id.prefix().as_u64(),
+        suffix = publisher_id.suffix(),
+    );
+    execute_oracle_script(mock_chain, oracle_id, tx_script_code).await
+}
+
+async fn register_asset(
+    mock_chain: &mut MockChain,
+    oracle_id: AccountId,
+    asset_key: AssetKey,
+    pair: &str,
+    decimals: u32,
+) -> Result<()> {
+    let asset_info = AssetInfoWord::new(&Pair::from_str(pair)?, decimals)?;
+    let tx_script_code = format!(
+        "
+        use oracle_component::oracle_module
+        use miden::core::sys
+
+        begin
+            push.{asset_info}
+            push.{asset_key}
+            call.oracle_module::register_asset
+            exec.sys::truncate_stack
+        end
+        ",
+        asset_info = word_to_masm(asset_info.to_push_word()),
+        asset_key = word_to_masm(asset_key.to_word()),
+    );
+    execute_oracle_script(mock_chain, oracle_id, tx_script_code).await
+}
+
+fn oracle_proc_root(name: &str) -> Word {
+    let lib = get_oracle_component_library();
+    let short = format!("oracle_module::{name}");
+    let full = format!("oracle_component::oracle_module::{name}");
+    lib.get_procedure_root_by_path(short.as_str())
+        .or_else(|| lib.get_procedure_root_by_path(full.as_str()))
+        .expect("oracle procedure root must resolve")
+}
+
+/// Runs `get_cross_median` and returns `(is_tracked, cross_price)`.
+async fn run_get_cross_median(
+    mock_chain: &MockChain,
+    oracle_id: AccountId,
+    publisher_ids: &[AccountId],
+    base: AssetKey,
+    quote: AssetKey,
+) -> Result<(u64, u64)> {
+    let foreign_inputs = publisher_ids
+        .iter()
+        .map(|id| mock_chain.get_foreign_account_inputs(*id))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let ctx = mock_chain
+        .build_tx_context(oracle_id, &[], &[])?
+        .foreign_accounts(foreign_inputs)
+        .build()?;
+    let code = format!(
+        "
+        use $kernel::prologue
+        use miden::core::sys
+
+        begin
+            exec.prologue::prepare_transaction
+            push.{quote_suffix}.{quote_prefix}.{base_suffix}.{base_prefix}
+            call.{root}
+            exec.sys::truncate_stack
+        end
+        ",
+        quote_suffix = quote.suffix(),
+        quote_prefix = quote.prefix(),
+        base_suffix = base.suffix(),
+        base_prefix = base.prefix(),
+        root = oracle_proc_root("get_cross_median").to_hex(),
+    );
+    let out = ctx
+        .execute_code(&code)
+        .await
+        .map_err(|e| anyhow::anyhow!("get_cross_median execute_code failed: {e:?}"))?;
+    Ok((
+        out.stack[0].as_canonical_u64(),
+        out.stack[1].as_canonical_u64(),
+    ))
+}
+
+// ============================================================================
+// Tests: get_cross_median
+// ============================================================================
+
+#[tokio::test]
+async fn test_oracle_get_cross_median_value() -> Result<()> {
+    let btc = AssetKey::new(1, 0)?;
+    let eth = AssetKey::new(2, 0)?;
+    let usdt = AssetKey::new(4, 0)?;
+
+    let mut builder = MockChainBuilder::new();
+    let publisher = builder.add_existing_account_from_components(
+        falcon_auth(),
+        [publisher_component_with_entries(&[
+            (btc, onchain_entry(6_000_000_000_000, 8)),
+            (eth, onchain_entry(300_000_000_000, 8)),
+            (usdt, onchain_entry(1_000_000, 6)),
+        ])],
+    )?;
+    let oracle =
+        builder.add_existing_account_from_components(falcon_auth(), [get_oracle_component()])?;
+    let mut mock_chain = builder.build()?;
+
+    register_publisher(&mut mock_chain, oracle.id(), publisher.id()).await?;
+    register_asset(&mut mock_chain, oracle.id(), btc, "BTC/USD", 8).await?;
+    register_asset(&mut mock_chain, oracle.id(), eth, "ETH/USD", 8).await?;
+    register_asset(&mut mock_chain, oracle.id(), usdt, "USDT/USD", 6).await?;
+    mock_chain.prove_next_block_at(NOW_TS)?;
+
+    // ETH/BTC = 3_000 / 60_000 = 0.05, with 8 decimals.
+    let (is_tracked, price) =
+        run_get_cross_median(&mock_chain, oracle.id(), &[publisher.id()], eth, btc).await?;
+    assert_eq!(is_tracked, 1, "cross pair must be tracked");
+    assert_eq!(price, 5_000_000, "ETH/BTC = 0.05");
+
+    // BTC/ETH = 20, the integer part of the division.
+    let (_, price) =
+        run_get_cross_median(&mock_chain, oracle.id(), &[publisher.id()], btc, eth).await?;
+    assert_eq!(price, 2_000_000_000, "BTC/ETH = 20");
+
+    // BTC/USDT = 60_000, with a quote median of 6 decimals.
+    let (_, price) =
+        run_get_cross_median(&mock_chain, oracle.id(), &[publisher.id()], btc, usdt).await?;
+    assert_eq!(price, 6_000_000_000_000, "BTC/USDT = 60_000");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_oracle_get_cross_median_untracked_quote() -> Result<()> {
+    let btc = AssetKey::new(1, 0)?;
+    let eth = AssetKey::new(2, 0)?;
+
+    let mut builder = MockChainBuilder::new();
+    let publisher = builder.add_existing_account_from_components(
+        falcon_auth(),
+        [publisher_component_with_entries(&[(
+            eth,
+            onchain_entry(300_000_000_000, 8),
+        )])],
+    )?;
+    let oracle =
+        builder.add_existing_account_from_components(falcon_auth(), [get_oracle_component()])?;
+    let mut mock_chain = builder.build()?;
+
+    register_publisher(&mut mock_chain, oracle.id(), publisher.id()).await?;
+    register_asset(&mut mock_chain, oracle.id(), btc, "BTC/USD", 8).await?;
+    register_asset(&mut mock_chain, oracle.id(), eth, "ETH/USD", 8).await?;
+    mock_chain.prove_next_block_at(NOW_TS)?;
+
+    let (is_tracked, price) =
+        run_get_cross_median(&mock_chain, oracle.id(), &[publisher.id()], eth, btc).await?;
+    assert_eq!(is_tracked, 0, "no BTC median, so no ETH/BTC price");
+    assert_eq!(price, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_oracle_get_cross_median_fails_if_asset_not_registered() -> Result<()> {
+    let mut builder = MockChainBuilder::new();
+    let oracle =
+        builder.add_existing_account_from_components(falcon_auth(), [get_oracle_component()])?;
+    let mock_chain = builder.build()?;
+
+    let tx_script_code = "
+        use oracle_component::oracle_module
+        use miden::core::sys
+
+        begin
+            push.0.1.0.2
+            call.oracle_module::get_cross_median
+            exec.sys::truncate_stack
+        end
+        "
+    .to_string();
+    let result = mock_chain
+        .build_tx_context(oracle.id(), &[], &[])?
+        .tx_script(compile_oracle_script(tx_script_code)?)
+        .build()?
+        .execute()
+        .await;
+
+    assert_transaction_executor_error!(result, ERR_CROSS_ASSET_NOT_REGISTERED);
+
+    Ok(())
+}
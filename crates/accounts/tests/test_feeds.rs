@@ -0,0 +1,448 @@
+//! Generic feed tests against an in-process MockChain.
+//!
+//! Publishers publish their values through `publish_feed`, and the aggregated
+//! value is read through `execute_code` (invoking `get_feed` by MAST root), as
+//! in the `get_median` value tests of `test_oracle.rs`.
+
+use anyhow::Result;
+use miden_client::account::AccountId;
+use miden_client::transaction::TransactionScript;
+use miden_protocol::account::{auth::AuthScheme, StorageSlotName};
+use miden_protocol::errors::MasmError;
+use miden_protocol::{Felt, Word, ZERO};
+use miden_standards::code_builder::CodeBuilder;
+use miden_testing::{assert_transaction_executor_error, Auth, MockChain, MockChainBuilder};
+
+use pm_accounts::{
+    oracle::{get_oracle_component, get_oracle_component_library},
+    publisher::{get_publisher_component, get_publisher_component_library},
+    utils::word_to_masm,
+};
+use pm_types::{decode_value, AggregationMode, FeedConfig, FeedId, FeedKind, FeedValue};
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+const ERR_INVALID_AGGREGATION_MODE: MasmError =
+    MasmError::from_static_str("invalid feed aggregation mode");
+
+const NOW_TS: u32 = 2_000_000_000;
+
+const FEED_NAME: &str = "BTC-PERP/funding";
+
+fn falcon_auth() -> Auth {
+    Auth::BasicAuth {
+        auth_scheme: AuthScheme::Falcon512Poseidon2,
+    }
+}
+
+fn feed_id() -> FeedId {
+    FeedId::from_name(FEED_NAME).unwrap()
+}
+
+fn feed_value(kind: FeedKind, value: i64, timestamp: u64) -> FeedValue {
+    FeedValue {
+        kind,
+        value,
+        decimals: 6,
+        timestamp,
+    }
+}
+
+fn publish_feed_script(value: FeedValue) -> Result<TransactionScript> {
+    let tx_script_code = format!(
+        "
+        use publisher_component::publisher_module
+        use miden::core::sys
+
+        begin
+            push.{value}
+            push.{feed_id}
+            call.publisher_module::publish_feed
+            exec.sys::truncate_stack
+        end
+        ",
+        value = word_to_masm(value.to_push_word()?),
+        feed_id = word_to_masm(feed_id().to_word()),
+    );
+    Ok(CodeBuilder::default()
+        .with_statically_linked_library(&get_publisher_component_library())?
+        .compile_tx_script(tx_script_code)?)
+}
+
+fn compile_oracle_script(tx_script_code: String) -> Result<TransactionScript> {
+    Ok(CodeBuilder::default()
+        .with_statically_linked_library(&get_oracle_component_library())?
+        .compile_tx_script(tx_script_code)?)
+}
+
+fn register_publisher_script(publisher_id: AccountId) -> Result<TransactionScript> {
+    compile_oracle_script(format!(
+        "
+        use oracle_component::oracle_module
+        use miden::core::sys
+
+        begin
+            push.0.0
+            push.{suffix} push.{prefix}
+            call.oracle_module::register_publisher
+            exec.sys::truncate_stack
+        end
+        ",
+        prefix = publisher_id.prefix().as_u64(),
+        suffix = publisher_id.suffix(),
+    ))
+}
+
+/// `set_feed_config` script, with the config given in its pushed form.
+fn set_feed_config_script(config: Word) -> Result<TransactionScript> {
+    compile_oracle_script(format!(
+        "
+        use oracle_component::oracle_module
+        use miden::core::sys
+
+        begin
+            push.{config}
+            push.{feed_id}
+            call.oracle_module::set_feed_config
+            exec.sys::truncate_stack
+        end
+        ",
+        config = word_to_masm(config),
+        feed_id = word_to_masm(feed_id().to_word()),
+    ))
+}
+
+async fn execute_and_commit(
+    mock_chain: &mut MockChain,
+    account_id: AccountId,
+    script: TransactionScript,
+) -> Result<()> {
+    let tx = mock_chain
+        .build_tx_context(account_id, &[], &[])?
+        .tx_script(script)
+        .build()?;
+    let executed = tx.execute().await?;
+    mock_chain.add_pending_executed_transaction(&executed)?;
+    mock_chain.prove_next_block()?;
+    Ok(())
+}
+
+async fn configure_feed(
+    mock_chain: &mut MockChain,
+    oracle_id: AccountId,
+    kind: FeedKind,
+    mode: AggregationMode,
+    max_age: u64,
+) -> Result<()> {
+    let config = FeedConfig {
+        kind,
+        mode,
+        max_age,
+        decimals: 6,
+    };
+    execute_and_commit(
+        mock_chain,
+        oracle_id,
+        set_feed_config_script(config.to_push_word()?)?,
+    )
+    .await
+}
+
+/// Builds a chain with an oracle and one registered publisher per value,
+/// each publisher having published its value for the test feed.
+async fn setup(values: &[FeedValue]) -> Result<(MockChain, AccountId, Vec<AccountId>)> {
+    let mut builder = MockChainBuilder::new();
+    let publisher_ids = values
+        .iter()
+        .map(|_| {
+            builder
+                .add_existing_account_from_components(falcon_auth(), [get_publisher_component()])
+                .map(|publisher| publisher.id())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let oracle =
+        builder.add_existing_account_from_components(falcon_auth(), [get_oracle_component()])?;
+    let mut mock_chain = builder.build()?;
+
+    for (publisher_id, value) in publisher_ids.iter().zip(values) {
+        execute_and_commit(&mut mock_chain, *publisher_id, publish_feed_script(*value)?).await?;
+        execute_and_commit(
+            &mut mock_chain,
+            oracle.id(),
+            register_publisher_script(*publisher_id)?,
+        )
+        .await?;
+    }
+    mock_chain.prove_next_block_at(NOW_TS)?;
+
+    Ok((mock_chain, oracle.id(), publisher_ids))
+}
+
+fn oracle_proc_root(name: &str) -> Word {
+    let lib = get_oracle_component_library();
+    let short = format!("oracle_module::{name}");
+    let full = format!("oracle_component::oracle_module::{name}");
+    lib.get_procedure_root_by_path(short.as_str())
+        .or_else(|| lib.get_procedure_root_by_path(full.as_str()))
+        .expect("oracle procedure root must resolve")
+}
+
+/// Runs `get_feed` for the test feed and returns `(is_tracked, raw_value, nb_of_values)`.
+async fn run_get_feed(
+    mock_chain: &MockChain,
+    oracle_id: AccountId,
+    publisher_ids: &[AccountId],
+) -> Result<(u64, u64, u64)> {
+    let foreign_inputs = publisher_ids
+        .iter()
+        .map(|id| mock_chain.get_foreign_account_inputs(*id))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let ctx = mock_chain
+        .build_tx_context(oracle_id, &[], &[])?
+        .foreign_accounts(foreign_inputs)
+        .build()?;
+    let code = format!(
+        "
+        use $kernel::prologue
+        use miden::core::sys
+
+        begin
+            exec.prologue::prepare_transaction
+            push.{feed_id}
+            call.{root}
+            exec.sys::truncate_stack
+        end
+        ",
+        feed_id = word_to_masm(feed_id().to_word()),
+        root = oracle_proc_root("get_feed").to_hex(),
+    );
+    let out = ctx
+        .execute_code(&code)
+        .await
+        .map_err(|e| anyhow::anyhow!("get_feed execute_code failed: {e:?}"))?;
+    Ok((
+        out.stack[0].as_canonical_u64(),
+        out.stack[1].as_canonical_u64(),
+        out.stack[2].as_canonical_u64(),
+    ))
+}
+
+// ============================================================================
+// Tests: publish_feed
+// ============================================================================
+
+#[tokio::test]
+async fn test_publisher_publish_feed_stores_value() -> Result<()> {
+    let mut builder = MockChainBuilder::new();
+    let publisher =
+        builder.add_existing_account_from_components(falcon_auth(), [get_publisher_component()])?;
+    let mock_chain = builder.build()?;
+
+    let value = feed_value(FeedKind::FundingRate, -1_250, NOW_TS as u64);
+    let executed_tx = mock_chain
+        .build_tx_context(publisher.id(), &[], &[])?
+        .tx_script(publish_feed_script(value)?)
+        .build()?
+        .execute()
+        .await?;
+
+    let mut publisher = publisher.clone();
+    publisher.apply_delta(executed_tx.account_delta())?;
+
+    let feeds_slot = StorageSlotName::new("pragma::publisher::feeds").unwrap();
+    let stored = publisher
+        .storage()
+        .get_map_item(&feeds_slot, feed_id().to_storage_key())
+        .unwrap();
+    assert_eq!(stored, value.to_word()?);
+    assert_eq!(FeedValue::from_word(stored)?, value);
+
+    Ok(())
+}
+
+// ============================================================================
+// Tests: get_feed
+// ============================================================================
+
+#[tokio::test]
+async fn test_oracle_get_feed_defaults_to_median_of_prices() -> Result<()> {
+    let now = NOW_TS as u64;
+    let (mock_chain, oracle_id, publisher_ids) = setup(&[
+        feed_value(FeedKind::Price, 100, now),
+        feed_value(FeedKind::Price, 300, now),
+        feed_value(FeedKind::Price, 200, now),
+    ])
+    .await?;
+
+    let (is_tracked, value, nb_of_values) =
+        run_get_feed(&mock_chain, oracle_id, &publisher_ids).await?;
+    assert_eq!(is_tracked, 1);
+    assert_eq!(value, 200);
+    assert_eq!(nb_of_values, 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_oracle_get_feed_aggregation_modes() -> Result<()> {
+    let now = NOW_TS as u64;
+    let (mut mock_chain, oracle_id, publisher_ids) = setup(&[
+        feed_value(FeedKind::ProofOfReserve, 1_000, now),
+        feed_value(FeedKind::ProofOfReserve, 4_000, now),
+        feed_value(FeedKind::ProofOfReserve, 2_000, now),
+    ])
+    .await?;
+
+    for (mode, expected) in [
+        (AggregationMode::Median, 2_000),
+        (AggregationMode::Mean, 2_333),
+        (AggregationMode::Min, 1_000),
+        (AggregationMode::Max, 4_000),
+    ] {
+        configure_feed(
+            &mut mock_chain,
+            oracle_id,
+            FeedKind::ProofOfReserve,
+            mode,
+            0,
+        )
+        .await?;
+        let (is_tracked, value, nb_of_values) =
+            run_get_feed(&mock_chain, oracle_id, &publisher_ids).await?;
+        assert_eq!(is_tracked, 1);
+        assert_eq!(value, expected, "{mode} of the reserves");
+        assert_eq!(nb_of_values, 3);
+    }
+
+    Ok(())
+}
+
+/// Min and max must hold with two values, the smallest case the values get sorted.
+#[tokio::test]
+async fn test_oracle_get_feed_min_max_of_two_values() -> Result<()> {
+    let now = NOW_TS as u64;
+    let (mut mock_chain, oracle_id, publisher_ids) = setup(&[
+        feed_value(FeedKind::InterestRateIndex, 1_050_000, now),
+        feed_value(FeedKind::InterestRateIndex, 1_020_000, now),
+    ])
+    .await?;
+
+    for (mode, expected) in [
+        (AggregationMode::Min, 1_020_000),
+        (AggregationMode::Max, 1_050_000),
+    ] {
+        configure_feed(
+            &mut mock_chain,
+            oracle_id,
+            FeedKind::InterestRateIndex,
+            mode,
+            0,
+        )
+        .await?;
+        let (_, value, _) = run_get_feed(&mock_chain, oracle_id, &publisher_ids).await?;
+        assert_eq!(value, expected, "{mode} of the indices");
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_oracle_get_feed_of_signed_funding_rates() -> Result<()> {
+    let now = NOW_TS as u64;
+    let (mut mock_chain, oracle_id, publisher_ids) = setup(&[
+        feed_value(FeedKind::FundingRate, -600, now),
+        feed_value(FeedKind::FundingRate, -300, now),
+        feed_value(FeedKind::FundingRate, 300, now),
+    ])
+    .await?;
+
+    for (mode, expected) in [
+        (AggregationMode::Median, -300),
+        (AggregationMode::Mean, -200),
+        (AggregationMode::Min, -600),
+        (AggregationMode::Max, 300),
+    ] {
+        configure_feed(&mut mock_chain, oracle_id, FeedKind::FundingRate, mode, 0).await?;
+        let (is_tracked, raw_value, _) =
+            run_get_feed(&mock_chain, oracle_id, &publisher_ids).await?;
+        assert_eq!(is_tracked, 1);
+        assert_eq!(
+            decode_value(FeedKind::FundingRate, raw_value)?,
+            expected,
+            "{mode} of the funding rates"
+        );
+    }
+
+    Ok(())
+}
+
+/// Stale values and values published with another kind are skipped, and the
+/// configured max_age replaces the default one.
+#[tokio::test]
+async fn test_oracle_get_feed_skips_stale_values_and_other_kinds() -> Result<()> {
+    let now = NOW_TS as u64;
+    let (mut mock_chain, oracle_id, publisher_ids) = setup(&[
+        feed_value(FeedKind::FundingRate, 100, now),
+        feed_value(FeedKind::FundingRate, 400, now - 7_200),
+        feed_value(FeedKind::Price, 1_000_000, now),
+    ])
+    .await?;
+
+    configure_feed(
+        &mut mock_chain,
+        oracle_id,
+        FeedKind::FundingRate,
+        AggregationMode::Mean,
+        0,
+    )
+    .await?;
+    let (is_tracked, raw_value, nb_of_values) =
+        run_get_feed(&mock_chain, oracle_id, &publisher_ids).await?;
+    assert_eq!(is_tracked, 1);
+    assert_eq!(decode_value(FeedKind::FundingRate, raw_value)?, 100);
+    assert_eq!(nb_of_values, 1, "only the fresh funding rate is aggregated");
+
+    configure_feed(
+        &mut mock_chain,
+        oracle_id,
+        FeedKind::FundingRate,
+        AggregationMode::Mean,
+        10_000,
+    )
+    .await?;
+    let (_, raw_value, nb_of_values) = run_get_feed(&mock_chain, oracle_id, &publisher_ids).await?;
+    assert_eq!(decode_value(FeedKind::FundingRate, raw_value)?, 250);
+    assert_eq!(nb_of_values, 2, "a longer max_age includes the older rate");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_oracle_get_feed_untracked_without_values() -> Result<()> {
+    let (mock_chain, oracle_id, _) = setup(&[]).await?;
+
+    let (is_tracked, value, nb_of_values) = run_get_feed(&mock_chain, oracle_id, &[]).await?;
+    assert_eq!((is_tracked, value, nb_of_values), (0, 0, 0));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_oracle_set_feed_config_fails_with_invalid_mode() -> Result<()> {
+    let (mock_chain, oracle_id, _) = setup(&[]).await?;
+
+    // Pushed form of [mode = 4, max_age = 0, kind = 0, decimals = 8].
+    let config: Word = [Felt::from(8u32), ZERO, ZERO, Felt::from(4u32)].into();
+    let result = mock_chain
+        .build_tx_context(oracle_id, &[], &[])?
+        .tx_script(set_feed_config_script(config)?)
+        .build()?
+        .execute()
+        .await;
+
+    assert_transaction_executor_error!(result, ERR_INVALID_AGGREGATION_MODE);
+
+    Ok(())
+}
@@ -0,0 +1,275 @@
+//! Publisher key rotation tests against an in-process MockChain.
+//!
+//! The publisher is built with a key the test holds, so that each transaction
+//! can be signed with the old key, the new one or both, as `pm-publisher
+//! rotate-key` would with the keystore.
+
+use anyhow::Result;
+use miden_client::account::component::{AuthScheme, AuthSingleSig};
+use miden_client::account::AccountId;
+use miden_client::auth::AuthSecretKey;
+use miden_client::crypto::rpo_falcon512::SecretKey;
+use miden_client::transaction::TransactionScript;
+use miden_protocol::account::{AccountBuilder, AccountType};
+use miden_protocol::errors::MasmError;
+use miden_protocol::transaction::ExecutedTransaction;
+use miden_protocol::{Felt, Word, ZERO};
+use miden_standards::code_builder::CodeBuilder;
+use miden_testing::{assert_transaction_executor_error, MockChain, MockChainBuilder};
+use miden_tx::auth::BasicAuthenticator;
+use miden_tx::{AuthenticationError, TransactionExecutorError, TransactionKernelError};
+
+use pm_accounts::{
+    publisher::{get_publisher_component, get_publisher_component_library},
+    utils::word_to_masm,
+};
+
+const ERR_EMPTY_KEY_COMMITMENT: MasmError =
+    MasmError::from_static_str("key commitment must not be empty");
+const ERR_NO_PROPOSED_KEY: MasmError =
+    MasmError::from_static_str("no key proposed for the rotation");
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn new_key() -> AuthSecretKey {
+    AuthSecretKey::Falcon512Poseidon2(SecretKey::new())
+}
+
+fn key_commitment(key: &AuthSecretKey) -> Word {
+    key.public_key().to_commitment().into()
+}
+
+fn publisher_script(body: &str) -> Result<TransactionScript> {
+    let tx_script_code = format!(
+        "
+        use publisher_component::publisher_module
+        use miden::core::sys
+
+        begin
+            {body}
+            exec.sys::truncate_stack
+        end
+        "
+    );
+    Ok(CodeBuilder::default()
+        .with_statically_linked_library(&get_publisher_component_library())?
+        .compile_tx_script(tx_script_code)?)
+}
+
+/// The commitment is pushed as a word literal, in the element order the auth
+/// component reads it from storage.
+fn propose_key_script(key_commitment: Word) -> Result<TransactionScript> {
+    publisher_script(&format!(
+        "push.{key_commitment}
+            call.publisher_module::propose_key"
+    ))
+}
+
+fn rotate_key_script() -> Result<TransactionScript> {
+    publisher_script("call.publisher_module::rotate_key")
+}
+
+fn publish_entry_script(price: u64) -> Result<TransactionScript> {
+    let entry: Word = [ZERO, Felt::new(price)?, Felt::new(8)?, Felt::new(1_000)?].into();
+    publisher_script(&format!(
+        "push.{entry}
+            push.0.0.0.1
+            call.publisher_module::publish_entry
+            dropw",
+        entry = word_to_masm(entry),
+    ))
+}
+
+/// Builds a chain with a publisher authenticated by `key`.
+fn setup(key: &AuthSecretKey) -> Result<(MockChain, AccountId)> {
+    let publisher = AccountBuilder::new([9; 32])
+        .account_type(AccountType::Public)
+        .with_auth_component(AuthSingleSig::new(
+            key.public_key().to_commitment(),
+            AuthScheme::Falcon512Poseidon2,
+        ))
+        .with_component(get_publisher_component())
+        .build_existing()?;
+
+    let mut builder = MockChainBuilder::new();
+    builder.add_account(publisher.clone())?;
+    Ok((builder.build()?, publisher.id()))
+}
+
+/// Executes `tx_script` against the publisher, signed with `keys`.
+async fn execute_signed_with(
+    mock_chain: &MockChain,
+    publisher_id: AccountId,
+    tx_script: TransactionScript,
+    keys: &[AuthSecretKey],
+) -> Result<ExecutedTransaction, TransactionExecutorError> {
+    mock_chain
+        .build_tx_context(publisher_id, &[], &[])
+        .expect("account should be on the chain")
+        .tx_script(tx_script)
+        .authenticator(Some(BasicAuthenticator::new(keys)))
+        .build()
+        .expect("tx context should build")
+        .execute()
+        .await
+}
+
+async fn commit(mock_chain: &mut MockChain, executed: ExecutedTransaction) -> Result<()> {
+    mock_chain.add_pending_executed_transaction(&executed)?;
+    mock_chain.prove_next_block()?;
+    Ok(())
+}
+
+/// What `pm-publisher rotate-key` does: proposes the new key with the old one,
+/// then installs it with both in the keystore.
+async fn rotate(
+    mock_chain: &mut MockChain,
+    publisher_id: AccountId,
+    old_key: &AuthSecretKey,
+    new_key: &AuthSecretKey,
+) -> Result<()> {
+    let executed = execute_signed_with(
+        mock_chain,
+        publisher_id,
+        propose_key_script(key_commitment(new_key))?,
+        std::slice::from_ref(old_key),
+    )
+    .await?;
+    commit(mock_chain, executed).await?;
+
+    let executed = execute_signed_with(
+        mock_chain,
+        publisher_id,
+        rotate_key_script()?,
+        &[old_key.clone(), new_key.clone()],
+    )
+    .await?;
+    commit(mock_chain, executed).await
+}
+
+/// Returns the commitment of the key the auth procedure asked a signature for,
+/// if `error` is the authenticator not holding that key.
+fn unknown_signing_key(error: &TransactionExecutorError) -> Option<Word> {
+    // The executor error does not expose the execution error as its source.
+    let mut source: Option<&(dyn std::error::Error + 'static)> = match error {
+        TransactionExecutorError::TransactionProgramExecutionFailed(error) => Some(error),
+        _ => None,
+    };
+    while let Some(error) = source {
+        if let Some(TransactionKernelError::SignatureGenerationFailed(
+            AuthenticationError::UnknownPublicKey(commitment),
+        )) = error.downcast_ref()
+        {
+            return Some((*commitment).into());
+        }
+        source = error.source();
+    }
+    None
+}
+
+fn auth_key_commitment(mock_chain: &MockChain, publisher_id: AccountId) -> Result<Word> {
+    let publisher = mock_chain.committed_account(publisher_id)?;
+    Ok(publisher
+        .storage()
+        .get_item(AuthSingleSig::public_key_slot())?)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_rotated_key_replaces_the_old_one() -> Result<()> {
+    let old_key = new_key();
+    let new_key = new_key();
+    let (mut mock_chain, publisher_id) = setup(&old_key)?;
+
+    rotate(&mut mock_chain, publisher_id, &old_key, &new_key).await?;
+
+    assert_eq!(
+        auth_key_commitment(&mock_chain, publisher_id)?,
+        key_commitment(&new_key),
+        "the auth component must hold the new key"
+    );
+    assert_eq!(
+        mock_chain.committed_account(publisher_id)?.id(),
+        publisher_id,
+        "the publisher must keep its account id"
+    );
+
+    let result = execute_signed_with(
+        &mock_chain,
+        publisher_id,
+        publish_entry_script(100)?,
+        std::slice::from_ref(&old_key),
+    )
+    .await
+    .expect_err("the old key must no longer publish");
+    assert_eq!(
+        unknown_signing_key(&result),
+        Some(key_commitment(&new_key)),
+        "the publisher must ask for a signature with the new key"
+    );
+
+    let executed = execute_signed_with(
+        &mock_chain,
+        publisher_id,
+        publish_entry_script(100)?,
+        std::slice::from_ref(&new_key),
+    )
+    .await?;
+    commit(&mut mock_chain, executed).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_proposing_a_key_needs_the_current_key() -> Result<()> {
+    let old_key = new_key();
+    let attacker_key = new_key();
+    let (mock_chain, publisher_id) = setup(&old_key)?;
+
+    let result = execute_signed_with(
+        &mock_chain,
+        publisher_id,
+        propose_key_script(key_commitment(&attacker_key))?,
+        std::slice::from_ref(&attacker_key),
+    )
+    .await
+    .expect_err("a key that is not the publisher's must not propose a key");
+    assert_eq!(
+        unknown_signing_key(&result),
+        Some(key_commitment(&old_key)),
+        "the publisher must ask for a signature with its current key"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rotate_needs_a_proposed_key() -> Result<()> {
+    let old_key = new_key();
+    let (mock_chain, publisher_id) = setup(&old_key)?;
+
+    let result = execute_signed_with(
+        &mock_chain,
+        publisher_id,
+        rotate_key_script()?,
+        std::slice::from_ref(&old_key),
+    )
+    .await;
+    assert_transaction_executor_error!(result, ERR_NO_PROPOSED_KEY);
+
+    let result = execute_signed_with(
+        &mock_chain,
+        publisher_id,
+        propose_key_script(Word::default())?,
+        std::slice::from_ref(&old_key),
+    )
+    .await;
+    assert_transaction_executor_error!(result, ERR_EMPTY_KEY_COMMITMENT);
+
+    Ok(())
+}
@@ -0,0 +1,296 @@
+//! Offline signing tests against an in-process MockChain.
+//!
+//! The oracle is built with a key held by a `FilesystemKeyStore`, as
+//! `pm-oracle init` does. The watch-only host executes with a
+//! `WatchOnlyKeyStore`, so that `register_publisher` stops at the signature;
+//! the exported transaction is signed with the keystore through files, and
+//! submitted with the signatures in the advice map, without authenticator.
+
+use std::path::Path;
+
+use anyhow::Result;
+use miden_client::account::component::{AuthScheme, AuthSingleSig};
+use miden_client::account::AccountId;
+use miden_client::auth::AuthSecretKey;
+use miden_client::crypto::rpo_falcon512::SecretKey;
+use miden_client::keystore::{FilesystemKeyStore, Keystore};
+use miden_client::transaction::TransactionScript;
+use miden_protocol::account::{AccountBuilder, AccountType, StorageSlotName};
+use miden_protocol::transaction::ExecutedTransaction;
+use miden_protocol::{Felt, Word, ZERO};
+use miden_standards::code_builder::CodeBuilder;
+use miden_testing::{Auth, MockChain, MockChainBuilder};
+use miden_tx::{TransactionExecutor, TransactionExecutorError};
+
+use pm_accounts::{
+    oracle::{get_oracle_component, get_oracle_component_library},
+    publisher::get_publisher_component,
+};
+use pm_types::PublisherRegistryWord;
+use pm_utils_cli::{requested_summary, SignedTransaction, UnsignedTransaction, WatchOnlyKeyStore};
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn falcon_auth() -> Auth {
+    Auth::BasicAuth {
+        auth_scheme: miden_protocol::account::auth::AuthScheme::Falcon512Poseidon2,
+    }
+}
+
+fn register_publisher_script(publisher_id: AccountId) -> Result<TransactionScript> {
+    let tx_script_code = format!(
+        "
+        use oracle_component::oracle_module
+        use miden::core::sys
+
+        begin
+            push.0.0
+            push.{suffix} push.{prefix}
+            call.oracle_module::register_publisher
+            exec.sys::truncate_stack
+        end
+        ",
+        prefix = publisher_id.prefix().as_u64(),
+        suffix = publisher_id.suffix(),
+    );
+    Ok(CodeBuilder::default()
+        .with_statically_linked_library(&get_oracle_component_library())?
+        .compile_tx_script(tx_script_code)?)
+}
+
+/// Builds a chain with an oracle, whose key is added to `keystore`, and two
+/// publishers.
+async fn setup(keystore: &FilesystemKeyStore) -> Result<(MockChain, AccountId, [AccountId; 2])> {
+    let secret_key = SecretKey::new();
+    let oracle = AccountBuilder::new([7; 32])
+        .account_type(AccountType::Public)
+        .with_auth_component(AuthSingleSig::new(
+            secret_key.public_key().to_commitment().into(),
+            AuthScheme::Falcon512Poseidon2,
+        ))
+        .with_component(get_oracle_component())
+        .build_existing()?;
+    keystore
+        .add_key(&AuthSecretKey::Falcon512Poseidon2(secret_key), oracle.id())
+        .await?;
+
+    let mut builder = MockChainBuilder::new();
+    builder.add_account(oracle.clone())?;
+    let first =
+        builder.add_existing_account_from_components(falcon_auth(), [get_publisher_component()])?;
+    let second =
+        builder.add_existing_account_from_components(falcon_auth(), [get_publisher_component()])?;
+    Ok((builder.build()?, oracle.id(), [first.id(), second.id()]))
+}
+
+/// Executes `tx_script` without authenticator, with `signatures` in the
+/// advice map.
+async fn execute_without_key(
+    mock_chain: &MockChain,
+    account_id: AccountId,
+    tx_script: TransactionScript,
+    signatures: Vec<(Word, Vec<Felt>)>,
+) -> Result<ExecutedTransaction, TransactionExecutorError> {
+    mock_chain
+        .build_tx_context(account_id, &[], &[])
+        .expect("account should be on the chain")
+        .tx_script(tx_script)
+        .extend_advice_map(signatures)
+        .authenticator(None)
+        .build()
+        .expect("tx context should build")
+        .execute()
+        .await
+}
+
+/// What `pm-oracle register-publisher --export-unsigned` writes.
+async fn export_unsigned(
+    mock_chain: &MockChain,
+    account_id: AccountId,
+    tx_script: TransactionScript,
+    path: &Path,
+) -> Result<()> {
+    let tx_context = mock_chain
+        .build_tx_context(account_id, &[], &[])?
+        .tx_script(tx_script.clone())
+        .authenticator(None)
+        .build()?;
+    let result = TransactionExecutor::new(&tx_context)
+        .with_authenticator(&WatchOnlyKeyStore)
+        .execute_transaction(
+            account_id,
+            tx_context.tx_inputs().block_header().block_num(),
+            tx_context.tx_inputs().input_notes().clone(),
+            tx_context.tx_args().clone(),
+        )
+        .await;
+    match result {
+        Ok(_) => anyhow::bail!("the transaction should need a signature"),
+        Err(e) => match requested_summary(&e) {
+            Some(summary) => UnsignedTransaction {
+                account_id,
+                tx_script,
+                summary,
+            }
+            .write(path),
+            None => Err(e.into()),
+        },
+    }
+}
+
+/// What `pm-oracle sign` does.
+async fn sign(keystore: &FilesystemKeyStore, input: &Path, output: &Path) -> Result<()> {
+    UnsignedTransaction::read(input)?
+        .sign(keystore)
+        .await?
+        .write(output)
+}
+
+/// What `pm-oracle submit` does, committing the transaction.
+async fn submit(mock_chain: &mut MockChain, input: &Path) -> Result<()> {
+    let signed = SignedTransaction::read(input)?;
+    let executed = execute_without_key(
+        mock_chain,
+        signed.account_id,
+        signed.tx_script,
+        signed.signatures,
+    )
+    .await?;
+    mock_chain.add_pending_executed_transaction(&executed)?;
+    mock_chain.prove_next_block()?;
+    Ok(())
+}
+
+fn registered_publisher(
+    mock_chain: &MockChain,
+    oracle_id: AccountId,
+    slot: u64,
+) -> Result<Option<AccountId>> {
+    let oracle = mock_chain.committed_account(oracle_id)?;
+    let publishers_slot = StorageSlotName::new("pragma::oracle::publishers").unwrap();
+    let slot_key: Word = [Felt::new(slot).unwrap(), ZERO, ZERO, ZERO].into();
+    let stored = oracle
+        .storage()
+        .get_map_item(&publishers_slot, slot_key)
+        .unwrap();
+    PublisherRegistryWord::from_word(stored).publisher_id()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_register_publisher_signed_offline() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let keystore = FilesystemKeyStore::new(dir.path().join("keystore"))?;
+    let (mut mock_chain, oracle_id, [publisher_id, _]) = setup(&keystore).await?;
+
+    let unsigned_path = dir.path().join("tx.bin");
+    let signed_path = dir.path().join("signed.bin");
+    export_unsigned(
+        &mock_chain,
+        oracle_id,
+        register_publisher_script(publisher_id)?,
+        &unsigned_path,
+    )
+    .await?;
+    sign(&keystore, &unsigned_path, &signed_path).await?;
+    submit(&mut mock_chain, &signed_path).await?;
+
+    assert_eq!(
+        registered_publisher(&mock_chain, oracle_id, 2)?,
+        Some(publisher_id),
+        "publisher slot 2 must hold the publisher registered offline"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_signature_does_not_authorize_another_transaction() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let keystore = FilesystemKeyStore::new(dir.path().join("keystore"))?;
+    let (mock_chain, oracle_id, [first, second]) = setup(&keystore).await?;
+
+    let unsigned_path = dir.path().join("tx.bin");
+    let signed_path = dir.path().join("signed.bin");
+    export_unsigned(
+        &mock_chain,
+        oracle_id,
+        register_publisher_script(first)?,
+        &unsigned_path,
+    )
+    .await?;
+    sign(&keystore, &unsigned_path, &signed_path).await?;
+
+    // The signature of the registration of `first` is no signature of the
+    // registration of `second`.
+    let signed = SignedTransaction::read(&signed_path)?;
+    let result = execute_without_key(
+        &mock_chain,
+        oracle_id,
+        register_publisher_script(second)?,
+        signed.signatures,
+    )
+    .await;
+    assert!(
+        matches!(result, Err(TransactionExecutorError::MissingAuthenticator)),
+        "registering another publisher must need another signature"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sign_needs_the_key_of_the_account() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let keystore = FilesystemKeyStore::new(dir.path().join("keystore"))?;
+    let (mock_chain, oracle_id, [publisher_id, _]) = setup(&keystore).await?;
+
+    let unsigned_path = dir.path().join("tx.bin");
+    export_unsigned(
+        &mock_chain,
+        oracle_id,
+        register_publisher_script(publisher_id)?,
+        &unsigned_path,
+    )
+    .await?;
+
+    let empty_keystore = FilesystemKeyStore::new(dir.path().join("empty_keystore"))?;
+    let signed_path = dir.path().join("signed.bin");
+    assert!(sign(&empty_keystore, &unsigned_path, &signed_path)
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_exported_files_are_not_mixed_up() -> Result<()> {
+    let dir = tempfile::tempdir()?;
+    let keystore = FilesystemKeyStore::new(dir.path().join("keystore"))?;
+    let (mock_chain, oracle_id, [publisher_id, _]) = setup(&keystore).await?;
+
+    let unsigned_path = dir.path().join("tx.bin");
+    let signed_path = dir.path().join("signed.bin");
+    export_unsigned(
+        &mock_chain,
+        oracle_id,
+        register_publisher_script(publisher_id)?,
+        &unsigned_path,
+    )
+    .await?;
+    sign(&keystore, &unsigned_path, &signed_path).await?;
+
+    assert!(SignedTransaction::read(&unsigned_path).is_err());
+    assert!(UnsignedTransaction::read(&signed_path).is_err());
+    assert_eq!(
+        SignedTransaction::read(&signed_path)?.account_id,
+        UnsignedTransaction::read(&unsigned_path)?.account_id
+    );
+
+    Ok(())
+}
@@ -15,16 +15,18 @@ use miden_protocol::account::{
     StorageSlot, StorageSlotName,
 };
 use miden_protocol::errors::MasmError;
+use miden_protocol::transaction::RawOutputNote;
 use miden_protocol::{Felt, Word, ZERO};
 use miden_standards::code_builder::CodeBuilder;
 use miden_testing::{assert_transaction_executor_error, Auth, MockChain, MockChainBuilder};
 
 use pm_accounts::{
+    notes::{create_leave_note, create_registration_request_note},
     oracle::{get_oracle_component, get_oracle_component_library},
     publisher::{get_publisher_component, get_publisher_component_library},
     utils::word_to_masm,
 };
-use pm_types::{Currency, Entry, Pair};
+use pm_types::{Currency, Entry, Pair, PublisherRegistryWord};
 
 // ============================================================================
 // Helpers
@@ -34,6 +36,12 @@ const ERR_PUBLISHER_ALREADY_REGISTERED: MasmError =
     MasmError::from_static_str("publisher already registered");
 const ERR_PUBLISHER_NOT_REGISTERED: MasmError =
     MasmError::from_static_str("publisher not registered");
+/// Kernel error raised when the active note is read outside of a note context.
+const ERR_INPUT_NOTE_INDEX_OUT_OF_BOUNDS: MasmError = MasmError::from_static_str(
+    "requested input note index should be less than the total number of input notes",
+);
+const ERR_LEAVE_NOTE_WRONG_ORACLE: MasmError =
+    MasmError::from_static_str("leave note can only be consumed by its target oracle");
 
 fn falcon_auth() -> Auth {
     Auth::BasicAuth {
@@ -46,12 +54,18 @@ fn falcon_auth() -> Auth {
 /// needing the publisher to run a separate publish_entry tx.
 fn publisher_component_with_entry(pair: Word, entry: Word) -> AccountComponent {
     let library = (*get_publisher_component_library()).clone();
-    let storage_slot = StorageSlot::with_map(
-        StorageSlotName::new("pragma::publisher::entries").unwrap(),
-        StorageMap::with_entries(vec![(StorageMapKey::new(pair), entry)]).unwrap(),
-    );
+    let storage_slots = vec![
+        StorageSlot::with_map(
+            StorageSlotName::new("pragma::publisher::entries").unwrap(),
+            StorageMap::with_entries(vec![(StorageMapKey::new(pair), entry)]).unwrap(),
+        ),
+        StorageSlot::with_value(
+            StorageSlotName::new("pragma::publisher::last_heartbeat").unwrap(),
+            [ZERO, ZERO, ZERO, ZERO].into(),
+        ),
+    ];
     let metadata = AccountComponentMetadata::new("pragma::publisher");
-    AccountComponent::new(library, vec![storage_slot], metadata)
+    AccountComponent::new(library, storage_slots, metadata)
         .expect("publisher component should assemble")
 }
 
@@ -97,6 +111,21 @@ fn remove_publisher_script(publisher_id: AccountId) -> Result<TransactionScript>
         .compile_tx_script(tx_script_code)?)
 }
 
+fn deregister_publisher_script() -> Result<TransactionScript> {
+    let tx_script_code = "
+        use oracle_component::oracle_module
+        use miden::core::sys
+
+        begin
+            call.oracle_module::deregister_publisher
+            exec.sys::truncate_stack
+        end
+        ";
+    Ok(CodeBuilder::default()
+        .with_statically_linked_library(&get_oracle_component_library())?
+        .compile_tx_script(tx_script_code)?)
+}
+
 fn get_median_script(pair_word: Word) -> Result<TransactionScript> {
     let tx_script_code = format!(
         "
@@ -138,6 +167,10 @@ fn onchain_entry(price: u64, decimals: u64, timestamp: u64) -> Word {
     .into()
 }
 
+fn serial_num(seed: u64) -> Word {
+    [Felt::new(seed).unwrap(), ZERO, ZERO, ZERO].into()
+}
+
 fn onchain_faucet_key(prefix: u64, suffix: u64) -> Word {
     [
         Felt::new(prefix).unwrap(),
@@ -243,14 +276,8 @@ async fn test_oracle_register_publisher() -> Result<()> {
         .get_map_item(&publishers_slot, slot_key)
         .unwrap();
     assert_eq!(
-        stored,
-        [
-            publisher_id.prefix().as_felt(),
-            publisher_id.suffix(),
-            ZERO,
-            ZERO,
-        ]
-        .into(),
+        PublisherRegistryWord::from_word(stored).publisher_id()?,
+        Some(publisher_id),
         "publisher slot 2 must hold the registered id"
     );
 
@@ -336,9 +363,8 @@ async fn test_oracle_remove_publisher() -> Result<()> {
         .storage()
         .get_map_item(&publishers_slot, slot_key)
         .unwrap();
-    assert_eq!(
-        stored,
-        [ZERO, ZERO, ZERO, ZERO].into(),
+    assert!(
+        PublisherRegistryWord::from_word(stored).is_removed(),
         "publisher slot 2 must be zeroed after remove_publisher"
     );
 
@@ -379,7 +405,7 @@ async fn test_oracle_remove_publisher_fails_if_not_registered() -> Result<()> {
 /// median value across the soft-delete.
 #[tokio::test]
 async fn test_oracle_get_median_skips_soft_deleted_publishers() -> Result<()> {
-    let pair_word = btc_usd_pair()?.to_word();
+    let pair_word = btc_usd_pair()?.to_word()?;
     let entry1 = Entry {
         faucet_id: "1:0".to_string(),
         price: 50_000_000_000,
@@ -577,3 +603,235 @@ async fn test_oracle_get_median_skips_stale_entry() -> Result<()> {
 
     Ok(())
 }
+
+// ============================================================================
+// Tests: self-service deregistration via leave notes
+// ============================================================================
+
+/// A publisher leaves by sending a leave note: once the oracle consumes it, the
+/// sender's slot is zeroed and the other publishers are left untouched.
+#[tokio::test]
+async fn test_oracle_publisher_leaves_with_note() -> Result<()> {
+    let mut builder = MockChainBuilder::new();
+    let oracle =
+        builder.add_existing_account_from_components(falcon_auth(), [get_oracle_component()])?;
+    let pub_a =
+        builder.add_existing_account_from_components(falcon_auth(), [get_publisher_component()])?;
+    let pub_b =
+        builder.add_existing_account_from_components(falcon_auth(), [get_publisher_component()])?;
+    let leave_note = create_leave_note(pub_a.id(), oracle.id(), serial_num(1))?;
+    builder.add_output_note(RawOutputNote::Full(leave_note.clone()));
+    let mut mock_chain = builder.build()?;
+
+    for publisher_id in [pub_a.id(), pub_b.id()] {
+        let tx = mock_chain
+            .build_tx_context(oracle.id(), &[], &[])?
+            .tx_script(register_publisher_script(publisher_id)?)
+            .build()?;
+        let executed = tx.execute().await?;
+        mock_chain.add_pending_executed_transaction(&executed)?;
+        mock_chain.prove_next_block()?;
+    }
+
+    let consume_tx = mock_chain
+        .build_tx_context(oracle.id(), &[leave_note.id()], &[])?
+        .build()?;
+    let consume_executed = consume_tx.execute().await?;
+
+    let mut oracle = mock_chain.committed_account(oracle.id())?.clone();
+    oracle.apply_delta(consume_executed.account_delta())?;
+
+    let publishers_slot = StorageSlotName::new("pragma::oracle::publishers").unwrap();
+    let slot_a: Word = [Felt::new(2).unwrap(), ZERO, ZERO, ZERO].into();
+    let slot_b: Word = [Felt::new(3).unwrap(), ZERO, ZERO, ZERO].into();
+    assert_eq!(
+        oracle
+            .storage()
+            .get_map_item(&publishers_slot, slot_a)
+            .unwrap(),
+        [ZERO, ZERO, ZERO, ZERO].into(),
+        "the leaving publisher's slot must be zeroed"
+    );
+    assert_eq!(
+        oracle
+            .storage()
+            .get_map_item(&publishers_slot, slot_b)
+            .unwrap(),
+        PublisherRegistryWord::from(pub_b.id()).word(),
+        "other publishers must stay registered"
+    );
+
+    Ok(())
+}
+
+/// A leave note only ever removes its own sender: an account that is not
+/// registered cannot use it to remove a registered publisher.
+#[tokio::test]
+async fn test_oracle_leave_note_from_unregistered_sender_fails() -> Result<()> {
+    let mut builder = MockChainBuilder::new();
+    let oracle =
+        builder.add_existing_account_from_components(falcon_auth(), [get_oracle_component()])?;
+    let publisher =
+        builder.add_existing_account_from_components(falcon_auth(), [get_publisher_component()])?;
+    let intruder =
+        builder.add_existing_account_from_components(falcon_auth(), [get_publisher_component()])?;
+    let leave_note = create_leave_note(intruder.id(), oracle.id(), serial_num(1))?;
+    builder.add_output_note(RawOutputNote::Full(leave_note.clone()));
+    let mut mock_chain = builder.build()?;
+
+    let tx = mock_chain
+        .build_tx_context(oracle.id(), &[], &[])?
+        .tx_script(register_publisher_script(publisher.id())?)
+        .build()?;
+    let executed = tx.execute().await?;
+    mock_chain.add_pending_executed_transaction(&executed)?;
+    mock_chain.prove_next_block()?;
+
+    let consume_tx = mock_chain
+        .build_tx_context(oracle.id(), &[leave_note.id()], &[])?
+        .build()?;
+    let result = consume_tx.execute().await;
+
+    assert_transaction_executor_error!(result, ERR_PUBLISHER_NOT_REGISTERED);
+
+    Ok(())
+}
+
+/// `deregister_publisher` reads the publisher id from the note sender, so it
+/// cannot be invoked from a transaction script to remove an arbitrary id.
+#[tokio::test]
+async fn test_oracle_deregister_publisher_requires_note() -> Result<()> {
+    let mut builder = MockChainBuilder::new();
+    let oracle =
+        builder.add_existing_account_from_components(falcon_auth(), [get_oracle_component()])?;
+    let publisher =
+        builder.add_existing_account_from_components(falcon_auth(), [get_publisher_component()])?;
+    let mut mock_chain = builder.build()?;
+
+    let tx = mock_chain
+        .build_tx_context(oracle.id(), &[], &[])?
+        .tx_script(register_publisher_script(publisher.id())?)
+        .build()?;
+    let executed = tx.execute().await?;
+    mock_chain.add_pending_executed_transaction(&executed)?;
+    mock_chain.prove_next_block()?;
+
+    let tx = mock_chain
+        .build_tx_context(oracle.id(), &[], &[])?
+        .tx_script(deregister_publisher_script()?)
+        .build()?;
+    let result = tx.execute().await;
+
+    // The note sender cannot be read outside of a note context.
+    assert_transaction_executor_error!(result, ERR_INPUT_NOTE_INDEX_OUT_OF_BOUNDS);
+
+    Ok(())
+}
+
+/// A leave note is bound to the oracle it targets and cannot be consumed by
+/// another oracle account.
+#[tokio::test]
+async fn test_oracle_leave_note_rejected_by_other_oracle() -> Result<()> {
+    let mut builder = MockChainBuilder::new();
+    let oracle =
+        builder.add_existing_account_from_components(falcon_auth(), [get_oracle_component()])?;
+    let other_oracle =
+        builder.add_existing_account_from_components(falcon_auth(), [get_oracle_component()])?;
+    let publisher =
+        builder.add_existing_account_from_components(falcon_auth(), [get_publisher_component()])?;
+    let leave_note = create_leave_note(publisher.id(), oracle.id(), serial_num(1))?;
+    builder.add_output_note(RawOutputNote::Full(leave_note.clone()));
+    let mut mock_chain = builder.build()?;
+
+    let tx = mock_chain
+        .build_tx_context(other_oracle.id(), &[], &[])?
+        .tx_script(register_publisher_script(publisher.id())?)
+        .build()?;
+    let executed = tx.execute().await?;
+    mock_chain.add_pending_executed_transaction(&executed)?;
+    mock_chain.prove_next_block()?;
+
+    let consume_tx = mock_chain
+        .build_tx_context(other_oracle.id(), &[leave_note.id()], &[])?
+        .build()?;
+    let result = consume_tx.execute().await;
+
+    assert_transaction_executor_error!(result, ERR_LEAVE_NOTE_WRONG_ORACLE);
+
+    Ok(())
+}
+
+// ============================================================================
+// Tests: note-based registration requests
+// ============================================================================
+
+/// Consuming a registration request registers its sender in the next slot.
+#[tokio::test]
+async fn test_oracle_accepts_registration_request() -> Result<()> {
+    let mut builder = MockChainBuilder::new();
+    let oracle =
+        builder.add_existing_account_from_components(falcon_auth(), [get_oracle_component()])?;
+    let publisher =
+        builder.add_existing_account_from_components(falcon_auth(), [get_publisher_component()])?;
+    let request = create_registration_request_note(publisher.id(), oracle.id(), serial_num(1))?;
+    builder.add_output_note(RawOutputNote::Full(request.clone()));
+    let mock_chain = builder.build()?;
+
+    let consume_tx = mock_chain
+        .build_tx_context(oracle.id(), &[request.id()], &[])?
+        .build()?;
+    let executed_tx = consume_tx.execute().await?;
+
+    let mut oracle = oracle.clone();
+    oracle.apply_delta(executed_tx.account_delta())?;
+
+    let next_index_slot = StorageSlotName::new("pragma::oracle::next_publisher_index").unwrap();
+    assert_eq!(
+        oracle.storage().get_item(&next_index_slot).unwrap(),
+        [Felt::new(3).unwrap(), ZERO, ZERO, ZERO].into(),
+        "next_publisher_index must advance from 2 to 3"
+    );
+
+    let publishers_slot = StorageSlotName::new("pragma::oracle::publishers").unwrap();
+    let slot_key: Word = [Felt::new(2).unwrap(), ZERO, ZERO, ZERO].into();
+    assert_eq!(
+        oracle
+            .storage()
+            .get_map_item(&publishers_slot, slot_key)
+            .unwrap(),
+        PublisherRegistryWord::from(publisher.id()).word(),
+        "publisher slot 2 must hold the requesting publisher"
+    );
+
+    Ok(())
+}
+
+/// A registration request from an already registered publisher is rejected.
+#[tokio::test]
+async fn test_oracle_registration_request_fails_if_already_registered() -> Result<()> {
+    let mut builder = MockChainBuilder::new();
+    let oracle =
+        builder.add_existing_account_from_components(falcon_auth(), [get_oracle_component()])?;
+    let publisher =
+        builder.add_existing_account_from_components(falcon_auth(), [get_publisher_component()])?;
+    let request = create_registration_request_note(publisher.id(), oracle.id(), serial_num(1))?;
+    builder.add_output_note(RawOutputNote::Full(request.clone()));
+    let mut mock_chain = builder.build()?;
+
+    let tx = mock_chain
+        .build_tx_context(oracle.id(), &[], &[])?
+        .tx_script(register_publisher_script(publisher.id())?)
+        .build()?;
+    let executed = tx.execute().await?;
+    mock_chain.add_pending_executed_transaction(&executed)?;
+    mock_chain.prove_next_block()?;
+
+    let consume_tx = mock_chain
+        .build_tx_context(oracle.id(), &[request.id()], &[])?
+        .build()?;
+    let result = consume_tx.execute().await;
+
+    assert_transaction_executor_error!(result, ERR_PUBLISHER_ALREADY_REGISTERED);
+
+    Ok(())
+}
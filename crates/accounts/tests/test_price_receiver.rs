@@ -0,0 +1,186 @@
+//! Price-push tests against an in-process MockChain.
+//!
+//! Each test builds a fresh chain with an oracle and a consumer account
+//! carrying the price receiver component, seeds the chain with price-push
+//! notes, and consumes them from the consumer account.
+
+use anyhow::Result;
+use miden_protocol::account::{auth::AuthScheme, StorageSlotName};
+use miden_protocol::errors::MasmError;
+use miden_protocol::transaction::RawOutputNote;
+use miden_protocol::{Felt, Word, ZERO};
+use miden_testing::{assert_transaction_executor_error, Auth, MockChainBuilder};
+
+use pm_accounts::{
+    notes::{create_price_push_note, PriceSnapshot},
+    oracle::get_oracle_component,
+    receiver::get_price_receiver_component,
+};
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+const ERR_PRICE_NOTE_UNTRUSTED_SENDER: MasmError =
+    MasmError::from_static_str("price snapshot was not sent by the trusted oracle");
+const ERR_PRICE_SNAPSHOT_OUTDATED: MasmError =
+    MasmError::from_static_str("price snapshot is older than the stored one");
+
+fn falcon_auth() -> Auth {
+    Auth::BasicAuth {
+        auth_scheme: AuthScheme::Falcon512Poseidon2,
+    }
+}
+
+fn serial_num(seed: u64) -> Word {
+    [Felt::new(seed).unwrap(), ZERO, ZERO, ZERO].into()
+}
+
+fn btc_snapshot(median: u64, timestamp: u64) -> PriceSnapshot {
+    PriceSnapshot {
+        faucet_id_prefix: 1,
+        faucet_id_suffix: 0,
+        median,
+        decimals: 8,
+        timestamp,
+    }
+}
+
+fn prices_slot() -> StorageSlotName {
+    StorageSlotName::new("pragma::price_receiver::prices").unwrap()
+}
+
+fn btc_key() -> Word {
+    [Felt::new(1).unwrap(), ZERO, ZERO, ZERO].into()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_receiver_stores_price_pushed_by_oracle() -> Result<()> {
+    let mut builder = MockChainBuilder::new();
+    let oracle =
+        builder.add_existing_account_from_components(falcon_auth(), [get_oracle_component()])?;
+    let consumer = builder.add_existing_account_from_components(
+        falcon_auth(),
+        [get_price_receiver_component(oracle.id())],
+    )?;
+    let note = create_price_push_note(
+        oracle.id(),
+        consumer.id(),
+        &btc_snapshot(50_000_000_000, 1_000),
+        serial_num(1),
+    )?;
+    builder.add_output_note(RawOutputNote::Full(note.clone()));
+    let mut mock_chain = builder.build()?;
+    mock_chain.prove_next_block()?;
+
+    let executed = mock_chain
+        .build_tx_context(consumer.id(), &[note.id()], &[])?
+        .build()?
+        .execute()
+        .await?;
+
+    let mut consumer = consumer.clone();
+    consumer.apply_delta(executed.account_delta())?;
+
+    assert_eq!(
+        consumer
+            .storage()
+            .get_map_item(&prices_slot(), btc_key())
+            .unwrap(),
+        [
+            Felt::new(1_000)?,
+            Felt::new(8)?,
+            Felt::new(50_000_000_000)?,
+            ZERO
+        ]
+        .into(),
+        "the snapshot must be stored with the publisher entry layout"
+    );
+
+    Ok(())
+}
+
+/// A note carrying a snapshot is only trusted if the oracle created it: any
+/// other sender is rejected, even with a well-formed snapshot.
+#[tokio::test]
+async fn test_receiver_rejects_price_from_untrusted_sender() -> Result<()> {
+    let mut builder = MockChainBuilder::new();
+    let oracle =
+        builder.add_existing_account_from_components(falcon_auth(), [get_oracle_component()])?;
+    let impostor =
+        builder.add_existing_account_from_components(falcon_auth(), [get_oracle_component()])?;
+    let consumer = builder.add_existing_account_from_components(
+        falcon_auth(),
+        [get_price_receiver_component(oracle.id())],
+    )?;
+    let note = create_price_push_note(
+        impostor.id(),
+        consumer.id(),
+        &btc_snapshot(1, 1_000),
+        serial_num(1),
+    )?;
+    builder.add_output_note(RawOutputNote::Full(note.clone()));
+    let mut mock_chain = builder.build()?;
+    mock_chain.prove_next_block()?;
+
+    let result = mock_chain
+        .build_tx_context(consumer.id(), &[note.id()], &[])?
+        .build()?
+        .execute()
+        .await;
+
+    assert_transaction_executor_error!(result, ERR_PRICE_NOTE_UNTRUSTED_SENDER);
+
+    Ok(())
+}
+
+/// Notes can be consumed in any order: an older snapshot consumed after a
+/// newer one must not overwrite it.
+#[tokio::test]
+async fn test_receiver_rejects_outdated_snapshot() -> Result<()> {
+    let mut builder = MockChainBuilder::new();
+    let oracle =
+        builder.add_existing_account_from_components(falcon_auth(), [get_oracle_component()])?;
+    let consumer = builder.add_existing_account_from_components(
+        falcon_auth(),
+        [get_price_receiver_component(oracle.id())],
+    )?;
+    let newer = create_price_push_note(
+        oracle.id(),
+        consumer.id(),
+        &btc_snapshot(51_000_000_000, 2_000),
+        serial_num(1),
+    )?;
+    let older = create_price_push_note(
+        oracle.id(),
+        consumer.id(),
+        &btc_snapshot(50_000_000_000, 1_000),
+        serial_num(2),
+    )?;
+    builder.add_output_note(RawOutputNote::Full(newer.clone()));
+    builder.add_output_note(RawOutputNote::Full(older.clone()));
+    let mut mock_chain = builder.build()?;
+    mock_chain.prove_next_block()?;
+
+    let executed = mock_chain
+        .build_tx_context(consumer.id(), &[newer.id()], &[])?
+        .build()?
+        .execute()
+        .await?;
+    mock_chain.add_pending_executed_transaction(&executed)?;
+    mock_chain.prove_next_block()?;
+
+    let result = mock_chain
+        .build_tx_context(consumer.id(), &[older.id()], &[])?
+        .build()?
+        .execute()
+        .await;
+
+    assert_transaction_executor_error!(result, ERR_PRICE_SNAPSHOT_OUTDATED);
+
+    Ok(())
+}
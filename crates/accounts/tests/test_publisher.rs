@@ -0,0 +1,296 @@
+//! Publisher component tests against an in-process MockChain.
+//!
+//! Each test builds a fresh chain with a single publisher account, runs a
+//! publisher tx script through `execute()` and inspects the resulting account
+//! delta.
+
+use anyhow::Result;
+use miden_client::transaction::TransactionScript;
+use miden_protocol::account::{auth::AuthScheme, Account, StorageSlotName};
+use miden_protocol::{Felt, Word, ZERO};
+use miden_standards::code_builder::CodeBuilder;
+use miden_testing::{Auth, MockChain, MockChainBuilder};
+
+use pm_accounts::{
+    publisher::{get_publisher_component, get_publisher_component_library},
+    utils::word_to_masm,
+};
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn falcon_auth() -> Auth {
+    Auth::BasicAuth {
+        auth_scheme: AuthScheme::Falcon512Poseidon2,
+    }
+}
+
+fn publish_entry_script(
+    prefix: u64,
+    suffix: u64,
+    price: u64,
+    decimals: u64,
+    timestamp: u64,
+) -> Result<TransactionScript> {
+    let entry: Word = [
+        ZERO,
+        Felt::new(price)?,
+        Felt::new(decimals)?,
+        Felt::new(timestamp)?,
+    ]
+    .into();
+    let tx_script_code = format!(
+        "
+        use publisher_component::publisher_module
+        use miden::core::sys
+
+        begin
+            push.{entry}
+            push.0.0.{suffix}.{prefix}
+            call.publisher_module::publish_entry
+            dropw
+            exec.sys::truncate_stack
+        end
+        ",
+        entry = word_to_masm(entry),
+    );
+    Ok(CodeBuilder::default()
+        .with_statically_linked_library(&get_publisher_component_library())?
+        .compile_tx_script(tx_script_code)?)
+}
+
+fn heartbeat_script() -> Result<TransactionScript> {
+    let tx_script_code = "
+        use publisher_component::publisher_module
+        use miden::core::sys
+
+        begin
+            call.publisher_module::heartbeat
+            exec.sys::truncate_stack
+        end
+        ";
+    Ok(CodeBuilder::default()
+        .with_statically_linked_library(&get_publisher_component_library())?
+        .compile_tx_script(tx_script_code)?)
+}
+
+/// Publishes an entry for faucet `1:0` and commits the transaction.
+async fn publish_and_commit(
+    mock_chain: &mut MockChain,
+    publisher: &mut Account,
+    price: u64,
+    timestamp: u64,
+) -> Result<()> {
+    let tx = mock_chain
+        .build_tx_context(publisher.id(), &[], &[])?
+        .tx_script(publish_entry_script(1, 0, price, 8, timestamp)?)
+        .build()?;
+    let executed = tx.execute().await?;
+    publisher.apply_delta(executed.account_delta())?;
+    mock_chain.add_pending_executed_transaction(&executed)?;
+    mock_chain.prove_next_block()?;
+    Ok(())
+}
+
+fn history_count(publisher: &Account) -> u64 {
+    let slot = StorageSlotName::new("pragma::publisher::history_counts").unwrap();
+    let key: Word = [Felt::new(1).unwrap(), ZERO, ZERO, ZERO].into();
+    publisher.storage().get_map_item(&slot, key).unwrap()[0].as_canonical_u64()
+}
+
+fn history_price(publisher: &Account, position: u64) -> u64 {
+    let slot = StorageSlotName::new("pragma::publisher::history").unwrap();
+    let key: Word = [
+        Felt::new(1).unwrap(),
+        ZERO,
+        Felt::new(position).unwrap(),
+        ZERO,
+    ]
+    .into();
+    publisher.storage().get_map_item(&slot, key).unwrap()[2].as_canonical_u64()
+}
+
+fn last_heartbeat_slot() -> StorageSlotName {
+    StorageSlotName::new("pragma::publisher::last_heartbeat").unwrap()
+}
+
+// ============================================================================
+// Tests: heartbeat
+// ============================================================================
+
+#[tokio::test]
+async fn test_publisher_heartbeat_records_block_timestamp() -> Result<()> {
+    const NOW_TS: u32 = 2_000_000_000;
+
+    let mut builder = MockChainBuilder::new();
+    let publisher =
+        builder.add_existing_account_from_components(falcon_auth(), [get_publisher_component()])?;
+    let mut mock_chain = builder.build()?;
+    mock_chain.prove_next_block_at(NOW_TS)?;
+
+    assert_eq!(
+        publisher
+            .storage()
+            .get_item(&last_heartbeat_slot())
+            .unwrap(),
+        [ZERO, ZERO, ZERO, ZERO].into(),
+        "a fresh publisher has never sent a heartbeat"
+    );
+
+    let tx_context = mock_chain
+        .build_tx_context(publisher.id(), &[], &[])?
+        .tx_script(heartbeat_script()?)
+        .build()?;
+    let executed_tx = tx_context.execute().await?;
+
+    let mut publisher = publisher.clone();
+    publisher.apply_delta(executed_tx.account_delta())?;
+
+    assert_eq!(
+        publisher
+            .storage()
+            .get_item(&last_heartbeat_slot())
+            .unwrap(),
+        [Felt::from(NOW_TS), ZERO, ZERO, ZERO].into(),
+        "heartbeat must store the reference block timestamp"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_publisher_publish_entry_refreshes_heartbeat() -> Result<()> {
+    const NOW_TS: u32 = 2_000_000_000;
+
+    let mut builder = MockChainBuilder::new();
+    let publisher =
+        builder.add_existing_account_from_components(falcon_auth(), [get_publisher_component()])?;
+    let mut mock_chain = builder.build()?;
+    mock_chain.prove_next_block_at(NOW_TS)?;
+
+    let tx_context = mock_chain
+        .build_tx_context(publisher.id(), &[], &[])?
+        .tx_script(publish_entry_script(
+            1,
+            0,
+            50_000_000_000,
+            8,
+            NOW_TS as u64,
+        )?)
+        .build()?;
+    let executed_tx = tx_context.execute().await?;
+
+    let mut publisher = publisher.clone();
+    publisher.apply_delta(executed_tx.account_delta())?;
+
+    assert_eq!(
+        publisher
+            .storage()
+            .get_item(&last_heartbeat_slot())
+            .unwrap(),
+        [Felt::from(NOW_TS), ZERO, ZERO, ZERO].into(),
+        "publish_entry must refresh the heartbeat implicitly"
+    );
+
+    let entries_slot = StorageSlotName::new("pragma::publisher::entries").unwrap();
+    let key: Word = [Felt::new(1)?, ZERO, ZERO, ZERO].into();
+    assert_eq!(
+        publisher
+            .storage()
+            .get_map_item(&entries_slot, key)
+            .unwrap(),
+        [
+            Felt::from(NOW_TS),
+            Felt::new(8)?,
+            Felt::new(50_000_000_000)?,
+            ZERO,
+        ]
+        .into(),
+        "publish_entry must still store the entry"
+    );
+
+    Ok(())
+}
+
+// ============================================================================
+// Tests: history
+// ============================================================================
+
+#[tokio::test]
+async fn test_publisher_publish_entry_appends_to_history() -> Result<()> {
+    let mut builder = MockChainBuilder::new();
+    let mut publisher =
+        builder.add_existing_account_from_components(falcon_auth(), [get_publisher_component()])?;
+    let mut mock_chain = builder.build()?;
+
+    for price in [100, 101, 102] {
+        publish_and_commit(&mut mock_chain, &mut publisher, price, 1).await?;
+    }
+
+    assert_eq!(history_count(&publisher), 3);
+    assert_eq!(history_price(&publisher, 0), 100);
+    assert_eq!(history_price(&publisher, 1), 101);
+    assert_eq!(history_price(&publisher, 2), 102);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_publisher_history_wraps_around() -> Result<()> {
+    const HISTORY_LEN: u64 = 16;
+
+    let mut builder = MockChainBuilder::new();
+    let mut publisher =
+        builder.add_existing_account_from_components(falcon_auth(), [get_publisher_component()])?;
+    let mut mock_chain = builder.build()?;
+
+    for price in 0..HISTORY_LEN + 2 {
+        publish_and_commit(&mut mock_chain, &mut publisher, 1_000 + price, 1).await?;
+    }
+
+    assert_eq!(history_count(&publisher), HISTORY_LEN + 2);
+    // The two latest entries overwrote the two oldest ones.
+    assert_eq!(history_price(&publisher, 0), 1_000 + HISTORY_LEN);
+    assert_eq!(history_price(&publisher, 1), 1_000 + HISTORY_LEN + 1);
+    assert_eq!(history_price(&publisher, 2), 1_002);
+
+    // get_history_entry walks back from the last entry and returns the zero
+    // word past the kept history. Entries are [timestamp, decimals, price, 0].
+    let tx_script_code = format!(
+        "
+        use publisher_component::publisher_module
+        use miden::core::sys
+
+        begin
+            push.0.0.0.1
+            call.publisher_module::get_history_entry
+            drop drop push.{last} assert_eq.err=\"wrong last entry\" drop
+
+            push.0.{oldest_lag}.0.1
+            call.publisher_module::get_history_entry
+            drop drop push.1002 assert_eq.err=\"wrong oldest entry\" drop
+
+            push.0.{past_lag}.0.1
+            call.publisher_module::get_history_entry
+            padw assert_eqw.err=\"entry past the history must be empty\"
+
+            exec.sys::truncate_stack
+        end
+        ",
+        last = 1_000 + HISTORY_LEN + 1,
+        oldest_lag = HISTORY_LEN - 1,
+        past_lag = HISTORY_LEN,
+    );
+    let script = CodeBuilder::default()
+        .with_statically_linked_library(&get_publisher_component_library())?
+        .compile_tx_script(tx_script_code)?;
+    mock_chain
+        .build_tx_context(publisher.id(), &[], &[])?
+        .tx_script(script)
+        .build()?
+        .execute()
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,457 @@
+//! Commit-reveal randomness tests against an in-process MockChain.
+//!
+//! Publishers commit to and reveal their randomness through
+//! `publish_randomness`, and the combined randomness is read through
+//! `execute_code` (invoking `get_randomness` by MAST root), as in
+//! `test_feeds.rs`.
+
+use anyhow::Result;
+use miden_client::account::AccountId;
+use miden_client::transaction::TransactionScript;
+use miden_protocol::account::{auth::AuthScheme, StorageSlotName};
+use miden_protocol::errors::MasmError;
+use miden_protocol::{Felt, Word, ZERO};
+use miden_standards::code_builder::CodeBuilder;
+use miden_testing::{assert_transaction_executor_error, Auth, MockChain, MockChainBuilder};
+
+use pm_accounts::{
+    oracle::{get_oracle_component, get_oracle_component_library},
+    publisher::{get_publisher_component, get_publisher_component_library, randomness_commitment},
+    utils::word_to_masm,
+};
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+const ERR_EMPTY_RANDOMNESS_COMMITMENT: MasmError =
+    MasmError::from_static_str("randomness commitment must not be empty");
+const ERR_RANDOMNESS_ALREADY_COMMITTED: MasmError =
+    MasmError::from_static_str("randomness already committed for the round");
+const ERR_RANDOMNESS_NOT_COMMITTED: MasmError =
+    MasmError::from_static_str("randomness not committed for the round");
+const ERR_RANDOMNESS_COMMITMENT_MISMATCH: MasmError =
+    MasmError::from_static_str("randomness does not match its commitment");
+const ERR_RANDOMNESS_ALREADY_REVEALED: MasmError =
+    MasmError::from_static_str("randomness already revealed for the round");
+
+const ROUND: u64 = 42;
+
+fn falcon_auth() -> Auth {
+    Auth::BasicAuth {
+        auth_scheme: AuthScheme::Falcon512Poseidon2,
+    }
+}
+
+/// A distinct non-zero randomness per seed.
+fn secret(seed: u64) -> Word {
+    [
+        Felt::new(seed).unwrap(),
+        Felt::new(seed + 1).unwrap(),
+        Felt::new(seed + 2).unwrap(),
+        Felt::new(seed + 3).unwrap(),
+    ]
+    .into()
+}
+
+/// Word to push so that `word` ends up on the stack as stored.
+fn push_form(word: Word) -> Word {
+    let mut elements: [Felt; 4] = word.into();
+    elements.reverse();
+    elements.into()
+}
+
+/// Key of a round in the publisher randomness maps: `[round, 0, 0, 0]`.
+fn round_key(round: u64) -> Word {
+    [Felt::new(round).unwrap(), ZERO, ZERO, ZERO].into()
+}
+
+fn publish_randomness_script(
+    round: u64,
+    value: Word,
+    commitment: Word,
+) -> Result<TransactionScript> {
+    let tx_script_code = format!(
+        "
+        use publisher_component::publisher_module
+        use miden::core::sys
+
+        begin
+            push.{commitment}
+            push.{value}
+            push.{round}
+            call.publisher_module::publish_randomness
+            exec.sys::truncate_stack
+        end
+        ",
+        commitment = word_to_masm(push_form(commitment)),
+        value = word_to_masm(push_form(value)),
+    );
+    Ok(CodeBuilder::default()
+        .with_statically_linked_library(&get_publisher_component_library())?
+        .compile_tx_script(tx_script_code)?)
+}
+
+fn commit_script(round: u64, value: Word) -> Result<TransactionScript> {
+    publish_randomness_script(round, Word::default(), randomness_commitment(value))
+}
+
+fn reveal_script(round: u64, value: Word) -> Result<TransactionScript> {
+    publish_randomness_script(round, value, randomness_commitment(value))
+}
+
+fn register_publisher_script(publisher_id: AccountId) -> Result<TransactionScript> {
+    let tx_script_code = format!(
+        "
+        use oracle_component::oracle_module
+        use miden::core::sys
+
+        begin
+            push.0.0
+            push.{suffix} push.{prefix}
+            call.oracle_module::register_publisher
+            exec.sys::truncate_stack
+        end
+        ",
+        prefix = publisher_id.prefix().as_u64(),
+        suffix = publisher_id.suffix(),
+    );
+    Ok(CodeBuilder::default()
+        .with_statically_linked_library(&get_oracle_component_library())?
+        .compile_tx_script(tx_script_code)?)
+}
+
+async fn execute_and_commit(
+    mock_chain: &mut MockChain,
+    account_id: AccountId,
+    script: TransactionScript,
+) -> Result<()> {
+    let tx = mock_chain
+        .build_tx_context(account_id, &[], &[])?
+        .tx_script(script)
+        .build()?;
+    let executed = tx.execute().await?;
+    mock_chain.add_pending_executed_transaction(&executed)?;
+    mock_chain.prove_next_block()?;
+    Ok(())
+}
+
+/// Builds a chain with a single publisher.
+fn setup_publisher() -> Result<(MockChain, AccountId)> {
+    let mut builder = MockChainBuilder::new();
+    let publisher =
+        builder.add_existing_account_from_components(falcon_auth(), [get_publisher_component()])?;
+    Ok((builder.build()?, publisher.id()))
+}
+
+/// Builds a chain with an oracle and one registered publisher per
+/// `(secret, reveals)`, each publisher having committed to its secret for
+/// ROUND and revealed it if `reveals` is true.
+async fn setup(publishers: &[(Word, bool)]) -> Result<(MockChain, AccountId, Vec<AccountId>)> {
+    let mut builder = MockChainBuilder::new();
+    let publisher_ids = publishers
+        .iter()
+        .map(|_| {
+            builder
+                .add_existing_account_from_components(falcon_auth(), [get_publisher_component()])
+                .map(|publisher| publisher.id())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let oracle =
+        builder.add_existing_account_from_components(falcon_auth(), [get_oracle_component()])?;
+    let mut mock_chain = builder.build()?;
+
+    for (publisher_id, (secret, reveals)) in publisher_ids.iter().zip(publishers) {
+        execute_and_commit(
+            &mut mock_chain,
+            *publisher_id,
+            commit_script(ROUND, *secret)?,
+        )
+        .await?;
+        if *reveals {
+            execute_and_commit(
+                &mut mock_chain,
+                *publisher_id,
+                reveal_script(ROUND, *secret)?,
+            )
+            .await?;
+        }
+        execute_and_commit(
+            &mut mock_chain,
+            oracle.id(),
+            register_publisher_script(*publisher_id)?,
+        )
+        .await?;
+    }
+
+    Ok((mock_chain, oracle.id(), publisher_ids))
+}
+
+fn oracle_proc_root(name: &str) -> Word {
+    let lib = get_oracle_component_library();
+    let short = format!("oracle_module::{name}");
+    let full = format!("oracle_component::oracle_module::{name}");
+    lib.get_procedure_root_by_path(short.as_str())
+        .or_else(|| lib.get_procedure_root_by_path(full.as_str()))
+        .expect("oracle procedure root must resolve")
+}
+
+/// Runs `get_randomness` for `round` and returns `(nb_of_reveals, RANDOMNESS)`.
+async fn run_get_randomness(
+    mock_chain: &MockChain,
+    oracle_id: AccountId,
+    publisher_ids: &[AccountId],
+    round: u64,
+) -> Result<(u64, Word)> {
+    let foreign_inputs = publisher_ids
+        .iter()
+        .map(|id| mock_chain.get_foreign_account_inputs(*id))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let ctx = mock_chain
+        .build_tx_context(oracle_id, &[], &[])?
+        .foreign_accounts(foreign_inputs)
+        .build()?;
+    let code = format!(
+        "
+        use $kernel::prologue
+        use miden::core::sys
+
+        begin
+            exec.prologue::prepare_transaction
+            push.{round}
+            call.{root}
+            exec.sys::truncate_stack
+        end
+        ",
+        root = oracle_proc_root("get_randomness").to_hex(),
+    );
+    let out = ctx
+        .execute_code(&code)
+        .await
+        .map_err(|e| anyhow::anyhow!("get_randomness execute_code failed: {e:?}"))?;
+    Ok((
+        out.stack[0].as_canonical_u64(),
+        [out.stack[1], out.stack[2], out.stack[3], out.stack[4]].into(),
+    ))
+}
+
+// ============================================================================
+// Tests: publish_randomness
+// ============================================================================
+
+#[tokio::test]
+async fn test_publisher_commit_then_reveal_stores_randomness() -> Result<()> {
+    let (mut mock_chain, publisher_id) = setup_publisher()?;
+
+    execute_and_commit(
+        &mut mock_chain,
+        publisher_id,
+        commit_script(ROUND, secret(1))?,
+    )
+    .await?;
+    let publisher = mock_chain.committed_account(publisher_id)?;
+    let commitments_slot =
+        StorageSlotName::new("pragma::publisher::randomness_commitments").unwrap();
+    let randomness_slot = StorageSlotName::new("pragma::publisher::randomness").unwrap();
+    assert_eq!(
+        publisher
+            .storage()
+            .get_map_item(&commitments_slot, round_key(ROUND))
+            .unwrap(),
+        randomness_commitment(secret(1))
+    );
+    assert_eq!(
+        publisher
+            .storage()
+            .get_map_item(&randomness_slot, round_key(ROUND))
+            .unwrap(),
+        Word::default()
+    );
+
+    execute_and_commit(
+        &mut mock_chain,
+        publisher_id,
+        reveal_script(ROUND, secret(1))?,
+    )
+    .await?;
+    let publisher = mock_chain.committed_account(publisher_id)?;
+    assert_eq!(
+        publisher
+            .storage()
+            .get_map_item(&randomness_slot, round_key(ROUND))
+            .unwrap(),
+        secret(1)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_publisher_reveal_fails_with_mismatched_value() -> Result<()> {
+    let (mut mock_chain, publisher_id) = setup_publisher()?;
+    execute_and_commit(
+        &mut mock_chain,
+        publisher_id,
+        commit_script(ROUND, secret(1))?,
+    )
+    .await?;
+
+    // Another value under the commitment of the round.
+    let result = mock_chain
+        .build_tx_context(publisher_id, &[], &[])?
+        .tx_script(publish_randomness_script(
+            ROUND,
+            secret(2),
+            randomness_commitment(secret(1)),
+        )?)
+        .build()?
+        .execute()
+        .await;
+    assert_transaction_executor_error!(result, ERR_RANDOMNESS_COMMITMENT_MISMATCH);
+
+    // Another value with its own commitment.
+    let result = mock_chain
+        .build_tx_context(publisher_id, &[], &[])?
+        .tx_script(reveal_script(ROUND, secret(2))?)
+        .build()?
+        .execute()
+        .await;
+    assert_transaction_executor_error!(result, ERR_RANDOMNESS_COMMITMENT_MISMATCH);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_publisher_reveal_fails_without_commit() -> Result<()> {
+    let (mock_chain, publisher_id) = setup_publisher()?;
+
+    let result = mock_chain
+        .build_tx_context(publisher_id, &[], &[])?
+        .tx_script(reveal_script(ROUND, secret(1))?)
+        .build()?
+        .execute()
+        .await;
+    assert_transaction_executor_error!(result, ERR_RANDOMNESS_NOT_COMMITTED);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_publisher_commit_fails_twice_for_a_round() -> Result<()> {
+    let (mut mock_chain, publisher_id) = setup_publisher()?;
+    execute_and_commit(
+        &mut mock_chain,
+        publisher_id,
+        commit_script(ROUND, secret(1))?,
+    )
+    .await?;
+
+    let result = mock_chain
+        .build_tx_context(publisher_id, &[], &[])?
+        .tx_script(commit_script(ROUND, secret(2))?)
+        .build()?
+        .execute()
+        .await;
+    assert_transaction_executor_error!(result, ERR_RANDOMNESS_ALREADY_COMMITTED);
+
+    // Other rounds are independent.
+    execute_and_commit(
+        &mut mock_chain,
+        publisher_id,
+        commit_script(ROUND + 1, secret(2))?,
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_publisher_reveal_fails_twice_for_a_round() -> Result<()> {
+    let (mut mock_chain, publisher_id) = setup_publisher()?;
+    execute_and_commit(
+        &mut mock_chain,
+        publisher_id,
+        commit_script(ROUND, secret(1))?,
+    )
+    .await?;
+    execute_and_commit(
+        &mut mock_chain,
+        publisher_id,
+        reveal_script(ROUND, secret(1))?,
+    )
+    .await?;
+
+    let result = mock_chain
+        .build_tx_context(publisher_id, &[], &[])?
+        .tx_script(reveal_script(ROUND, secret(1))?)
+        .build()?
+        .execute()
+        .await;
+    assert_transaction_executor_error!(result, ERR_RANDOMNESS_ALREADY_REVEALED);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_publisher_commit_fails_with_empty_commitment() -> Result<()> {
+    let (mock_chain, publisher_id) = setup_publisher()?;
+
+    let result = mock_chain
+        .build_tx_context(publisher_id, &[], &[])?
+        .tx_script(publish_randomness_script(
+            ROUND,
+            Word::default(),
+            Word::default(),
+        )?)
+        .build()?
+        .execute()
+        .await;
+    assert_transaction_executor_error!(result, ERR_EMPTY_RANDOMNESS_COMMITMENT);
+
+    Ok(())
+}
+
+// ============================================================================
+// Tests: get_randomness
+// ============================================================================
+
+#[tokio::test]
+async fn test_oracle_get_randomness_excludes_unrevealed_publishers() -> Result<()> {
+    let (mock_chain, oracle_id, publisher_ids) =
+        setup(&[(secret(1), true), (secret(10), false), (secret(20), true)]).await?;
+    let (nb_of_reveals, randomness) =
+        run_get_randomness(&mock_chain, oracle_id, &publisher_ids, ROUND).await?;
+    assert_eq!(nb_of_reveals, 2);
+    assert_ne!(randomness, Word::default());
+
+    // Same randomness as if only the revealing publishers were registered.
+    let (revealed_chain, revealed_oracle_id, revealed_ids) =
+        setup(&[(secret(1), true), (secret(20), true)]).await?;
+    let (revealed_nb_of_reveals, revealed_randomness) =
+        run_get_randomness(&revealed_chain, revealed_oracle_id, &revealed_ids, ROUND).await?;
+    assert_eq!(revealed_nb_of_reveals, 2);
+    assert_eq!(randomness, revealed_randomness);
+
+    // And a different one once every publisher revealed.
+    let (all_chain, all_oracle_id, all_ids) =
+        setup(&[(secret(1), true), (secret(10), true), (secret(20), true)]).await?;
+    let (all_nb_of_reveals, all_randomness) =
+        run_get_randomness(&all_chain, all_oracle_id, &all_ids, ROUND).await?;
+    assert_eq!(all_nb_of_reveals, 3);
+    assert_ne!(randomness, all_randomness);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_oracle_get_randomness_of_unrevealed_round_is_empty() -> Result<()> {
+    let (mock_chain, oracle_id, publisher_ids) =
+        setup(&[(secret(1), false), (secret(10), true)]).await?;
+
+    let (nb_of_reveals, randomness) =
+        run_get_randomness(&mock_chain, oracle_id, &publisher_ids, ROUND + 1).await?;
+    assert_eq!(nb_of_reveals, 0);
+    assert_eq!(randomness, Word::default());
+
+    Ok(())
+}
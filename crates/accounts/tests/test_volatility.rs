@@ -0,0 +1,321 @@
+//! `get_volatility` tests against an in-process MockChain.
+//!
+//! Publishers publish synthetic price paths through `publish_entry`, so that
+//! their histories are built the same way as on-chain; the volatility is then
+//! read through `execute_code`, as in the `get_median` value tests of
+//! `test_oracle.rs`.
+
+use anyhow::Result;
+use miden_client::account::AccountId;
+use miden_client::transaction::TransactionScript;
+use miden_protocol::account::auth::AuthScheme;
+use miden_protocol::errors::MasmError;
+use miden_protocol::{Felt, Word, ZERO};
+use miden_standards::code_builder::CodeBuilder;
+use miden_testing::{assert_transaction_executor_error, Auth, MockChain, MockChainBuilder};
+
+use pm_accounts::{
+    oracle::{get_oracle_component, get_oracle_component_library},
+    publisher::{get_publisher_component, get_publisher_component_library},
+    utils::word_to_masm,
+};
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+const ERR_INVALID_VOLATILITY_WINDOW: MasmError =
+    MasmError::from_static_str("invalid volatility window");
+
+const NOW_TS: u32 = 2_000_000_000;
+
+fn falcon_auth() -> Auth {
+    Auth::BasicAuth {
+        auth_scheme: AuthScheme::Falcon512Poseidon2,
+    }
+}
+
+fn publish_entry_script(price: u64, timestamp: u64) -> Result<TransactionScript> {
+    let entry: Word = [
+        ZERO,
+        Felt::new(price)?,
+        Felt::new(8)?,
+        Felt::new(timestamp)?,
+    ]
+    .into();
+    let tx_script_code = format!(
+        "
+        use publisher_component::publisher_module
+        use miden::core::sys
+
+        begin
+            push.{entry}
+            push.0.0.0.1
+            call.publisher_module::publish_entry
+            dropw
+            exec.sys::truncate_stack
+        end
+        ",
+        entry = word_to_masm(entry),
+    );
+    Ok(CodeBuilder::default()
+        .with_statically_linked_library(&get_publisher_component_library())?
+        .compile_tx_script(tx_script_code)?)
+}
+
+fn register_publisher_script(publisher_id: AccountId) -> Result<TransactionScript> {
+    let tx_script_code = format!(
+        "
+        use oracle_component::oracle_module
+        use miden::core::sys
+
+        begin
+            push.0.0
+            push.{suffix} push.{prefix}
+            call.oracle_module::register_publisher
+            exec.sys::truncate_stack
+        end
+        ",
+        prefix = publisher_id.prefix().as_u64(),
+        suffix = publisher_id.suffix(),
+    );
+    Ok(CodeBuilder::default()
+        .with_statically_linked_library(&get_oracle_component_library())?
+        .compile_tx_script(tx_script_code)?)
+}
+
+async fn execute_and_commit(
+    mock_chain: &mut MockChain,
+    account_id: AccountId,
+    script: TransactionScript,
+) -> Result<()> {
+    let tx = mock_chain
+        .build_tx_context(account_id, &[], &[])?
+        .tx_script(script)
+        .build()?;
+    let executed = tx.execute().await?;
+    mock_chain.add_pending_executed_transaction(&executed)?;
+    mock_chain.prove_next_block()?;
+    Ok(())
+}
+
+/// Publishes `path`, oldest price first, as the entries of faucet `1:0`.
+async fn publish_path(
+    mock_chain: &mut MockChain,
+    publisher_id: AccountId,
+    path: &[u64],
+    timestamp: u64,
+) -> Result<()> {
+    for price in path {
+        execute_and_commit(
+            mock_chain,
+            publisher_id,
+            publish_entry_script(*price, timestamp)?,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Builds a chain with an oracle and one registered publisher per path.
+async fn setup(paths: &[(&[u64], u64)]) -> Result<(MockChain, AccountId, Vec<AccountId>)> {
+    let mut builder = MockChainBuilder::new();
+    let publisher_ids = paths
+        .iter()
+        .map(|_| {
+            builder
+                .add_existing_account_from_components(falcon_auth(), [get_publisher_component()])
+                .map(|publisher| publisher.id())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let oracle =
+        builder.add_existing_account_from_components(falcon_auth(), [get_oracle_component()])?;
+    let mut mock_chain = builder.build()?;
+
+    for (publisher_id, (path, timestamp)) in publisher_ids.iter().zip(paths) {
+        publish_path(&mut mock_chain, *publisher_id, path, *timestamp).await?;
+        execute_and_commit(
+            &mut mock_chain,
+            oracle.id(),
+            register_publisher_script(*publisher_id)?,
+        )
+        .await?;
+    }
+    mock_chain.prove_next_block_at(NOW_TS)?;
+
+    Ok((mock_chain, oracle.id(), publisher_ids))
+}
+
+fn oracle_proc_root(name: &str) -> Word {
+    let lib = get_oracle_component_library();
+    let short = format!("oracle_module::{name}");
+    let full = format!("oracle_component::oracle_module::{name}");
+    lib.get_procedure_root_by_path(short.as_str())
+        .or_else(|| lib.get_procedure_root_by_path(full.as_str()))
+        .expect("oracle procedure root must resolve")
+}
+
+/// Runs `get_volatility` for faucet `1:0` and returns `(is_tracked, volatility)`.
+async fn run_get_volatility(
+    mock_chain: &MockChain,
+    oracle_id: AccountId,
+    publisher_ids: &[AccountId],
+    window: u64,
+) -> Result<(u64, u64)> {
+    let foreign_inputs = publisher_ids
+        .iter()
+        .map(|id| mock_chain.get_foreign_account_inputs(*id))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let ctx = mock_chain
+        .build_tx_context(oracle_id, &[], &[])?
+        .foreign_accounts(foreign_inputs)
+        .build()?;
+    let code = format!(
+        "
+        use $kernel::prologue
+        use miden::core::sys
+
+        begin
+            exec.prologue::prepare_transaction
+            push.0.{window}.0.1
+            call.{root}
+            exec.sys::truncate_stack
+        end
+        ",
+        root = oracle_proc_root("get_volatility").to_hex(),
+    );
+    let out = ctx
+        .execute_code(&code)
+        .await
+        .map_err(|e| anyhow::anyhow!("get_volatility execute_code failed: {e:?}"))?;
+    Ok((
+        out.stack[0].as_canonical_u64(),
+        out.stack[1].as_canonical_u64(),
+    ))
+}
+
+// ============================================================================
+// Tests: get_volatility
+// ============================================================================
+
+/// Every price moves by 10% of the previous one: the volatility is 10%.
+#[tokio::test]
+async fn test_oracle_get_volatility_of_constant_moves() -> Result<()> {
+    let path: &[u64] = &[1_000_000, 1_100_000, 990_000, 1_089_000];
+    let (mock_chain, oracle_id, publisher_ids) = setup(&[(path, NOW_TS as u64)]).await?;
+
+    let (is_tracked, volatility) =
+        run_get_volatility(&mock_chain, oracle_id, &publisher_ids, 3).await?;
+    assert_eq!(is_tracked, 1);
+    assert_eq!(volatility, 10_000_000, "10% with 8 decimals");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_oracle_get_volatility_of_flat_path_is_zero() -> Result<()> {
+    let path: &[u64] = &[1_000_000; 5];
+    let (mock_chain, oracle_id, publisher_ids) = setup(&[(path, NOW_TS as u64)]).await?;
+
+    let (is_tracked, volatility) =
+        run_get_volatility(&mock_chain, oracle_id, &publisher_ids, 4).await?;
+    assert_eq!(is_tracked, 1, "a flat path is still tracked");
+    assert_eq!(volatility, 0);
+
+    Ok(())
+}
+
+/// Only the last `window` returns count, and a window longer than the history
+/// uses every kept entry.
+#[tokio::test]
+async fn test_oracle_get_volatility_window() -> Result<()> {
+    // Returns: 100%, then 10% twice.
+    let path: &[u64] = &[1_000_000, 2_000_000, 2_200_000, 1_980_000];
+    let (mock_chain, oracle_id, publisher_ids) = setup(&[(path, NOW_TS as u64)]).await?;
+
+    let (_, volatility) = run_get_volatility(&mock_chain, oracle_id, &publisher_ids, 2).await?;
+    assert_eq!(volatility, 10_000_000, "last 2 returns: 10%");
+
+    // sqrt((1^2 + 0.1^2 + 0.1^2) / 3) with 8 decimals.
+    let (_, volatility) = run_get_volatility(&mock_chain, oracle_id, &publisher_ids, 3).await?;
+    assert_eq!(volatility, 58_309_518);
+
+    let (_, longer) = run_get_volatility(&mock_chain, oracle_id, &publisher_ids, 15).await?;
+    assert_eq!(longer, volatility, "only 3 returns are available");
+
+    Ok(())
+}
+
+/// The volatility is the median over the publishers, and publishers whose last
+/// entry is stale are skipped.
+#[tokio::test]
+async fn test_oracle_get_volatility_median_over_publishers() -> Result<()> {
+    let ten_percent: &[u64] = &[1_000_000, 1_100_000, 990_000];
+    let twenty_percent: &[u64] = &[1_000_000, 1_200_000, 960_000];
+    let stale: &[u64] = &[1_000_000, 2_000_000, 4_000_000];
+    let (mock_chain, oracle_id, publisher_ids) = setup(&[
+        (ten_percent, NOW_TS as u64),
+        (twenty_percent, NOW_TS as u64),
+        (stale, (NOW_TS - 7200) as u64),
+    ])
+    .await?;
+
+    let (is_tracked, volatility) =
+        run_get_volatility(&mock_chain, oracle_id, &publisher_ids, 2).await?;
+    assert_eq!(is_tracked, 1);
+    assert_eq!(
+        volatility, 15_000_000,
+        "avg(10%, 20%), the stale publisher skipped"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_oracle_get_volatility_untracked_without_history() -> Result<()> {
+    let single_entry: &[u64] = &[1_000_000];
+    let (mock_chain, oracle_id, publisher_ids) = setup(&[(single_entry, NOW_TS as u64)]).await?;
+
+    let (is_tracked, volatility) =
+        run_get_volatility(&mock_chain, oracle_id, &publisher_ids, 3).await?;
+    assert_eq!(is_tracked, 0, "a single entry has no return");
+    assert_eq!(volatility, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_oracle_get_volatility_fails_with_invalid_window() -> Result<()> {
+    let mut builder = MockChainBuilder::new();
+    let oracle =
+        builder.add_existing_account_from_components(falcon_auth(), [get_oracle_component()])?;
+    let mock_chain = builder.build()?;
+
+    for window in [0, 16] {
+        let tx_script_code = format!(
+            "
+            use oracle_component::oracle_module
+            use miden::core::sys
+
+            begin
+                push.0.{window}.0.1
+                call.oracle_module::get_volatility
+                exec.sys::truncate_stack
+            end
+            "
+        );
+        let script = CodeBuilder::default()
+            .with_statically_linked_library(&get_oracle_component_library())?
+            .compile_tx_script(tx_script_code)?;
+        let result = mock_chain
+            .build_tx_context(oracle.id(), &[], &[])?
+            .tx_script(script)
+            .build()?
+            .execute()
+            .await;
+
+        assert_transaction_executor_error!(result, ERR_INVALID_VOLATILITY_WINDOW);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use colored::*;
+use miden_client::Client;
+use pm_consumer::OracleReader;
+use pm_utils_cli::{get_oracle_id, plain_record, PragmaKeyStore, PRAGMA_ACCOUNTS_STORAGE_FILE};
+use prettytable::{Cell, Row, Table};
+use serde::Serialize;
+
+#[derive(clap::Parser, Debug, Clone)]
+#[clap(about = "Lists the assets registered in the Oracle asset registry")]
+pub struct AssetsCmd {}
+
+/// Assets of the Oracle asset registry.
+///
+/// JSON schema: `{"oracle_id": string, "assets": [{"pair": string,
+/// "faucet_id": string, "decimals": u32}]}`.
+#[derive(Debug, Clone, Serialize)]
+pub struct AssetsOutput {
+    pub oracle_id: String,
+    pub assets: Vec<AssetRecord>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AssetRecord {
+    pub pair: String,
+    pub faucet_id: String,
+    pub decimals: u32,
+}
+
+impl AssetsCmd {
+    /// Lists the assets of the Oracle asset registry
+    ///
+    /// The names and decimals are read from the Oracle account storage, so no
+    /// local asset configuration is involved.
+    pub async fn call(
+        &self,
+        client: &mut Client<PragmaKeyStore>,
+        network: &str,
+    ) -> anyhow::Result<AssetsOutput> {
+        let oracle_id = get_oracle_id(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network)?;
+        let assets = OracleReader::new(client, oracle_id).assets().await?;
+
+        Ok(AssetsOutput {
+            oracle_id: oracle_id.to_hex(),
+            assets: assets
+                .iter()
+                .map(|asset| AssetRecord {
+                    pair: asset.pair.to_string(),
+                    faucet_id: asset.asset_key.to_string(),
+                    decimals: asset.decimals,
+                })
+                .collect(),
+        })
+    }
+}
+
+impl AssetsOutput {
+    pub fn table(&self) -> String {
+        let mut out = String::new();
+        writeln!(
+            out,
+            "{}",
+            format!("🔍 Oracle ID: {}", self.oracle_id).bright_yellow()
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "{}",
+            format!("📊 Total Assets: {}\n", self.assets.len()).bright_yellow()
+        )
+        .unwrap();
+
+        if self.assets.is_empty() {
+            write!(
+                out,
+                "{}",
+                "ℹ️  No assets registered yet! Use 'pm-oracle-cli register-asset [PAIR]' to add assets."
+                    .bright_yellow()
+            )
+            .unwrap();
+            return out;
+        }
+
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_BOX_CHARS);
+        table.add_row(Row::new(vec![
+            Cell::new("Pair").style_spec("Fcb"),
+            Cell::new("Faucet ID").style_spec("Fcb"),
+            Cell::new("Decimals").style_spec("Fcb"),
+        ]));
+        for asset in &self.assets {
+            table.add_row(Row::new(vec![
+                Cell::new(&asset.pair).style_spec("Fy"),
+                Cell::new(&asset.faucet_id).style_spec("Fw"),
+                Cell::new(&asset.decimals.to_string()).style_spec("Fw"),
+            ]));
+        }
+        write!(out, "{table}").unwrap();
+        out
+    }
+
+    /// One line per asset: pair, faucet id, decimals.
+    pub fn plain(&self) -> String {
+        self.assets
+            .iter()
+            .map(|asset| {
+                plain_record([
+                    asset.pair.clone(),
+                    asset.faucet_id.clone(),
+                    asset.decimals.to_string(),
+                ])
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
@@ -0,0 +1,95 @@
+use std::path::Path;
+
+use miden_client::note::Note;
+use miden_client::transaction::TransactionRequestBuilder;
+use miden_client::Client;
+use pm_accounts::notes::get_leave_note_script;
+use pm_utils_cli::{
+    get_oracle_id, retry_policy, submit_transaction_with_retry, sync_state_with_retry, Done,
+    PragmaKeyStore, PRAGMA_ACCOUNTS_STORAGE_FILE,
+};
+
+#[derive(clap::Parser, Debug, Clone)]
+#[clap(about = "Consumes the leave notes sent by publishers to the Oracle")]
+pub struct ConsumeLeaveNotesCmd {}
+
+impl ConsumeLeaveNotesCmd {
+    /// Consumes every pending leave note targeting the Oracle
+    ///
+    /// Each note is consumed in its own transaction, which soft-deletes the
+    /// note sender from the registry. A note whose sender is not registered
+    /// anymore fails on its own without blocking the other ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - A mutable reference to the Miden client, to be initialized first
+    /// * `network` - The network identifier (e.g., "devnet", "testnet")
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The Oracle ID cannot be retrieved from configuration
+    /// - The client fails to sync state with the network
+    /// - The leave note script compilation fails
+    pub async fn call(
+        &self,
+        client: &mut Client<PragmaKeyStore>,
+        network: &str,
+    ) -> anyhow::Result<Done> {
+        let retry = retry_policy(network);
+        let oracle_id = get_oracle_id(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network)?;
+
+        sync_state_with_retry(client, &retry)
+            .await
+            .map_err(|e| anyhow::anyhow!("Error while syncing state: {e:?}"))?;
+
+        // The oracle id is baked into the leave note script, so its root only
+        // matches the leave notes targeting this oracle.
+        let leave_script_root = get_leave_note_script(oracle_id)?.root();
+        let leave_notes: Vec<_> = client
+            .get_consumable_notes(Some(oracle_id))
+            .await?
+            .into_iter()
+            .map(|(record, _)| record)
+            .filter(|record| record.details().script().root() == leave_script_root)
+            // Records without metadata cannot be consumed yet.
+            .filter_map(|record| record.try_into().ok())
+            .collect::<Vec<Note>>();
+
+        if leave_notes.is_empty() {
+            return Ok(Done::new("No pending leave notes"));
+        }
+
+        let nb_of_notes = leave_notes.len();
+        let mut nb_of_consumed = 0;
+        for note in leave_notes {
+            let note_id = note.id();
+            let sender = note.metadata().sender().to_hex();
+
+            let transaction_request = TransactionRequestBuilder::new()
+                .build_consume_notes(vec![note])
+                .map_err(|e| anyhow::anyhow!("Error while building transaction request: {e:?}"))?;
+
+            match submit_transaction_with_retry(client, oracle_id, transaction_request, &retry)
+                .await
+            {
+                Ok(_) => {
+                    nb_of_consumed += 1;
+                    eprintln!("✅ Publisher {} left the Oracle (note {})", sender, note_id)
+                }
+                Err(e) => eprintln!(
+                    "❌ Could not consume leave note {} from {}: {e:?}",
+                    note_id, sender
+                ),
+            }
+        }
+
+        sync_state_with_retry(client, &retry)
+            .await
+            .map_err(|e| anyhow::anyhow!("Error while syncing state after consume: {e:?}"))?;
+
+        Ok(Done::new(format!(
+            "{nb_of_consumed} of {nb_of_notes} leave notes consumed"
+        )))
+    }
+}
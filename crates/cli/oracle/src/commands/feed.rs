@@ -0,0 +1,66 @@
+use miden_client::Client;
+use pm_consumer::{FeedAggregate, OracleReader};
+use pm_types::FeedId;
+use pm_utils_cli::{get_oracle_id, plain_record, PragmaKeyStore, PRAGMA_ACCOUNTS_STORAGE_FILE};
+use std::path::Path;
+
+#[derive(clap::Parser, Debug, Clone)]
+#[clap(
+    about = "Aggregate the values published for a generic feed (funding rate, index, reserves...)"
+)]
+pub struct FeedCmd {
+    /// Name of the feed (e.g., BTC-PERP/funding)
+    pub feed: String,
+}
+
+impl FeedCmd {
+    /// Aggregates the values published for a feed
+    ///
+    /// The values are aggregated by the oracle with the mode of the feed
+    /// config (see `pm-oracle-cli set-feed-config`), a median of prices for
+    /// unconfigured feeds.
+    pub async fn call(
+        &self,
+        client: &mut Client<PragmaKeyStore>,
+        network: &str,
+    ) -> anyhow::Result<FeedAggregate> {
+        let oracle_id = get_oracle_id(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network)?;
+        let feed_id = FeedId::from_name(&self.feed)?;
+
+        OracleReader::new(client, oracle_id).feed(feed_id).await
+    }
+}
+
+/// Renders the aggregate of a feed. Its JSON schema is the one of
+/// [`FeedAggregate`]: `{"feed_id": string, "kind": string, "mode": string,
+/// "is_tracked": bool, "value": i64, "decimals": u32, "nb_of_values": u64}`.
+pub fn table(aggregate: &FeedAggregate) -> String {
+    if aggregate.is_tracked {
+        format!(
+            "{} ({}, {}) value: {} ({} decimals, {} values)",
+            aggregate.feed_id,
+            aggregate.kind,
+            aggregate.mode,
+            aggregate.value,
+            aggregate.decimals,
+            aggregate.nb_of_values
+        )
+    } else {
+        format!(
+            "{} not tracked (no fresh {} value)",
+            aggregate.feed_id, aggregate.kind
+        )
+    }
+}
+
+pub fn plain(aggregate: &FeedAggregate) -> String {
+    plain_record([
+        aggregate.feed_id.clone(),
+        aggregate.kind.clone(),
+        aggregate.mode.clone(),
+        aggregate.is_tracked.to_string(),
+        aggregate.value.to_string(),
+        aggregate.decimals.to_string(),
+        aggregate.nb_of_values.to_string(),
+    ])
+}
@@ -1,24 +1,18 @@
-use std::collections::BTreeMap;
 use std::path::Path;
 
 use miden_client::account::AccountId;
-use miden_client::rpc::domain::account::AccountStorageRequirements;
-use miden_client::transaction::ForeignAccount;
-use miden_client::{keystore::FilesystemKeyStore, Client, Felt};
-use miden_protocol::account::{StorageMapKey, StorageSlotName};
-use miden_standards::code_builder::CodeBuilder;
-
-use miden_protocol::vm::AdviceInputs;
-use pm_accounts::oracle::get_oracle_component_library;
-use pm_accounts::utils::word_to_masm;
+use miden_client::Client;
+use pm_consumer::OracleReader;
 use pm_types::Entry;
-use pm_utils_cli::{get_oracle_id, PRAGMA_ACCOUNTS_STORAGE_FILE};
+use pm_utils_cli::{
+    get_asset_registry, get_oracle_id, PragmaKeyStore, PRAGMA_ACCOUNTS_STORAGE_FILE,
+};
 
 #[derive(clap::Parser, Debug, Clone)]
 #[clap(about = "Gets entry")]
 pub struct GetEntryCmd {
     publisher_id: String,
-    // Input faucet_id (format example: "1:0" for BTC/USD)
+    // Input pair symbol or faucet_id (format example: "BTC/USD" or "1:0")
     faucet_id: String,
 }
 
@@ -38,89 +32,16 @@ impl GetEntryCmd {
     /// * `Result<Entry>` - The retrieved entry or an error with context
     pub async fn call(
         &self,
-        client: &mut Client<FilesystemKeyStore>,
+        client: &mut Client<PragmaKeyStore>,
         network: &str,
     ) -> anyhow::Result<Entry> {
         let oracle_id = get_oracle_id(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network)?;
         let publisher_id = AccountId::from_hex(&self.publisher_id)?;
-        client.import_account_by_id(publisher_id).await?;
-        client.import_account_by_id(oracle_id).await?;
-        client.sync_state().await?;
-        let publisher = client
-            .get_account(publisher_id)
-            .await?
-            .expect("Publisher account not found");
-
-        let parts: Vec<&str> = self.faucet_id.split(':').collect();
-        if parts.len() != 2 {
-            return Err(anyhow::anyhow!(
-                "Invalid faucet_id format. Expected PREFIX:SUFFIX (e.g., 1:0)"
-            ));
-        }
-        let prefix = parts[0]
-            .parse::<u64>()
-            .map_err(|_| anyhow::anyhow!("Invalid faucet_id prefix"))?;
-        let suffix = parts[1]
-            .parse::<u64>()
-            .map_err(|_| anyhow::anyhow!("Invalid faucet_id suffix"))?;
-
-        let faucet_id_word: miden_client::Word = [
-            Felt::new(0)?,
-            Felt::new(0)?,
-            Felt::new(suffix)?,
-            Felt::new(prefix)?,
-        ]
-        .into();
-
-        let publisher_entries_slot = StorageSlotName::new("pragma::publisher::entries")
-            .map_err(|e| anyhow::anyhow!("Invalid storage slot name: {e:?}"))?;
-        let foreign_account = ForeignAccount::public(
-            publisher.id(),
-            AccountStorageRequirements::new([(
-                publisher_entries_slot,
-                &[StorageMapKey::new(faucet_id_word)],
-            )]),
-        )?;
-        let tx_script_code = format!(
-            "
-            use oracle_component::oracle_module
-            use miden::core::sys
-
-            begin
-                push.{faucet_id}
-                push.0.0
-                push.{account_id_suffix} push.{account_id_prefix}
-                call.oracle_module::get_entry
-                exec.sys::truncate_stack
-            end
-            ",
-            faucet_id = word_to_masm(faucet_id_word),
-            account_id_prefix = publisher_id.prefix().as_u64(),
-            account_id_suffix = publisher_id.suffix(),
-        );
+        let asset_key = get_asset_registry(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE))?
+            .resolve(&self.faucet_id)?;
 
-        let oracle_lib = get_oracle_component_library();
-        let get_entry_script = CodeBuilder::default()
-            .with_dynamically_linked_library(&oracle_lib)
-            .map_err(|e| anyhow::anyhow!("Error while setting up the component library: {e:?}"))?
-            .compile_tx_script(tx_script_code)
-            .map_err(|e| anyhow::anyhow!("Error while compiling the script: {e:?}"))?;
-        let mut foreign_accounts_map: BTreeMap<AccountId, ForeignAccount> = BTreeMap::new();
-        foreign_accounts_map.insert(foreign_account.account_id(), foreign_account);
-        let output_stack = client
-            .execute_program(
-                oracle_id,
-                get_entry_script,
-                AdviceInputs::default(),
-                foreign_accounts_map,
-            )
+        OracleReader::new(client, oracle_id)
+            .entry(publisher_id, asset_key)
             .await
-            .map_err(|e| anyhow::anyhow!("Error executing transaction: {:?}", e))?;
-        Ok(Entry {
-            faucet_id: self.faucet_id.clone(),
-            price: output_stack[2].as_canonical_u64(),
-            decimals: output_stack[1].as_canonical_u64() as u32,
-            timestamp: output_stack[0].as_canonical_u64(),
-        })
     }
 }
@@ -0,0 +1,241 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use colored::*;
+use miden_client::Client;
+use miden_protocol::account::StorageSlotName;
+use pm_consumer::OracleReader;
+use pm_utils_cli::{
+    get_asset_registry, get_oracle_id, import_account_with_retry, plain_record, retry_policy,
+    PragmaKeyStore, PRAGMA_ACCOUNTS_STORAGE_FILE,
+};
+use prettytable::{Cell, Row, Table};
+use serde::Serialize;
+
+#[derive(clap::Parser, Debug, Clone)]
+#[clap(about = "Checks the liveness of every registered publisher")]
+pub struct HealthCmd {
+    /// Pairs or faucet ids every publisher is expected to keep fresh (e.g., BTC/USD 2:0)
+    pub faucet_ids: Vec<String>,
+
+    /// Maximum age (in seconds) of a publisher's last heartbeat
+    #[clap(long, default_value = "3600")]
+    pub max_heartbeat_age: u64,
+
+    /// Maximum age (in seconds) of a publisher entry before its asset is reported as stale
+    #[clap(long, default_value = "3600")]
+    pub max_entry_age: u64,
+}
+
+/// Liveness of the registered publishers.
+///
+/// JSON schema: `{"checked_at": u64, "healthy": bool, "publishers":
+/// [{"publisher_id": string, "last_heartbeat": u64 | null, "stale_assets":
+/// [string], "healthy": bool}]}`, timestamps being in seconds.
+#[derive(Debug, Serialize)]
+pub struct HealthReport {
+    pub checked_at: u64,
+    pub healthy: bool,
+    pub publishers: Vec<PublisherHealth>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PublisherHealth {
+    pub publisher_id: String,
+    /// Block timestamp of the last heartbeat, `None` if the publisher never sent one.
+    pub last_heartbeat: Option<u64>,
+    pub stale_assets: Vec<String>,
+    pub healthy: bool,
+}
+
+impl HealthCmd {
+    /// Reports the last heartbeat and the stale assets of every registered publisher
+    ///
+    /// This function performs the following operations:
+    /// 1. Reads the oracle publisher registry, skipping soft-deleted slots
+    /// 2. Imports each publisher account to get its latest storage
+    /// 3. Compares the last heartbeat and each requested entry timestamp against the thresholds
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - A mutable reference to the Miden client
+    /// * `network` - The network identifier (e.g., "devnet", "testnet")
+    ///
+    /// The report is returned even when a publisher breached a threshold,
+    /// see [`HealthReport::ensure_healthy`] for the alerting exit code.
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The Oracle ID cannot be retrieved from configuration
+    /// - A faucet_id cannot be parsed
+    /// - An account cannot be imported from the network
+    pub async fn call(
+        &self,
+        client: &mut Client<PragmaKeyStore>,
+        network: &str,
+    ) -> anyhow::Result<HealthReport> {
+        let retry = retry_policy(network);
+        let oracle_id = get_oracle_id(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network)?;
+
+        let registry = get_asset_registry(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE))?;
+        let mut faucet_keys = Vec::with_capacity(self.faucet_ids.len());
+        for faucet_id in &self.faucet_ids {
+            let asset_key = registry.resolve(faucet_id)?;
+            // Entries are keyed by the word as stored on-chain: [prefix, suffix, 0, 0].
+            let key = asset_key.to_storage_key();
+            faucet_keys.push((registry.label(asset_key), key));
+        }
+
+        let publisher_ids = OracleReader::new(client, oracle_id)
+            .active_publishers()
+            .await?;
+
+        let heartbeat_slot = StorageSlotName::new("pragma::publisher::last_heartbeat")
+            .map_err(|e| anyhow::anyhow!("Invalid storage slot name: {e:?}"))?;
+        let entries_slot = StorageSlotName::new("pragma::publisher::entries")
+            .map_err(|e| anyhow::anyhow!("Invalid storage slot name: {e:?}"))?;
+
+        let now = Utc::now().timestamp() as u64;
+        let mut publishers = Vec::new();
+
+        for publisher_id in publisher_ids {
+            import_account_with_retry(client, publisher_id, &retry)
+                .await
+                .with_context(|| format!("Failed to import publisher {publisher_id}"))?;
+            let publisher = client
+                .get_account(publisher_id)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("Publisher account {publisher_id} not found"))?;
+            let publisher_storage = publisher.storage();
+
+            // Publishers deployed before the heartbeat slot existed have no
+            // heartbeat at all, which is reported the same as never beating.
+            let last_heartbeat = publisher_storage
+                .get_item(&heartbeat_slot)
+                .ok()
+                .map(|word| word[0].as_canonical_u64())
+                .filter(|ts| *ts != 0);
+
+            let mut stale_assets = Vec::new();
+            for (faucet_id, key) in &faucet_keys {
+                // Stored entry word is [timestamp, decimals, price, 0].
+                let entry_ts = publisher_storage
+                    .get_map_item(&entries_slot, *key)
+                    .map(|word| word[0].as_canonical_u64())
+                    .unwrap_or(0);
+                if entry_ts == 0 || now.saturating_sub(entry_ts) > self.max_entry_age {
+                    stale_assets.push(faucet_id.clone());
+                }
+            }
+
+            let heartbeat_ok =
+                last_heartbeat.is_some_and(|ts| now.saturating_sub(ts) <= self.max_heartbeat_age);
+
+            publishers.push(PublisherHealth {
+                publisher_id: publisher_id.to_hex(),
+                last_heartbeat,
+                healthy: heartbeat_ok && stale_assets.is_empty(),
+                stale_assets,
+            });
+        }
+
+        Ok(HealthReport {
+            checked_at: now,
+            healthy: publishers.iter().all(|p| p.healthy),
+            publishers,
+        })
+    }
+}
+
+impl HealthReport {
+    /// Fails if any publisher breached a threshold, so that the command exits
+    /// with a non-zero code once the report is printed and can be used for
+    /// alerting.
+    pub fn ensure_healthy(&self) -> anyhow::Result<()> {
+        let unhealthy = self.publishers.iter().filter(|p| !p.healthy).count();
+        if unhealthy > 0 {
+            return Err(anyhow::anyhow!(
+                "{} of {} publisher(s) breached the health thresholds",
+                unhealthy,
+                self.publishers.len()
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn table(&self) -> String {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_BOX_CHARS);
+        table.add_row(Row::new(vec![
+            Cell::new("Publisher ID").style_spec("Fcb"),
+            Cell::new("Last Heartbeat").style_spec("Fcb"),
+            Cell::new("Stale Assets").style_spec("Fcb"),
+            Cell::new("Status").style_spec("Fcb"),
+        ]));
+
+        for publisher in &self.publishers {
+            let last_heartbeat = match publisher.last_heartbeat {
+                Some(ts) => {
+                    let formatted = DateTime::<Utc>::from_timestamp(ts as i64, 0)
+                        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+                        .unwrap_or_else(|| ts.to_string());
+                    format!(
+                        "{} ({}s ago)",
+                        formatted,
+                        self.checked_at.saturating_sub(ts)
+                    )
+                }
+                None => "never".to_string(),
+            };
+            let stale_assets = if publisher.stale_assets.is_empty() {
+                "-".to_string()
+            } else {
+                publisher.stale_assets.join(", ")
+            };
+            let status = if publisher.healthy {
+                "Healthy ✅"
+            } else {
+                "Unhealthy ❌"
+            };
+
+            table.add_row(Row::new(vec![
+                Cell::new(&publisher.publisher_id).style_spec("Fy"),
+                Cell::new(&last_heartbeat).style_spec("Fw"),
+                Cell::new(&stale_assets).style_spec("Fw"),
+                Cell::new(status).style_spec("Fw"),
+            ]));
+        }
+
+        let mut out = table.to_string();
+        if self.healthy {
+            write!(out, "{}", "✅ All publishers are healthy!".bright_green()).unwrap();
+        }
+        out
+    }
+
+    /// One line per publisher: id, last heartbeat (`-` if never), stale
+    /// assets (comma separated, `-` if none), healthy.
+    pub fn plain(&self) -> String {
+        self.publishers
+            .iter()
+            .map(|publisher| {
+                plain_record([
+                    publisher.publisher_id.clone(),
+                    publisher
+                        .last_heartbeat
+                        .map_or_else(|| "-".to_string(), |ts| ts.to_string()),
+                    if publisher.stale_assets.is_empty() {
+                        "-".to_string()
+                    } else {
+                        publisher.stale_assets.join(",")
+                    },
+                    publisher.healthy.to_string(),
+                ])
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
@@ -1,14 +1,30 @@
+use std::fmt::Write;
 use std::path::Path;
 
 use colored::*;
-use miden_client::{keystore::FilesystemKeyStore, Client};
+use miden_client::Client;
 use pm_accounts::oracle::OracleAccountBuilder;
-use pm_utils_cli::{set_oracle_id, PRAGMA_ACCOUNTS_STORAGE_FILE};
+use pm_utils_cli::{
+    retry_policy, set_oracle_id, sync_state_with_retry, PragmaKeyStore,
+    PRAGMA_ACCOUNTS_STORAGE_FILE,
+};
+use serde::Serialize;
 
 #[derive(clap::Parser, Debug, Clone)]
 #[clap(about = "Creates a new Oracle Account")]
 pub struct InitCmd {}
 
+/// Result of `init`.
+///
+/// JSON schema: `{"oracle_id": string, "storage_file": string}`, the oracle id
+/// being hex encoded.
+#[derive(Debug, Clone, Serialize)]
+pub struct InitOutput {
+    pub oracle_id: String,
+    /// Configuration file the oracle id was saved to.
+    pub storage_file: String,
+}
+
 impl InitCmd {
     /// Initializes a new Oracle Account and sets up the local configuration
     ///
@@ -30,11 +46,12 @@ impl InitCmd {
     /// - The configuration file cannot be updated
     pub async fn call(
         &self,
-        client: &mut Client<FilesystemKeyStore>,
+        client: &mut Client<PragmaKeyStore>,
         network: &str,
-    ) -> anyhow::Result<()> {
-        println!("⏳ Initiating the Oracle...\n");
-        client.sync_state().await?;
+    ) -> anyhow::Result<InitOutput> {
+        let retry = retry_policy(network);
+        eprintln!("⏳ Initiating the Oracle...\n");
+        sync_state_with_retry(client, &retry).await?;
 
         let (oracle_account, _) = OracleAccountBuilder::new()
             .with_client(client)
@@ -48,12 +65,21 @@ impl InitCmd {
             network,
             &created_oracle_id,
         )?;
-        println!();
 
+        Ok(InitOutput {
+            oracle_id: created_oracle_id.to_hex(),
+            storage_file: PRAGMA_ACCOUNTS_STORAGE_FILE.to_string(),
+        })
+    }
+}
+
+impl InitOutput {
+    pub fn table(&self) -> String {
         // Clear screen for better presentation
-        print!("\x1B[2J\x1B[1;1H");
+        let mut out = String::from("\x1B[2J\x1B[1;1H");
 
-        println!(
+        writeln!(
+            out,
             "{}",
             r#"
         ==============================================================
@@ -66,18 +92,22 @@ impl InitCmd {
 
         "#
             .bright_cyan()
-        );
+        )
+        .unwrap();
 
-        println!(
+        writeln!(
+            out,
             "{}",
             r#"
         🌟 Welcome to the Pragma Oracle Network - Admin Console 🌟
         "#
             .bright_yellow()
-        );
+        )
+        .unwrap();
 
-        println!("\n{}", "📝 Oracle Details".bright_green());
-        println!(
+        writeln!(out, "\n{}", "📝 Oracle Details".bright_green()).unwrap();
+        writeln!(
+            out,
             "{}",
             format!(
                 "
@@ -85,15 +115,17 @@ impl InitCmd {
         │ Oracle ID: {}
         │ Storage Location: {}
         ╰────────────────────────────────────────────────────────────╯",
-                created_oracle_id.to_string().bright_white(),
-                PRAGMA_ACCOUNTS_STORAGE_FILE.bright_white()
+                self.oracle_id.bright_white(),
+                self.storage_file.bright_white()
             )
             .bright_blue()
-        );
+        )
+        .unwrap();
 
-        println!("\n{}", "🎮 Available Commands".bright_green());
+        writeln!(out, "\n{}", "🎮 Available Commands".bright_green()).unwrap();
 
-        println!(
+        writeln!(
+            out,
             "{}",
             r#"
         - Register New Publishers
@@ -102,9 +134,11 @@ impl InitCmd {
         ╰────────────────────────────────────────────────────────────╯
         "#
             .bright_blue()
-        );
+        )
+        .unwrap();
 
-        println!(
+        writeln!(
+            out,
             "{}",
             r#"
         - View Publisher Entries
@@ -113,9 +147,11 @@ impl InitCmd {
         ╰────────────────────────────────────────────────────────────╯
         "#
             .bright_blue()
-        );
+        )
+        .unwrap();
 
-        println!(
+        writeln!(
+            out,
             "{}",
             r#"
         - Calculate Median Price
@@ -124,28 +160,37 @@ impl InitCmd {
         ╰────────────────────────────────────────────────────────────╯
         "#
             .bright_blue()
-        );
+        )
+        .unwrap();
 
-        println!("{}", "📋 Example Usage".bright_yellow());
-        println!(
+        writeln!(out, "{}", "📋 Example Usage".bright_yellow()).unwrap();
+        writeln!(
+            out,
             "{}",
             r#"
         ╭────────────────────────────────────────────────────────────────────╮
         │ pm-oracle-cli register-publisher 0x64cbfe4bc88cfe00000556901757eb  │
         │ pm-oracle-cli median 1:0                                           │
-        │ pm-oracle-cli median-batch 1:0 2:0 3:0 --json                      │
+        │ pm-oracle-cli median-batch 1:0 2:0 3:0 --output json               │
         ╰────────────────────────────────────────────────────────────────────╯
         
         💡 Faucet IDs: 1:0=BTC/USD, 2:0=ETH/USD, 3:0=SOL/USD
         "#
             .bright_blue()
-        );
+        )
+        .unwrap();
 
-        println!(
+        write!(
+            out,
             "\n{}",
             "✨ Your Oracle node is ready! Start managing your network! ✨".bright_green()
-        );
+        )
+        .unwrap();
+
+        out
+    }
 
-        Ok(())
+    pub fn plain(&self) -> String {
+        self.oracle_id.clone()
     }
 }
@@ -1,170 +1,76 @@
-use anyhow::Context;
-use miden_client::account::AccountId;
-use miden_client::rpc::domain::account::AccountStorageRequirements;
-use miden_client::transaction::ForeignAccount;
-use miden_client::{keystore::FilesystemKeyStore, Client, Felt, Word, ZERO};
-use miden_protocol::account::{StorageMapKey, StorageSlotName};
-use miden_protocol::vm::AdviceInputs;
-use miden_standards::code_builder::CodeBuilder;
-use pm_accounts::oracle::get_oracle_component_library;
-use pm_utils_cli::{get_oracle_id, PRAGMA_ACCOUNTS_STORAGE_FILE};
-use std::collections::BTreeMap;
+use crate::commands::median_batch::MedianResult;
+use miden_client::Client;
+use pm_consumer::{OracleReader, CROSS_PRICE_DECIMALS};
+use pm_utils_cli::{
+    get_asset_registry, get_oracle_id, PragmaKeyStore, PRAGMA_ACCOUNTS_STORAGE_FILE,
+};
 use std::path::Path;
 
 #[derive(clap::Parser, Debug, Clone)]
 #[clap(about = "Compute the median for a given faucet_id")]
 pub struct MedianCmd {
-    pub faucet_id: String,
+    /// Pair symbol (e.g., BTC/USD) or asset key (e.g., 1:0)
+    #[clap(required_unless_present = "cross", conflicts_with = "cross")]
+    pub faucet_id: Option<String>,
 
-    /// Optional amount parameter (defaults to 0)
-    #[clap(short, long, default_value = "0")]
+    /// Cross pair derived from the medians of its base and quote (e.g., ETH/BTC
+    /// from ETH/USD and BTC/USD), or two asset keys (e.g., 2:0/1:0)
+    #[clap(long)]
+    pub cross: Option<String>,
+
+    /// Optional amount parameter passed to get_median (defaults to 0)
+    #[clap(short, long, default_value = "0", conflicts_with = "cross")]
     pub amount: u64,
 }
 
 impl MedianCmd {
     pub async fn call(
         &self,
-        client: &mut Client<FilesystemKeyStore>,
+        client: &mut Client<PragmaKeyStore>,
         network: &str,
-    ) -> anyhow::Result<Felt> {
+    ) -> anyhow::Result<MedianResult> {
         let oracle_id = get_oracle_id(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network)?;
-
-        client.import_account_by_id(oracle_id).await?;
-        client.sync_state().await?;
-        eprintln!("[DBG] sync1 done");
-        let _account = client
-            .get_account(oracle_id)
-            .await?
-            .expect("Oracle account not found");
-        eprintln!("[DBG] oracle fetched from store");
-
-        let parts: Vec<&str> = self.faucet_id.split(':').collect();
-        if parts.len() != 2 {
-            return Err(anyhow::anyhow!(
-                "Invalid faucet_id format. Expected PREFIX:SUFFIX (e.g., 1:0)"
-            ));
-        }
-
-        let prefix = parts[0]
-            .parse::<u64>()
-            .map_err(|_| anyhow::anyhow!("Invalid faucet_id prefix: {}", parts[0]))?;
-        let suffix = parts[1]
-            .parse::<u64>()
-            .map_err(|_| anyhow::anyhow!("Invalid faucet_id suffix: {}", parts[1]))?;
-
-        let faucet_id_word: Word = [
-            Felt::new(0)?,
-            Felt::new(0)?,
-            Felt::new(suffix)?,
-            Felt::new(prefix)?,
-        ]
-        .into();
-
-        // Re-import oracle from chain to get fresh storage state
-        client.import_account_by_id(oracle_id).await?;
-        let account = client
-            .get_account(oracle_id)
-            .await?
-            .expect("Oracle account not found after re-import");
-        let storage = account.storage();
-
-        let next_index_slot = StorageSlotName::new("pragma::oracle::next_publisher_index")
-            .map_err(|e| anyhow::anyhow!("Invalid storage slot name: {e:?}"))?;
-        let publisher_count = storage
-            .get_item(&next_index_slot)
-            .context("Unable to retrieve publisher count")?[0]
-            .as_canonical_u64();
-
-        let publishers_slot = StorageSlotName::new("pragma::oracle::publishers")
-            .map_err(|e| anyhow::anyhow!("Invalid storage slot name: {e:?}"))?;
-        // Publisher ID word is [prefix, suffix, 0, 0]. A slot zeroed out by
-        // `remove_publisher` decodes to AccountId(0, 0) which doesn't exist on
-        // the network — skip those slots before importing.
-        let publisher_array: Vec<AccountId> = (2..publisher_count)
-            .map(|i: u64| -> anyhow::Result<Word> {
-                let key: [Felt; 4] = [Felt::new(i)?, ZERO, ZERO, ZERO];
-                storage
-                    .get_map_item(&publishers_slot, key.into())
-                    .with_context(|| format!("Failed to retrieve publisher at index {i}"))
-            })
-            .collect::<Result<Vec<_>, _>>()?
-            .into_iter()
-            .filter(|w| !(w[0] == ZERO && w[1] == ZERO))
-            .map(|w| AccountId::new_unchecked([w[0], w[1]]))
-            .collect();
-        eprintln!("[DBG] found {} publishers on-chain", publisher_array.len());
-
-        let mut foreign_accounts: Vec<ForeignAccount> = vec![];
-        let publisher_entries_slot = StorageSlotName::new("pragma::publisher::entries")
-            .map_err(|e| anyhow::anyhow!("Invalid storage slot name: {e:?}"))?;
-        for publisher_id in publisher_array {
-            client.import_account_by_id(publisher_id).await?;
-            eprintln!("[DBG] publisher {publisher_id} imported");
-            let foreign_account = ForeignAccount::public(
-                publisher_id,
-                AccountStorageRequirements::new([(
-                    publisher_entries_slot.clone(),
-                    &[StorageMapKey::new(faucet_id_word)],
-                )]),
-            )?;
-            foreign_accounts.push(foreign_account);
-        }
-        client.sync_state().await?;
-        eprintln!("[DBG] sync2 done after publisher imports");
-
-        let tx_script_code = format!(
-            "
-            use oracle_component::oracle_module
-            use miden::core::sys
-    
-            begin
-                push.0.{amount}.{suffix}.{prefix}
-                call.oracle_module::get_median
-                exec.sys::truncate_stack
-            end
-            ",
-            prefix = prefix,
-            suffix = suffix,
-            amount = self.amount,
-        );
-        let oracle_lib = get_oracle_component_library();
-        let median_script = CodeBuilder::default()
-            .with_dynamically_linked_library(&oracle_lib)
-            .map_err(|e| anyhow::anyhow!("Error while setting up the component library: {e:?}"))?
-            .compile_tx_script(tx_script_code.clone())
-            .map_err(|e| anyhow::anyhow!("Error while compiling the script: {e:?}"))?;
-
-        let foreign_accounts_map: BTreeMap<AccountId, ForeignAccount> = foreign_accounts
-            .into_iter()
-            .map(|fa| (fa.account_id(), fa))
-            .collect();
-        let output_stack = client
-            .execute_program(
-                oracle_id,
-                median_script,
-                AdviceInputs::default(),
-                foreign_accounts_map,
-            )
-            .await
-            .map_err(|e| anyhow::anyhow!("execute_program error: {e:?}"))?;
-
-        // Stack output: [amount, is_tracked, median_price]
-        if output_stack.len() < 3 {
-            return Err(anyhow::anyhow!(
-                "Invalid output: expected [amount, is_tracked, median_price]"
-            ));
-        }
-
-        let is_tracked = output_stack[0];
-        let median = output_stack[1];
-        let returned_amount = output_stack[2];
-
-        if is_tracked.as_canonical_u64() == 0 {
-            println!("Asset not tracked (median: 0, amount: {})", returned_amount);
-        } else {
-            println!("Median value: {} (amount: {})", median, returned_amount);
+        let registry = get_asset_registry(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE))?;
+
+        if let Some(cross) = &self.cross {
+            let (base, quote) = registry.resolve_cross(cross)?;
+            let cross_median = OracleReader::new(client, oracle_id)
+                .cross_median(base, quote)
+                .await?;
+
+            return Ok(MedianResult {
+                faucet_id: format!(
+                    "{}/{}",
+                    cross_median.base_faucet_id, cross_median.quote_faucet_id
+                ),
+                pair: Some(cross.trim().to_ascii_uppercase()),
+                is_tracked: cross_median.is_tracked,
+                median: cross_median.price,
+                // get_cross_median takes no amount
+                amount: 0,
+                decimals: Some(CROSS_PRICE_DECIMALS),
+            });
         }
 
-        Ok(median)
+        let faucet_id = self
+            .faucet_id
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("A faucet_id or a --cross pair is required"))?;
+        let asset_key = registry.resolve(faucet_id)?;
+
+        let median = OracleReader::new(client, oracle_id)
+            .median(asset_key, self.amount)
+            .await?;
+
+        Ok(MedianResult {
+            faucet_id: median.faucet_id,
+            pair: registry
+                .get_by_key(asset_key)
+                .map(|asset| asset.pair.to_string()),
+            is_tracked: median.is_tracked,
+            median: median.price,
+            amount: median.amount,
+            decimals: None,
+        })
     }
 }
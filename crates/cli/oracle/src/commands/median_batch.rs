@@ -1,44 +1,55 @@
-use anyhow::Context;
-use miden_client::account::AccountId;
-use miden_client::rpc::domain::account::AccountStorageRequirements;
-use miden_client::transaction::ForeignAccount;
-use miden_client::{keystore::FilesystemKeyStore, Client, Felt, Word, ZERO};
-use miden_protocol::account::{StorageMapKey, StorageSlotName};
-use miden_protocol::vm::AdviceInputs;
-use miden_standards::code_builder::CodeBuilder;
-use pm_accounts::oracle::get_oracle_component_library;
-
-use pm_utils_cli::{get_oracle_id, PRAGMA_ACCOUNTS_STORAGE_FILE};
+use miden_client::Client;
+use pm_consumer::{OracleReader, CROSS_PRICE_DECIMALS};
+use pm_utils_cli::{
+    get_asset_registry, get_oracle_id, plain_record, PragmaKeyStore, PRAGMA_ACCOUNTS_STORAGE_FILE,
+};
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
 use std::path::Path;
 
 #[derive(clap::Parser, Debug, Clone)]
 #[clap(about = "Compute the median for multiple faucet_ids in one batch")]
 pub struct MedianBatchCmd {
+    /// Pair symbols (e.g., BTC/USD) or asset keys (e.g., 1:0)
     pub faucet_ids: Vec<String>,
 
-    /// Output results as JSON array
-    #[clap(short = 'j', long = "json")]
+    /// Cross pairs derived from the medians of their base and quote (e.g., --cross ETH/BTC)
+    #[clap(long = "cross")]
+    pub crosses: Vec<String>,
+
+    /// Optional amount parameter passed to get_median for every faucet_id
+    /// (defaults to 0, cross pairs take none)
+    #[clap(short, long, default_value = "0")]
+    pub amount: u64,
+
+    /// Deprecated alias of `--output json`
+    #[clap(short = 'j', long = "json", hide = true)]
     pub json: bool,
 }
 
+/// Median of an asset or of a cross pair.
+///
+/// JSON schema: `{"faucet_id": string, "pair"?: string, "is_tracked": bool,
+/// "median": u64, "amount": u64, "decimals"?: u32}`. `median-batch` prints an
+/// array of them.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MedianResult {
     pub faucet_id: String,
+    /// Pair symbol of the asset, if registered
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pair: Option<String>,
     pub is_tracked: bool,
     pub median: u64,
     pub amount: u64,
+    /// Decimals of a cross median, which don't follow the decimals of its assets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decimals: Option<u32>,
 }
 
 impl MedianBatchCmd {
     /// Computes the median price for multiple trading pairs in a single batch
     ///
-    /// This function optimizes performance by:
-    /// 1. Syncing client state ONCE (instead of per-pair)
-    /// 2. Fetching Oracle account ONCE
-    /// 3. Parsing publishers ONCE
-    /// 4. Looping through pairs only for script compilation + execution
+    /// The Oracle account and its publishers are fetched ONCE for all pairs,
+    /// only the script compilation + execution is done per pair.
     ///
     /// Expected performance gain: ~52% faster than running median command N times
     ///
@@ -58,183 +69,100 @@ impl MedianBatchCmd {
     /// - Network/client issues occur during setup
     pub async fn call(
         &self,
-        client: &mut Client<FilesystemKeyStore>,
+        client: &mut Client<PragmaKeyStore>,
         network: &str,
     ) -> anyhow::Result<Vec<MedianResult>> {
-        if self.faucet_ids.is_empty() {
-            return Err(anyhow::anyhow!("No faucet_ids provided"));
+        if self.faucet_ids.is_empty() && self.crosses.is_empty() {
+            return Err(anyhow::anyhow!("No faucet_ids or cross pairs provided"));
         }
 
-        // STEP 1: Setup - done ONCE for all pairs
         let oracle_id = get_oracle_id(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network)?;
-
-        // Re-import oracle from chain to get fresh storage state. sync_state
-        // only refreshes accounts already in the tracked set, so on a fresh
-        // store (e.g. emptyDir K8s deployments) we need to import first.
-        client.import_account_by_id(oracle_id).await?;
-        client.sync_state().await?;
-        let account = client
-            .get_account(oracle_id)
-            .await?
-            .ok_or_else(|| {
-                anyhow::anyhow!(
-                    "Oracle account not found. Make sure you run this command from the oracle workspace directory (e.g., .demo-workspaces/oracle/)"
-                )
-            })?;
-
-        let storage = account.storage();
-
-        // Get publisher count from storage
-        let next_publisher_index_slot =
-            StorageSlotName::new("pragma::oracle::next_publisher_index")
-                .map_err(|e| anyhow::anyhow!("Invalid storage slot name: {e:?}"))?;
-        let publisher_count = storage
-            .get_item(&next_publisher_index_slot)
-            .context("Unable to retrieve publisher count")?[0]
-            .as_canonical_u64();
-
-        // Collect publishers from the map slot
-        let publishers_slot = StorageSlotName::new("pragma::oracle::publishers")
-            .map_err(|e| anyhow::anyhow!("Invalid storage slot name: {e:?}"))?;
-        // Publisher ID word is [prefix, suffix, 0, 0]. A slot zeroed out by
-        // `remove_publisher` decodes to AccountId(0, 0) which doesn't exist on
-        // the network — skip those slots before importing.
-        let publisher_array: Vec<AccountId> = (2..publisher_count)
-            .map(|i| -> anyhow::Result<Word> {
-                let key: [Felt; 4] = [Felt::new(i)?, ZERO, ZERO, ZERO];
-                storage
-                    .get_map_item(&publishers_slot, key.into())
-                    .with_context(|| format!("Failed to retrieve publisher at index {i}"))
+        let registry = get_asset_registry(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE))?;
+        let asset_keys = self
+            .faucet_ids
+            .iter()
+            .map(|faucet_id| registry.resolve(faucet_id))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let crosses = self
+            .crosses
+            .iter()
+            .map(|cross| registry.resolve_cross(cross))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let mut reader = OracleReader::new(client, oracle_id);
+        let medians = if asset_keys.is_empty() {
+            Vec::new()
+        } else {
+            reader.medians(&asset_keys, self.amount).await?
+        };
+        let cross_medians = if crosses.is_empty() {
+            Vec::new()
+        } else {
+            reader.cross_medians(&crosses).await?
+        };
+
+        let mut results: Vec<MedianResult> = asset_keys
+            .iter()
+            .zip(medians)
+            .map(|(asset_key, median)| MedianResult {
+                faucet_id: median.faucet_id,
+                pair: registry
+                    .get_by_key(*asset_key)
+                    .map(|asset| asset.pair.to_string()),
+                is_tracked: median.is_tracked,
+                median: median.price,
+                amount: median.amount,
+                decimals: None,
             })
-            .collect::<Result<Vec<_>, _>>()
-            .context("Failed to collect publisher array")?
-            .into_iter()
-            .filter(|w| !(w[0] == ZERO && w[1] == ZERO))
-            .map(|w| AccountId::new_unchecked([w[0], w[1]]))
             .collect();
+        results.extend(
+            self.crosses
+                .iter()
+                .zip(cross_medians)
+                .map(|(cross, cross_median)| MedianResult {
+                    faucet_id: format!(
+                        "{}/{}",
+                        cross_median.base_faucet_id, cross_median.quote_faucet_id
+                    ),
+                    pair: Some(cross.trim().to_ascii_uppercase()),
+                    is_tracked: cross_median.is_tracked,
+                    median: cross_median.price,
+                    // get_cross_median takes no amount
+                    amount: 0,
+                    decimals: Some(CROSS_PRICE_DECIMALS),
+                }),
+        );
 
-        // STEP 2: Process each faucet_id
-        let mut results = Vec::with_capacity(self.faucet_ids.len());
-
-        for faucet_id_str in &self.faucet_ids {
-            let parts: Vec<&str> = faucet_id_str.split(':').collect();
-            if parts.len() != 2 {
-                return Err(anyhow::anyhow!(
-                    "Invalid faucet_id format: {}. Expected PREFIX:SUFFIX (e.g., 1:0)",
-                    faucet_id_str
-                ));
-            }
-
-            let prefix = parts[0]
-                .parse::<u64>()
-                .map_err(|_| anyhow::anyhow!("Invalid faucet_id prefix: {}", parts[0]))?;
-            let suffix = parts[1]
-                .parse::<u64>()
-                .map_err(|_| anyhow::anyhow!("Invalid faucet_id suffix: {}", parts[1]))?;
-
-            let faucet_id_word: Word = [
-                Felt::new(0)?,
-                Felt::new(0)?,
-                Felt::new(suffix)?,
-                Felt::new(prefix)?,
-            ]
-            .into();
-
-            let mut foreign_accounts: Vec<ForeignAccount> = vec![];
-            let publisher_entries_slot = StorageSlotName::new("pragma::publisher::entries")
-                .map_err(|e| anyhow::anyhow!("Invalid storage slot name: {e:?}"))?;
-            for publisher_id in &publisher_array {
-                let foreign_account = ForeignAccount::public(
-                    *publisher_id,
-                    AccountStorageRequirements::new([(
-                        publisher_entries_slot.clone(),
-                        &[StorageMapKey::new(faucet_id_word)],
-                    )]),
-                )?;
-                foreign_accounts.push(foreign_account);
-            }
-
-            let tx_script_code = format!(
-                "
-                use oracle_component::oracle_module
-                use miden::core::sys
-        
-                begin
-                    push.0.0.{suffix}.{prefix}
-                    call.oracle_module::get_median
-                    exec.sys::truncate_stack
-                end
-                ",
-                prefix = prefix,
-                suffix = suffix,
-            );
-
-            let oracle_lib = get_oracle_component_library();
-            let median_script = CodeBuilder::default()
-                .with_dynamically_linked_library(&oracle_lib)
-                .map_err(|e| {
-                    anyhow::anyhow!("Error while setting up the component library: {e:?}")
-                })?
-                .compile_tx_script(tx_script_code.clone())
-                .map_err(|e| anyhow::anyhow!("Error while compiling the script: {e:?}"))?;
-
-            let foreign_accounts_map: BTreeMap<AccountId, ForeignAccount> = foreign_accounts
-                .into_iter()
-                .map(|fa| (fa.account_id(), fa))
-                .collect();
-
-            let output_stack = client
-                .execute_program(
-                    oracle_id,
-                    median_script,
-                    AdviceInputs::default(),
-                    foreign_accounts_map,
-                )
-                .await
-                .with_context(|| {
-                    format!("Failed to execute median for faucet_id: {}", faucet_id_str)
-                })?;
-
-            if output_stack.len() < 3 {
-                return Err(anyhow::anyhow!(
-                    "Invalid output for {}: expected [is_tracked, median_price, amount]",
-                    faucet_id_str
-                ));
-            }
-
-            let is_tracked = output_stack[0].as_canonical_u64();
-            let median = output_stack[1].as_canonical_u64();
-            let amount = output_stack[2].as_canonical_u64();
-
-            results.push(MedianResult {
-                faucet_id: faucet_id_str.clone(),
-                is_tracked: is_tracked != 0,
-                median,
-                amount,
-            });
-        }
+        Ok(results)
+    }
+}
 
-        // STEP 3: Output results
-        if self.json {
-            let json_output =
-                serde_json::to_string(&results).context("Failed to serialize results to JSON")?;
-            println!("{}", json_output);
-        } else {
-            for result in &results {
-                if result.is_tracked {
-                    println!(
-                        "{}: {} (amount: {})",
-                        result.faucet_id, result.median, result.amount
-                    );
-                } else {
-                    println!(
-                        "{}: Not tracked (amount: {})",
-                        result.faucet_id, result.amount
-                    );
-                }
-            }
+impl MedianResult {
+    pub fn table(&self) -> String {
+        let label = self.pair.as_deref().unwrap_or(&self.faucet_id);
+        match (self.is_tracked, self.decimals) {
+            (true, Some(decimals)) => format!(
+                "{label} median value: {} ({decimals} decimals, amount: {})",
+                self.median, self.amount
+            ),
+            (true, None) => format!(
+                "{label} median value: {} (amount: {})",
+                self.median, self.amount
+            ),
+            (false, _) => format!("{label} not tracked (median: 0, amount: {})", self.amount),
         }
+    }
 
-        Ok(results)
+    pub fn plain(&self) -> String {
+        plain_record([
+            self.faucet_id.clone(),
+            self.pair.clone().unwrap_or_else(|| "-".to_string()),
+            self.is_tracked.to_string(),
+            self.median.to_string(),
+            self.amount.to_string(),
+            self.decimals
+                .map(|decimals| decimals.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+        ])
     }
 }
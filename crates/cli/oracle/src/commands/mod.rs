@@ -1,35 +1,146 @@
+pub mod assets;
+pub mod consume_leave_notes;
+pub mod feed;
 pub mod get_entry;
+pub mod health;
 pub mod init;
 pub mod median;
 pub mod median_batch;
+pub mod network;
+pub mod pending_registrations;
 pub mod publishers;
+pub mod push_price;
+pub mod randomness;
+pub mod register_asset;
 pub mod register_publisher;
 pub mod remove_publisher;
+pub mod set_feed_config;
+pub mod sign;
+pub mod submit;
 pub mod sync;
-
-use std::path::PathBuf;
+pub mod volatility;
 
 use clap::Parser;
-use miden_client::Felt;
+use serde::Serialize;
 
+use assets::{AssetsCmd, AssetsOutput};
+use consume_leave_notes::ConsumeLeaveNotesCmd;
+use feed::FeedCmd;
 use get_entry::GetEntryCmd;
-use init::InitCmd;
+use health::{HealthCmd, HealthReport};
+use init::{InitCmd, InitOutput};
 use median::MedianCmd;
-use median_batch::MedianBatchCmd;
+use median_batch::{MedianBatchCmd, MedianResult};
+use network::{NetworkCmd, NetworkOutput, NetworksOutput};
+use pending_registrations::{PendingRegistrationsCmd, PendingRegistrationsOutput};
+use pm_consumer::{FeedAggregate, Randomness};
 use pm_types::Entry;
-use publishers::PublishersCmd;
+use publishers::{PublishersCmd, PublishersOutput};
+use push_price::PushPriceCmd;
+use randomness::RandomnessCmd;
+use register_asset::RegisterAssetCmd;
 use register_publisher::RegisterPublisherCmd;
 use remove_publisher::RemovePublisherCmd;
+use set_feed_config::SetFeedConfigCmd;
+use sign::SignCmd;
+use submit::SubmitCmd;
 use sync::SyncCmd;
+use volatility::{VolatilityCmd, VolatilityResult};
 
-use pm_utils_cli::{setup_devnet_client, setup_local_client, setup_testnet_client, STORE_FILENAME};
+use pm_utils_cli::{
+    entry_plain, entry_table, setup_network_client, setup_watch_only_client, to_json, Done,
+    OutputFormat, SyncOutput,
+};
 
-#[derive(Debug)]
+/// Result of a command, printed on stdout in the `--output` format.
+///
+/// The JSON document of each variant follows the schema documented on its
+/// type, without any wrapping, so that scripts parse it directly. The schemas
+/// are stable: fields may be added, never renamed or removed.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
 pub enum CommandOutput {
-    None,
-    Felt(Felt),
+    /// `init`
+    Init(InitOutput),
+    /// `sync`
+    Sync(SyncOutput),
+    /// Commands changing the state, `sign` and `network add|remove`
+    Done(Done),
+    /// `median`
+    Median(MedianResult),
+    /// `median-batch`, a JSON array of `median` results
+    MedianBatch(Vec<MedianResult>),
+    /// `volatility`
+    Volatility(VolatilityResult),
+    /// `publishers`
+    Publishers(PublishersOutput),
+    /// `get-entry`
     Entry(Entry),
-    MedianBatch(Vec<median_batch::MedianResult>),
+    /// `health`
+    Health(HealthReport),
+    /// `pending-registrations`
+    PendingRegistrations(PendingRegistrationsOutput),
+    /// `assets`
+    Assets(AssetsOutput),
+    /// `feed`
+    Feed(FeedAggregate),
+    /// `randomness`
+    Randomness(Randomness),
+    /// `network list`
+    Networks(NetworksOutput),
+}
+
+impl CommandOutput {
+    /// Renders the output in `format`, without trailing newline.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the output cannot be serialized to JSON.
+    pub fn render(&self, format: OutputFormat) -> anyhow::Result<String> {
+        if format == OutputFormat::Json {
+            return to_json(self);
+        }
+        let table = format == OutputFormat::Table;
+        Ok(match self {
+            Self::Init(init) if table => init.table(),
+            Self::Init(init) => init.plain(),
+            Self::Sync(sync) if table => sync.table(),
+            Self::Sync(sync) => sync.plain(),
+            Self::Done(done) if table => done.table(),
+            Self::Done(done) => done.plain(),
+            Self::Median(median) if table => median.table(),
+            Self::Median(median) => median.plain(),
+            Self::MedianBatch(results) => results
+                .iter()
+                .map(|result| {
+                    if table {
+                        result.table()
+                    } else {
+                        result.plain()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Self::Volatility(volatility) if table => volatility.table(),
+            Self::Volatility(volatility) => volatility.plain(),
+            Self::Publishers(publishers) if table => publishers.table(),
+            Self::Publishers(publishers) => publishers.plain(),
+            Self::Entry(entry) if table => entry_table(entry),
+            Self::Entry(entry) => entry_plain(entry),
+            Self::Health(report) if table => report.table(),
+            Self::Health(report) => report.plain(),
+            Self::PendingRegistrations(requests) if table => requests.table(),
+            Self::PendingRegistrations(requests) => requests.plain(),
+            Self::Assets(assets) if table => assets.table(),
+            Self::Assets(assets) => assets.plain(),
+            Self::Feed(aggregate) if table => feed::table(aggregate),
+            Self::Feed(aggregate) => feed::plain(aggregate),
+            Self::Randomness(randomness) if table => randomness::table(randomness),
+            Self::Randomness(randomness) => randomness::plain(randomness),
+            Self::Networks(networks) if table => networks.table(),
+            Self::Networks(networks) => networks.plain(),
+        })
+    }
 }
 
 #[derive(Debug, Parser, Clone)]
@@ -42,74 +153,92 @@ pub enum SubCommand {
     RegisterPublisher(RegisterPublisherCmd),
     #[clap(name = "remove-publisher", bin_name = "remove-publisher")]
     RemovePublisher(RemovePublisherCmd),
+    #[clap(name = "sign", bin_name = "sign")]
+    Sign(SignCmd),
+    #[clap(name = "submit", bin_name = "submit")]
+    Submit(SubmitCmd),
     #[clap(name = "median", bin_name = "median")]
     Median(MedianCmd),
     #[clap(name = "median-batch", bin_name = "median-batch")]
     MedianBatch(MedianBatchCmd),
+    #[clap(name = "volatility", bin_name = "volatility")]
+    Volatility(VolatilityCmd),
     #[clap(name = "publishers", bin_name = "publishers")]
     Publishers(PublishersCmd),
     #[clap(name = "get-entry", bin_name = "get-entry")]
     GetEntry(GetEntryCmd),
+    #[clap(name = "health", bin_name = "health")]
+    Health(HealthCmd),
+    #[clap(name = "consume-leave-notes", bin_name = "consume-leave-notes")]
+    ConsumeLeaveNotes(ConsumeLeaveNotesCmd),
+    #[clap(name = "pending-registrations", bin_name = "pending-registrations")]
+    PendingRegistrations(PendingRegistrationsCmd),
+    #[clap(name = "push-price", bin_name = "push-price")]
+    PushPrice(PushPriceCmd),
+    #[clap(name = "register-asset", bin_name = "register-asset")]
+    RegisterAsset(RegisterAssetCmd),
+    #[clap(name = "assets", bin_name = "assets")]
+    Assets(AssetsCmd),
+    #[clap(name = "feed", bin_name = "feed")]
+    Feed(FeedCmd),
+    #[clap(name = "set-feed-config", bin_name = "set-feed-config")]
+    SetFeedConfig(SetFeedConfigCmd),
+    #[clap(name = "randomness", bin_name = "randomness")]
+    Randomness(RandomnessCmd),
+    #[clap(name = "network", bin_name = "network")]
+    Network(NetworkCmd),
 }
 
 impl SubCommand {
     pub async fn call(&self, network: &str) -> anyhow::Result<CommandOutput> {
-        let crate_path = PathBuf::new();
-        let store_config = crate_path.join(STORE_FILENAME);
-        // Set up client based on network parameter
-        let mut client = match network {
-            "testnet" => {
-                println!("Using testnet client");
-                setup_testnet_client(Some(store_config), None).await?
-            }
-            "devnet" => {
-                println!("Using devnet client");
-                setup_devnet_client(Some(store_config), None).await?
-            }
-            "local" => {
-                println!("Using local client");
-                setup_local_client(Some(store_config), None).await?
-            }
-            other => {
-                return Err(anyhow::anyhow!(
-                    "Unknown network '{}'. Must be 'local', 'devnet' or 'testnet'",
-                    other
-                ));
-            }
-        };
+        // Signing runs on an offline host and profiles are managed without
+        // client.
         match self {
-            Self::Init(cmd) => {
-                cmd.call(&mut client, network).await?;
-                Ok(CommandOutput::None)
-            }
-            Self::Sync(cmd) => {
-                cmd.call(&mut client).await?;
-                Ok(CommandOutput::None)
-            }
-            Self::RegisterPublisher(cmd) => {
-                cmd.call(&mut client, network).await?;
-                Ok(CommandOutput::None)
-            }
-            Self::RemovePublisher(cmd) => {
-                cmd.call(&mut client, network).await?;
-                Ok(CommandOutput::None)
-            }
-            Self::Median(cmd) => {
-                let median = cmd.call(&mut client, network).await?;
-                Ok(CommandOutput::Felt(median))
-            }
-            Self::MedianBatch(cmd) => {
-                let results = cmd.call(&mut client, network).await?;
-                Ok(CommandOutput::MedianBatch(results))
-            }
-            Self::Publishers(cmd) => {
-                cmd.call(&mut client, network).await?;
-                Ok(CommandOutput::None)
-            }
-            Self::GetEntry(cmd) => {
-                let entry = cmd.call(&mut client, network).await?;
-                Ok(CommandOutput::Entry(entry))
+            Self::Sign(cmd) => return Ok(CommandOutput::Done(cmd.call().await?)),
+            Self::Network(cmd) => {
+                return Ok(match cmd.call()? {
+                    NetworkOutput::Done(done) => CommandOutput::Done(done),
+                    NetworkOutput::List(networks) => CommandOutput::Networks(networks),
+                });
             }
+            _ => {}
         }
+
+        // Set up client based on the network profile. Exported transactions
+        // are executed without keys, so that they stop at the signature.
+        let exports_unsigned =
+            matches!(self, Self::RegisterPublisher(cmd) if cmd.export_unsigned.is_some());
+        let mut client = if exports_unsigned {
+            eprintln!("Using watch-only {network} client");
+            setup_watch_only_client(network, None).await?
+        } else {
+            eprintln!("Using {network} client");
+            setup_network_client(network, None, None).await?
+        };
+        let client = &mut client;
+        Ok(match self {
+            Self::Init(cmd) => CommandOutput::Init(cmd.call(client, network).await?),
+            Self::Sync(cmd) => CommandOutput::Sync(cmd.call(client, network).await?),
+            Self::RegisterPublisher(cmd) => CommandOutput::Done(cmd.call(client, network).await?),
+            Self::RemovePublisher(cmd) => CommandOutput::Done(cmd.call(client, network).await?),
+            Self::Sign(_) | Self::Network(_) => unreachable!("runs without client"),
+            Self::Submit(cmd) => CommandOutput::Done(cmd.call(client, network).await?),
+            Self::Median(cmd) => CommandOutput::Median(cmd.call(client, network).await?),
+            Self::MedianBatch(cmd) => CommandOutput::MedianBatch(cmd.call(client, network).await?),
+            Self::Volatility(cmd) => CommandOutput::Volatility(cmd.call(client, network).await?),
+            Self::Publishers(cmd) => CommandOutput::Publishers(cmd.call(client, network).await?),
+            Self::GetEntry(cmd) => CommandOutput::Entry(cmd.call(client, network).await?),
+            Self::Health(cmd) => CommandOutput::Health(cmd.call(client, network).await?),
+            Self::ConsumeLeaveNotes(cmd) => CommandOutput::Done(cmd.call(client, network).await?),
+            Self::PendingRegistrations(cmd) => {
+                CommandOutput::PendingRegistrations(cmd.call(client, network).await?)
+            }
+            Self::PushPrice(cmd) => CommandOutput::Done(cmd.call(client, network).await?),
+            Self::RegisterAsset(cmd) => CommandOutput::Done(cmd.call(client, network).await?),
+            Self::Assets(cmd) => CommandOutput::Assets(cmd.call(client, network).await?),
+            Self::Feed(cmd) => CommandOutput::Feed(cmd.call(client, network).await?),
+            Self::SetFeedConfig(cmd) => CommandOutput::Done(cmd.call(client, network).await?),
+            Self::Randomness(cmd) => CommandOutput::Randomness(cmd.call(client, network).await?),
+        })
     }
 }
@@ -0,0 +1,216 @@
+use std::path::{Path, PathBuf};
+
+use pm_utils_cli::{
+    list_network_profiles, plain_record, remove_network_profile, set_network_profile, Done,
+    NetworkProfile, RetryPolicy, BUILTIN_NETWORKS, DEFAULT_INITIAL_BACKOFF_MS,
+    DEFAULT_MAX_ATTEMPTS, DEFAULT_MAX_BACKOFF_MS, DEFAULT_RPC_TIMEOUT_MS,
+    PRAGMA_ACCOUNTS_STORAGE_FILE,
+};
+use prettytable::{Cell, Row, Table};
+use serde::Serialize;
+
+#[derive(clap::Parser, Debug, Clone)]
+#[clap(about = "Manages the network profiles usable with --network (runs offline)")]
+pub struct NetworkCmd {
+    #[command(subcommand)]
+    pub action: NetworkAction,
+}
+
+#[derive(clap::Subcommand, Debug, Clone)]
+pub enum NetworkAction {
+    /// Adds a network profile, or replaces it
+    Add {
+        /// Name of the network, given to --network
+        name: String,
+        /// gRPC endpoint of the node, e.g. http://10.0.0.2:57291
+        #[clap(long)]
+        rpc_url: String,
+        /// Remote prover proving the transactions. Transactions are proved
+        /// locally without it
+        #[clap(long)]
+        prover_url: Option<String>,
+        /// Timeout of every RPC call, in milliseconds
+        #[clap(long, default_value_t = DEFAULT_RPC_TIMEOUT_MS)]
+        timeout_ms: u64,
+        /// Client store of the network. Defaults to the shared store
+        #[clap(long)]
+        store_path: Option<PathBuf>,
+        /// Attempts of an RPC call failing with a transient error, the first
+        /// one included. 1 disables retries
+        #[clap(long, default_value_t = DEFAULT_MAX_ATTEMPTS)]
+        max_attempts: u32,
+        /// Delay before the first retry, in milliseconds. It doubles on every
+        /// retry
+        #[clap(long, default_value_t = DEFAULT_INITIAL_BACKOFF_MS)]
+        initial_backoff_ms: u64,
+        /// Upper bound of the delay between two retries, in milliseconds
+        #[clap(long, default_value_t = DEFAULT_MAX_BACKOFF_MS)]
+        max_backoff_ms: u64,
+    },
+    /// Lists the built-in networks and the network profiles
+    List,
+    /// Removes a network profile, keeping the account ids of the network
+    Remove {
+        /// Name of the network
+        name: String,
+    },
+}
+
+/// Built-in networks and network profiles.
+///
+/// JSON schema: `{"networks": [{"name": string, "rpc_url": string,
+/// "prover_url"?: string, "timeout_ms": u64, "store_path"?: string,
+/// "retry": {"max_attempts": u32, "initial_backoff_ms": u64,
+/// "max_backoff_ms": u64}}]}`, `prover_url` and `store_path` being omitted
+/// when proving locally and using the shared store.
+#[derive(Debug, Clone, Serialize)]
+pub struct NetworksOutput {
+    pub networks: Vec<NetworkEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NetworkEntry {
+    pub name: String,
+    #[serde(flatten)]
+    pub profile: NetworkProfile,
+}
+
+/// Result of `network`: a change of the profiles or their list.
+#[derive(Debug)]
+pub enum NetworkOutput {
+    Done(Done),
+    List(NetworksOutput),
+}
+
+impl NetworkCmd {
+    /// Adds, lists or removes network profiles in the configuration file
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The configuration file cannot be read or written
+    /// - The RPC URL is not a valid endpoint
+    /// - The retry policy allows no attempt
+    /// - The profile to remove does not exist
+    pub fn call(&self) -> anyhow::Result<NetworkOutput> {
+        let storage_path = Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE);
+        match &self.action {
+            NetworkAction::Add {
+                name,
+                rpc_url,
+                prover_url,
+                timeout_ms,
+                store_path,
+                max_attempts,
+                initial_backoff_ms,
+                max_backoff_ms,
+            } => {
+                if *max_attempts == 0 {
+                    anyhow::bail!("--max-attempts must be at least 1");
+                }
+                let profile = NetworkProfile {
+                    rpc_url: rpc_url.clone(),
+                    prover_url: prover_url.clone(),
+                    timeout_ms: *timeout_ms,
+                    store_path: store_path.clone(),
+                    retry: RetryPolicy {
+                        max_attempts: *max_attempts,
+                        initial_backoff_ms: *initial_backoff_ms,
+                        max_backoff_ms: *max_backoff_ms,
+                    },
+                };
+                profile.endpoint()?;
+                set_network_profile(storage_path, name, &profile)?;
+                Ok(NetworkOutput::Done(Done::new(format!(
+                    "Network '{name}' saved, use it with --network {name}"
+                ))))
+            }
+            NetworkAction::List => Ok(NetworkOutput::List(NetworksOutput {
+                networks: list_network_profiles(storage_path)?
+                    .into_iter()
+                    .map(|(name, profile)| NetworkEntry { name, profile })
+                    .collect(),
+            })),
+            NetworkAction::Remove { name } => {
+                if !remove_network_profile(storage_path, name)? {
+                    anyhow::bail!("No profile found for network '{name}'");
+                }
+                let message = if BUILTIN_NETWORKS.contains(&name.as_str()) {
+                    format!("Network '{name}' is back to its built-in profile")
+                } else {
+                    format!("Network '{name}' removed")
+                };
+                Ok(NetworkOutput::Done(Done::new(message)))
+            }
+        }
+    }
+}
+
+impl NetworkEntry {
+    fn store(&self) -> String {
+        self.profile
+            .store_path
+            .as_ref()
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|| "default".to_string())
+    }
+}
+
+impl NetworksOutput {
+    pub fn table(&self) -> String {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_BOX_CHARS);
+        table.add_row(Row::new(vec![
+            Cell::new("Network").style_spec("Fcb"),
+            Cell::new("RPC").style_spec("Fcb"),
+            Cell::new("Prover").style_spec("Fcb"),
+            Cell::new("Timeout (ms)").style_spec("Fcb"),
+            Cell::new("Attempts").style_spec("Fcb"),
+            Cell::new("Backoff (ms)").style_spec("Fcb"),
+            Cell::new("Store").style_spec("Fcb"),
+        ]));
+        for network in &self.networks {
+            let profile = &network.profile;
+            table.add_row(Row::new(vec![
+                Cell::new(&network.name).style_spec("Fy"),
+                Cell::new(&profile.rpc_url).style_spec("Fw"),
+                Cell::new(profile.prover_url.as_deref().unwrap_or("local proving"))
+                    .style_spec("Fw"),
+                Cell::new(&profile.timeout_ms.to_string()).style_spec("Fw"),
+                Cell::new(&profile.retry.max_attempts.to_string()).style_spec("Fw"),
+                Cell::new(&format!(
+                    "{}-{}",
+                    profile.retry.initial_backoff_ms, profile.retry.max_backoff_ms
+                ))
+                .style_spec("Fw"),
+                Cell::new(&network.store()).style_spec("Fw"),
+            ]));
+        }
+        table.to_string()
+    }
+
+    /// One line per network: name, RPC, prover (`-` when proving locally),
+    /// timeout, attempts, initial and max backoff, store.
+    pub fn plain(&self) -> String {
+        self.networks
+            .iter()
+            .map(|network| {
+                let profile = &network.profile;
+                plain_record([
+                    network.name.clone(),
+                    profile.rpc_url.clone(),
+                    profile
+                        .prover_url
+                        .clone()
+                        .unwrap_or_else(|| "-".to_string()),
+                    profile.timeout_ms.to_string(),
+                    profile.retry.max_attempts.to_string(),
+                    profile.retry.initial_backoff_ms.to_string(),
+                    profile.retry.max_backoff_ms.to_string(),
+                    network.store(),
+                ])
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
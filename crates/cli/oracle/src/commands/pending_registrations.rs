@@ -0,0 +1,221 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use colored::*;
+use miden_client::account::AccountId;
+use miden_client::note::Note;
+use miden_client::transaction::TransactionRequestBuilder;
+use miden_client::Client;
+use pm_accounts::notes::get_registration_note_script;
+use pm_utils_cli::{
+    get_oracle_id, plain_record, retry_policy, submit_transaction_with_retry,
+    sync_state_with_retry, PragmaKeyStore, PRAGMA_ACCOUNTS_STORAGE_FILE,
+};
+use prettytable::{Cell, Row, Table};
+use serde::Serialize;
+
+#[derive(clap::Parser, Debug, Clone)]
+#[clap(about = "Lists the pending publisher registration requests, and optionally accepts them")]
+pub struct PendingRegistrationsCmd {
+    /// Accept the pending requests, registering each requesting publisher
+    #[clap(long)]
+    pub accept: bool,
+
+    /// Only accept the requests of these publisher ids (defaults to every pending request)
+    #[clap(long = "publisher-id")]
+    pub publisher_ids: Vec<String>,
+}
+
+/// Registration requests targeting the Oracle.
+///
+/// JSON schema: `{"requests": [{"note_id": string, "publisher_id": string,
+/// "status": "pending" | "accepted" | "failed"}]}`.
+#[derive(Debug, Clone, Serialize)]
+pub struct PendingRegistrationsOutput {
+    pub requests: Vec<RegistrationRequest>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RegistrationRequest {
+    pub note_id: String,
+    pub publisher_id: String,
+    pub status: RegistrationStatus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegistrationStatus {
+    Pending,
+    Accepted,
+    Failed,
+}
+
+impl PendingRegistrationsCmd {
+    /// Lists the registration request notes targeting the Oracle
+    ///
+    /// With `--accept`, each request is consumed in its own transaction, which
+    /// runs `register_publisher` for the note sender. A request that fails (e.g.
+    /// the publisher is already registered) does not block the other ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - A mutable reference to the Miden client, to be initialized first
+    /// * `network` - The network identifier (e.g., "devnet", "testnet")
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The Oracle ID cannot be retrieved from configuration
+    /// - A publisher ID cannot be parsed
+    /// - The client fails to sync state with the network
+    /// - The registration note script compilation fails
+    pub async fn call(
+        &self,
+        client: &mut Client<PragmaKeyStore>,
+        network: &str,
+    ) -> anyhow::Result<PendingRegistrationsOutput> {
+        let retry = retry_policy(network);
+        let oracle_id = get_oracle_id(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network)?;
+        let accepted_ids = self
+            .publisher_ids
+            .iter()
+            .map(|id| AccountId::from_hex(id))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| anyhow::anyhow!("Invalid publisher id: {e}"))?;
+
+        sync_state_with_retry(client, &retry)
+            .await
+            .map_err(|e| anyhow::anyhow!("Error while syncing state: {e:?}"))?;
+
+        // The oracle id is baked into the registration note script, so its root
+        // only matches the requests targeting this oracle.
+        let registration_script_root = get_registration_note_script(oracle_id)?.root();
+        let requests: Vec<_> = client
+            .get_consumable_notes(Some(oracle_id))
+            .await?
+            .into_iter()
+            .map(|(record, _)| record)
+            .filter(|record| record.details().script().root() == registration_script_root)
+            .filter_map(|record| {
+                // Records without metadata cannot be consumed yet.
+                let note: Note = record.try_into().ok()?;
+                let sender = note.metadata().sender();
+                Some((note, sender))
+            })
+            .collect();
+
+        let mut output = PendingRegistrationsOutput {
+            requests: requests
+                .iter()
+                .map(|(note, sender)| RegistrationRequest {
+                    note_id: note.id().to_string(),
+                    publisher_id: sender.to_hex(),
+                    status: RegistrationStatus::Pending,
+                })
+                .collect(),
+        };
+
+        if !self.accept || requests.is_empty() {
+            return Ok(output);
+        }
+
+        for ((note, sender), request) in requests.into_iter().zip(&mut output.requests) {
+            if !accepted_ids.is_empty() && !accepted_ids.contains(&sender) {
+                continue;
+            }
+
+            let note_id = note.id();
+            let transaction_request = TransactionRequestBuilder::new()
+                .build_consume_notes(vec![note])
+                .map_err(|e| anyhow::anyhow!("Error while building transaction request: {e:?}"))?;
+
+            match submit_transaction_with_retry(client, oracle_id, transaction_request, &retry)
+                .await
+            {
+                Ok(_) => request.status = RegistrationStatus::Accepted,
+                Err(e) => {
+                    eprintln!(
+                        "❌ Could not accept registration request {} from {}: {e:?}",
+                        note_id,
+                        sender.to_hex()
+                    );
+                    request.status = RegistrationStatus::Failed;
+                }
+            }
+        }
+
+        sync_state_with_retry(client, &retry)
+            .await
+            .map_err(|e| anyhow::anyhow!("Error while syncing state after register: {e:?}"))?;
+
+        Ok(output)
+    }
+}
+
+impl RegistrationStatus {
+    fn label(self) -> &'static str {
+        match self {
+            RegistrationStatus::Pending => "pending",
+            RegistrationStatus::Accepted => "accepted",
+            RegistrationStatus::Failed => "failed",
+        }
+    }
+}
+
+impl PendingRegistrationsOutput {
+    pub fn table(&self) -> String {
+        if self.requests.is_empty() {
+            return "ℹ️  No pending registration requests.".to_string();
+        }
+
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_BOX_CHARS);
+        table.add_row(Row::new(vec![
+            Cell::new("Note ID").style_spec("Fcb"),
+            Cell::new("Publisher ID").style_spec("Fcb"),
+            Cell::new("Status").style_spec("Fcb"),
+        ]));
+        for request in &self.requests {
+            let status = match request.status {
+                RegistrationStatus::Pending => "Pending ⏳",
+                RegistrationStatus::Accepted => "Registered ✅",
+                RegistrationStatus::Failed => "Failed ❌",
+            };
+            table.add_row(Row::new(vec![
+                Cell::new(&request.note_id).style_spec("Fw"),
+                Cell::new(&request.publisher_id).style_spec("Fy"),
+                Cell::new(status).style_spec("Fw"),
+            ]));
+        }
+
+        let mut out = table.to_string();
+        if self
+            .requests
+            .iter()
+            .all(|request| request.status == RegistrationStatus::Pending)
+        {
+            write!(
+                out,
+                "\n{}",
+                "💡 Accept them with: pm-oracle pending-registrations --accept".bright_blue()
+            )
+            .unwrap();
+        }
+        out
+    }
+
+    /// One line per request: note id, publisher id, status.
+    pub fn plain(&self) -> String {
+        self.requests
+            .iter()
+            .map(|request| {
+                plain_record([
+                    request.note_id.as_str(),
+                    request.publisher_id.as_str(),
+                    request.status.label(),
+                ])
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
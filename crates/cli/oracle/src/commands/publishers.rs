@@ -1,44 +1,63 @@
+use std::fmt::Write;
 use std::path::Path;
 
-use anyhow::Context;
 use colored::*;
-use miden_client::account::AccountId;
-use miden_client::{keystore::FilesystemKeyStore, Client, Felt, ZERO};
-use miden_protocol::account::StorageSlotName;
-use pm_utils_cli::{get_oracle_id, PRAGMA_ACCOUNTS_STORAGE_FILE};
+use miden_client::Client;
+use pm_consumer::OracleReader;
+use pm_utils_cli::{get_oracle_id, plain_record, PragmaKeyStore, PRAGMA_ACCOUNTS_STORAGE_FILE};
 use prettytable::{Cell, Row, Table};
+use serde::Serialize;
 
 #[derive(clap::Parser, Debug, Clone)]
 #[clap(about = "Fetches the registered publishers")]
 pub struct PublishersCmd {}
 
+/// Publishers registered in the oracle.
+///
+/// JSON schema: `{"oracle_id": string, "publishers": [{"index": u64,
+/// "publisher_id": string | null, "active": bool}]}`, a removed publisher
+/// leaving an inactive slot without id.
+#[derive(Debug, Clone, Serialize)]
+pub struct PublishersOutput {
+    pub oracle_id: String,
+    pub publishers: Vec<PublisherSlot>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PublisherSlot {
+    pub index: u64,
+    pub publisher_id: Option<String>,
+    pub active: bool,
+}
+
 impl PublishersCmd {
     pub async fn call(
         &self,
-        client: &mut Client<FilesystemKeyStore>,
+        client: &mut Client<PragmaKeyStore>,
         network: &str,
-    ) -> anyhow::Result<()> {
-        client.sync_state().await.unwrap();
+    ) -> anyhow::Result<PublishersOutput> {
         let oracle_id = get_oracle_id(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network)?;
+        let publishers = OracleReader::new(client, oracle_id).publishers().await?;
+
+        Ok(PublishersOutput {
+            oracle_id: oracle_id.to_hex(),
+            publishers: publishers
+                .iter()
+                .map(|slot| PublisherSlot {
+                    index: slot.index - 1,
+                    publisher_id: slot.publisher_id.map(|id| id.to_hex()),
+                    active: slot.publisher_id.is_some(),
+                })
+                .collect(),
+        })
+    }
+}
 
-        let account = client
-            .get_account(oracle_id)
-            .await
-            .unwrap()
-            .expect("Oracle account not found");
-
-        let storage = account.storage();
-
-        let next_index_slot = StorageSlotName::new("pragma::oracle::next_publisher_index")
-            .map_err(|e| anyhow::anyhow!("Invalid storage slot name: {e:?}"))?;
-        let next_index = storage
-            .get_item(&next_index_slot)
-            .context("Unable to retrieve publisher count")?[0]
-            .as_canonical_u64();
-
-        let publisher_count = next_index.saturating_sub(2);
-
-        println!(
+impl PublishersOutput {
+    pub fn table(&self) -> String {
+        let mut out = String::new();
+        writeln!(
+            out,
             "{}",
             r#"
         ╭──────────────────────────────────────────────╮
@@ -46,28 +65,35 @@ impl PublishersCmd {
         ╰──────────────────────────────────────────────╯
         "#
             .bright_cyan()
-        );
-
-        println!("{}", format!("🔍 Oracle ID: {}", oracle_id).bright_yellow());
-        println!(
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "{}",
+            format!("🔍 Oracle ID: {}", self.oracle_id).bright_yellow()
+        )
+        .unwrap();
+        writeln!(
+            out,
             "{}",
-            format!("📊 Total Publishers: {}\n", publisher_count).bright_yellow()
-        );
+            format!("📊 Total Publishers: {}\n", self.publishers.len()).bright_yellow()
+        )
+        .unwrap();
 
-        if next_index <= 2 {
-            println!(
+        if self.publishers.is_empty() {
+            write!(
+                out,
                 "{}",
                 r#"
             ℹ️  No publishers registered yet!
             Use 'pm-oracle-cli register-publisher [PUBLISHER_ID]' to add publishers.
             "#
                 .bright_yellow()
-            );
-            return Ok(());
+            )
+            .unwrap();
+            return out;
         }
 
-        let publishers_slot = StorageSlotName::new("pragma::oracle::publishers")
-            .map_err(|e| anyhow::anyhow!("Invalid storage slot name: {e:?}"))?;
         let mut table = Table::new();
         table.set_format(*prettytable::format::consts::FORMAT_BOX_CHARS);
         table.add_row(Row::new(vec![
@@ -76,43 +102,47 @@ impl PublishersCmd {
             Cell::new("Status").style_spec("Fcb"),
         ]));
 
-        for i in 2..next_index {
-            // KEY raw = [idx, 0, 0, 0] — get_stack_word_be maps stack bottom -> word[0]
-            // MASM stack has idx at bottom of KEY word, so word[0]=idx
-            let key: [Felt; 4] = [Felt::new(i)?, ZERO, ZERO, ZERO];
-            let publisher_word = storage
-                .get_map_item(&publishers_slot, key.into())
-                .with_context(|| format!("Failed to retrieve publisher at index {i}"))?;
-            // In 0.14 LE, publisher ID word is [prefix, suffix, 0, 0]
-            // (matches median.rs and the on-chain storage layout).
-            let publisher_id = AccountId::new_unchecked([publisher_word[0], publisher_word[1]]);
-
-            let status = if publisher_word != [ZERO, ZERO, ZERO, ZERO].into() {
+        for slot in &self.publishers {
+            let status = if slot.active {
                 "Active ✅"
             } else {
                 "Inactive ❌"
             };
-
             table.add_row(Row::new(vec![
-                Cell::new(&format!("{}", i - 1)).style_spec("Fg"),
-                Cell::new(&publisher_id.to_hex().to_string()).style_spec("Fy"),
+                Cell::new(&slot.index.to_string()).style_spec("Fg"),
+                Cell::new(slot.publisher_id.as_deref().unwrap_or("-")).style_spec("Fy"),
                 Cell::new(status).style_spec("Fw"),
             ]));
         }
 
-        table.printstd();
-
-        println!(
+        write!(out, "{table}").unwrap();
+        write!(
+            out,
             "\n{}",
             r#"
         💡 Tips:
         • Calculate median: pm-oracle-cli median [FAUCET_ID]
         • Register new publisher: pm-oracle-cli register-publisher [PUBLISHER_ID]
-        • Faucet IDs: 1:0=BTC/USD, 2:0=ETH/USD, 3:0=SOL/USD
+        • Supported assets: pm-oracle-cli assets
         "#
             .bright_blue()
-        );
+        )
+        .unwrap();
+        out
+    }
 
-        Ok(())
+    /// One line per slot: index, publisher id (`-` once removed), active.
+    pub fn plain(&self) -> String {
+        self.publishers
+            .iter()
+            .map(|slot| {
+                plain_record([
+                    slot.index.to_string(),
+                    slot.publisher_id.clone().unwrap_or_else(|| "-".to_string()),
+                    slot.active.to_string(),
+                ])
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 }
@@ -0,0 +1,122 @@
+use std::path::Path;
+
+use miden_client::{
+    account::AccountId, crypto::FeltRng, transaction::TransactionRequestBuilder, Client,
+};
+use pm_accounts::notes::{create_price_push_note, PriceSnapshot};
+use pm_consumer::OracleReader;
+use pm_utils_cli::{
+    get_asset_registry, get_oracle_id, retry_policy, submit_transaction_with_retry, Done,
+    PragmaKeyStore, PRAGMA_ACCOUNTS_STORAGE_FILE,
+};
+
+#[derive(clap::Parser, Debug, Clone)]
+#[clap(about = "Pushes the current median of a faucet_id to a consumer account with a note")]
+pub struct PushPriceCmd {
+    /// Pair symbol or faucet id of the asset to push (e.g., BTC/USD or 1:0)
+    pub faucet_id: String,
+
+    /// Id of the consumer account, which must carry the price receiver component
+    #[clap(long)]
+    pub to: String,
+
+    /// Decimals of the entries published for this asset (e.g., 8), defaults to the
+    /// decimals of the registered asset
+    #[clap(long)]
+    pub decimals: Option<u32>,
+}
+
+impl PushPriceCmd {
+    /// Sends the current median of the asset to a consumer account
+    ///
+    /// This function performs the following operations:
+    /// 1. Computes the current median of the asset, and reads the timestamp
+    ///    of the newest entry behind it
+    /// 2. Builds a price-push note carrying the snapshot, tagged for the consumer
+    /// 3. Submits a transaction from the Oracle account creating the note
+    ///
+    /// The note sender is the Oracle, which is what the consumer's price
+    /// receiver checks before storing the snapshot.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - A mutable reference to the Miden client, to be initialized first
+    /// * `network` - The network identifier (e.g., "devnet", "testnet")
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The Oracle ID cannot be retrieved from configuration
+    /// - The faucet_id or the consumer id cannot be parsed
+    /// - The asset is not tracked by the Oracle
+    /// - The note script compilation fails
+    /// - The transaction submission fails
+    pub async fn call(
+        &self,
+        client: &mut Client<PragmaKeyStore>,
+        network: &str,
+    ) -> anyhow::Result<Done> {
+        let retry = retry_policy(network);
+        let oracle_id = get_oracle_id(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network)?;
+        let consumer_id = AccountId::from_hex(&self.to)
+            .map_err(|e| anyhow::anyhow!("Invalid consumer id {}: {e}", self.to))?;
+
+        let registry = get_asset_registry(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE))?;
+        let asset_key = registry.resolve(&self.faucet_id)?;
+        let decimals = self
+            .decimals
+            .or_else(|| registry.get_by_key(asset_key).map(|asset| asset.decimals))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Asset {} is not registered, its decimals must be given with --decimals",
+                    self.faucet_id
+                )
+            })?;
+
+        let mut reader = OracleReader::new(client, oracle_id);
+        let median = reader.median(asset_key, 0).await?;
+        if !median.is_tracked {
+            return Err(anyhow::anyhow!(
+                "Asset {} is not tracked by the Oracle, nothing to push",
+                registry.label(asset_key)
+            ));
+        }
+        // The snapshot is stamped with the newest entry behind the median, the
+        // time of the price rather than of the push, which the receiver
+        // compares with the stored snapshot. Entries older than the ones the
+        // median keeps cannot be the newest one.
+        let mut timestamp = 0;
+        for publisher_id in reader.active_publishers().await? {
+            timestamp = timestamp.max(reader.entry(publisher_id, asset_key).await?.timestamp);
+        }
+
+        let snapshot = PriceSnapshot {
+            faucet_id_prefix: asset_key.prefix(),
+            faucet_id_suffix: asset_key.suffix(),
+            median: median.price,
+            decimals,
+            timestamp,
+        };
+        let serial_num = client.rng().draw_word();
+        let note = create_price_push_note(oracle_id, consumer_id, &snapshot, serial_num)?;
+        let note_id = note.id();
+
+        let transaction_request = TransactionRequestBuilder::new()
+            .own_output_notes(vec![note])
+            .build()
+            .map_err(|e| anyhow::anyhow!("Error while building transaction request: {e:?}"))?;
+
+        submit_transaction_with_retry(client, oracle_id, transaction_request, &retry)
+            .await
+            .map_err(|e| anyhow::anyhow!("Error while submitting transaction: {e:?}"))?;
+
+        Ok(Done::new(format!(
+            "{} price note {} sent to {} (median: {}, decimals: {})",
+            registry.label(asset_key),
+            note_id,
+            consumer_id.to_hex(),
+            median.price,
+            decimals
+        )))
+    }
+}
@@ -0,0 +1,51 @@
+use miden_client::Client;
+use pm_consumer::{OracleReader, Randomness};
+use pm_utils_cli::{get_oracle_id, plain_record, PragmaKeyStore, PRAGMA_ACCOUNTS_STORAGE_FILE};
+use std::path::Path;
+
+#[derive(clap::Parser, Debug, Clone)]
+#[clap(about = "Combine the randomness revealed by the publishers for a round")]
+pub struct RandomnessCmd {
+    /// Round to get the randomness of
+    pub round: u64,
+}
+
+impl RandomnessCmd {
+    /// Gets the randomness of a round
+    ///
+    /// Only the publishers that revealed their randomness (see
+    /// `pm-publisher reveal-randomness`) contribute to it, so the result of a
+    /// round should be read once every publisher had the chance to reveal.
+    pub async fn call(
+        &self,
+        client: &mut Client<PragmaKeyStore>,
+        network: &str,
+    ) -> anyhow::Result<Randomness> {
+        let oracle_id = get_oracle_id(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network)?;
+
+        OracleReader::new(client, oracle_id)
+            .randomness(self.round)
+            .await
+    }
+}
+
+/// Renders the randomness of a round. Its JSON schema is the one of
+/// [`Randomness`]: `{"round": u64, "nb_of_reveals": u64, "value": string}`.
+pub fn table(randomness: &Randomness) -> String {
+    if randomness.nb_of_reveals > 0 {
+        format!(
+            "Round {} randomness: {} ({} reveals)",
+            randomness.round, randomness.value, randomness.nb_of_reveals
+        )
+    } else {
+        format!("Round {} not revealed yet", randomness.round)
+    }
+}
+
+pub fn plain(randomness: &Randomness) -> String {
+    plain_record([
+        randomness.round.to_string(),
+        randomness.nb_of_reveals.to_string(),
+        randomness.value.clone(),
+    ])
+}
@@ -0,0 +1,112 @@
+use std::path::Path;
+use std::str::FromStr;
+
+use miden_client::transaction::TransactionRequestBuilder;
+use miden_client::Client;
+use miden_standards::code_builder::CodeBuilder;
+use pm_accounts::oracle::get_oracle_component_library;
+use pm_accounts::utils::word_to_masm;
+use pm_types::{AssetInfoWord, Pair};
+use pm_utils_cli::{
+    get_asset_registry, get_oracle_id, retry_policy, submit_transaction_with_retry,
+    sync_state_with_retry, Done, PragmaKeyStore, PRAGMA_ACCOUNTS_STORAGE_FILE,
+};
+
+#[derive(clap::Parser, Debug, Clone)]
+#[clap(about = "Registers an asset name and its decimals into the Oracle asset registry")]
+pub struct RegisterAssetCmd {
+    /// Pair of the asset (e.g., BTC/USD)
+    pub pair: String,
+
+    /// Faucet id of the asset (e.g., 1:0), defaults to the one of the registered pair
+    #[clap(long)]
+    pub faucet_id: Option<String>,
+
+    /// Decimals of the asset prices, defaults to the ones of the registered pair
+    #[clap(long)]
+    pub decimals: Option<u32>,
+}
+
+impl RegisterAssetCmd {
+    /// Registers an asset into the Oracle asset registry
+    ///
+    /// Registering an asset again updates its name and decimals. Once
+    /// registered, the asset is listed by `pm-oracle-cli assets` and by the
+    /// Oracle `list_assets` procedure.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - A mutable reference to the Miden client, to be initialized first
+    /// * `network` - The network identifier (e.g., "devnet", "testnet")
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The Oracle ID cannot be retrieved from configuration
+    /// - The pair is invalid, or unknown while no faucet id or decimals are given
+    /// - The transaction script compilation fails
+    /// - The transaction submission fails
+    pub async fn call(
+        &self,
+        client: &mut Client<PragmaKeyStore>,
+        network: &str,
+    ) -> anyhow::Result<Done> {
+        let retry = retry_policy(network);
+        let oracle_id = get_oracle_id(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network)?;
+        let registry = get_asset_registry(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE))?;
+
+        let pair = Pair::from_str(&self.pair)?;
+        let known = registry.get(&pair);
+        let asset_key = match &self.faucet_id {
+            Some(faucet_id) => registry.resolve(faucet_id)?,
+            None => known.map(|asset| asset.asset_key).ok_or_else(|| {
+                anyhow::anyhow!("Unknown pair {pair}, its faucet id must be given with --faucet-id")
+            })?,
+        };
+        let decimals = self
+            .decimals
+            .or_else(|| known.map(|asset| asset.decimals))
+            .ok_or_else(|| {
+                anyhow::anyhow!("Unknown pair {pair}, its decimals must be given with --decimals")
+            })?;
+        let asset_info = AssetInfoWord::new(&pair, decimals)?;
+
+        let tx_script_code = format!(
+            "
+            use oracle_component::oracle_module
+            use miden::core::sys
+            begin
+                push.{asset_info}
+                push.{asset_key}
+                call.oracle_module::register_asset
+                exec.sys::truncate_stack
+            end
+            ",
+            asset_info = word_to_masm(asset_info.to_push_word()),
+            asset_key = word_to_masm(asset_key.to_word()),
+        );
+        let oracle_lib = get_oracle_component_library();
+        let register_script = CodeBuilder::default()
+            .with_dynamically_linked_library(&oracle_lib)
+            .map_err(|e| anyhow::anyhow!("Error while setting up the component library: {e:?}"))?
+            .compile_tx_script(tx_script_code)
+            .map_err(|e| anyhow::anyhow!("Error while compiling the script: {e:?}"))?;
+
+        let transaction_request = TransactionRequestBuilder::new()
+            .custom_script(register_script)
+            .build()
+            .map_err(|e| anyhow::anyhow!("Error while building transaction request: {e:?}"))?;
+
+        submit_transaction_with_retry(client, oracle_id, transaction_request, &retry)
+            .await
+            .map_err(|e| anyhow::anyhow!("Error while submitting transaction: {e:?}"))?;
+
+        sync_state_with_retry(client, &retry)
+            .await
+            .map_err(|e| anyhow::anyhow!("Error while syncing state after register: {e:?}"))?;
+
+        Ok(Done::new(format!(
+            "{pair} registered as {asset_key} ({decimals} decimals)"
+        )))
+    }
+}
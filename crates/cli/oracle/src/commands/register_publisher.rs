@@ -1,17 +1,28 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use miden_client::account::AccountId;
 use miden_client::transaction::TransactionRequestBuilder;
-use miden_client::{keystore::FilesystemKeyStore, Client};
+use miden_client::Client;
 use miden_standards::code_builder::CodeBuilder;
 use pm_accounts::oracle::get_oracle_component_library;
-use pm_utils_cli::{get_oracle_id, PRAGMA_ACCOUNTS_STORAGE_FILE};
+use pm_utils_cli::{
+    dry_run_transaction, get_oracle_id, retry_policy, submit_transaction_with_retry,
+    sync_state_with_retry, Done, PragmaKeyStore, UnsignedTransaction, PRAGMA_ACCOUNTS_STORAGE_FILE,
+};
 
 #[derive(clap::Parser, Debug, Clone)]
 #[clap(about = "Registers a publisher id into the Oracle")]
 pub struct RegisterPublisherCmd {
     // The id of the publisher
     pub publisher_id: String,
+    /// Execute the transaction locally and print the storage changes it would
+    /// make, without proving nor submitting it
+    #[clap(long)]
+    pub dry_run: bool,
+    /// Write the transaction to this file instead of submitting it, to be
+    /// signed offline with `sign` and submitted with `submit`
+    #[clap(long, value_name = "PATH", conflicts_with = "dry_run")]
+    pub export_unsigned: Option<PathBuf>,
 }
 
 impl RegisterPublisherCmd {
@@ -21,7 +32,12 @@ impl RegisterPublisherCmd {
     /// 1. Retrieves the Oracle account ID from configuration
     /// 2. Verifies that the Oracle account exists
     /// 3. Constructs a transaction script that calls the register_publisher function
-    /// 4. Submits the transaction to the Miden network
+    /// 4. Submits the transaction to the Miden network, or only executes it
+    ///    locally and prints its storage changes with `--dry-run`, or exports
+    ///    it to be signed offline with `--export-unsigned`
+    ///
+    /// With `--export-unsigned`, `client` must hold no key of the Oracle, so
+    /// that the execution stops at the signature.
     ///
     /// # Arguments
     ///
@@ -43,9 +59,10 @@ impl RegisterPublisherCmd {
     /// - The transaction submission fails
     pub async fn call(
         &self,
-        client: &mut Client<FilesystemKeyStore>,
+        client: &mut Client<PragmaKeyStore>,
         network: &str,
-    ) -> anyhow::Result<()> {
+    ) -> anyhow::Result<Done> {
+        let retry = retry_policy(network);
         let oracle_id = get_oracle_id(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network)?;
 
         // just assert that the account exists
@@ -77,23 +94,35 @@ impl RegisterPublisherCmd {
             .compile_tx_script(tx_script_code)
             .map_err(|e| anyhow::anyhow!("Error while compiling the script: {e:?}"))?;
 
+        if let Some(path) = &self.export_unsigned {
+            let unsigned = UnsignedTransaction::export(client, oracle_id, register_script).await?;
+            unsigned.write(path)?;
+            return Ok(Done::new(format!(
+                "Unsigned transaction written to {}, sign it offline with `sign`",
+                path.display()
+            )));
+        }
+
         let transaction_request = TransactionRequestBuilder::new()
             .custom_script(register_script)
             .build()
             .map_err(|e| anyhow::anyhow!("Error while building transaction request: {e:?}"))?;
 
-        client
-            .submit_new_transaction(oracle_id, transaction_request)
+        if self.dry_run {
+            return dry_run_transaction(client, oracle_id, transaction_request).await;
+        }
+
+        submit_transaction_with_retry(client, oracle_id, transaction_request, &retry)
             .await
             .map_err(|e| anyhow::anyhow!("Error while submitting transaction: {e:?}"))?;
 
-        client
-            .sync_state()
+        sync_state_with_retry(client, &retry)
             .await
             .map_err(|e| anyhow::anyhow!("Error while syncing state after register: {e:?}"))?;
 
-        println!("✅ Register successful!");
-
-        Ok(())
+        Ok(Done::new(format!(
+            "Publisher {} registered",
+            publisher_id.to_hex()
+        )))
     }
 }
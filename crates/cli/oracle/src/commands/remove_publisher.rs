@@ -2,24 +2,32 @@ use std::path::Path;
 
 use miden_client::account::AccountId;
 use miden_client::transaction::TransactionRequestBuilder;
-use miden_client::{keystore::FilesystemKeyStore, Client};
+use miden_client::Client;
 use miden_standards::code_builder::CodeBuilder;
 use pm_accounts::oracle::get_oracle_component_library;
-use pm_utils_cli::{get_oracle_id, PRAGMA_ACCOUNTS_STORAGE_FILE};
+use pm_utils_cli::{
+    dry_run_transaction, get_oracle_id, retry_policy, submit_transaction_with_retry,
+    sync_state_with_retry, Done, PragmaKeyStore, PRAGMA_ACCOUNTS_STORAGE_FILE,
+};
 
 #[derive(clap::Parser, Debug, Clone)]
 #[clap(about = "Soft-deletes a publisher id from the Oracle registry")]
 pub struct RemovePublisherCmd {
     /// The id of the publisher to remove
     pub publisher_id: String,
+    /// Execute the transaction locally and print the storage changes it would
+    /// make, without proving nor submitting it
+    #[clap(long)]
+    pub dry_run: bool,
 }
 
 impl RemovePublisherCmd {
     pub async fn call(
         &self,
-        client: &mut Client<FilesystemKeyStore>,
+        client: &mut Client<PragmaKeyStore>,
         network: &str,
-    ) -> anyhow::Result<()> {
+    ) -> anyhow::Result<Done> {
+        let retry = retry_policy(network);
         let oracle_id = get_oracle_id(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network)?;
 
         client
@@ -55,18 +63,21 @@ impl RemovePublisherCmd {
             .build()
             .map_err(|e| anyhow::anyhow!("Error while building transaction request: {e:?}"))?;
 
-        client
-            .submit_new_transaction(oracle_id, transaction_request)
+        if self.dry_run {
+            return dry_run_transaction(client, oracle_id, transaction_request).await;
+        }
+
+        submit_transaction_with_retry(client, oracle_id, transaction_request, &retry)
             .await
             .map_err(|e| anyhow::anyhow!("Error while submitting transaction: {e:?}"))?;
 
-        client
-            .sync_state()
+        sync_state_with_retry(client, &retry)
             .await
             .map_err(|e| anyhow::anyhow!("Error while syncing state after remove: {e:?}"))?;
 
-        println!("✅ Publisher {} removed!", self.publisher_id);
-
-        Ok(())
+        Ok(Done::new(format!(
+            "Publisher {} removed",
+            self.publisher_id
+        )))
     }
 }
@@ -0,0 +1,105 @@
+use std::path::Path;
+
+use miden_client::transaction::TransactionRequestBuilder;
+use miden_client::Client;
+use miden_standards::code_builder::CodeBuilder;
+use pm_accounts::oracle::get_oracle_component_library;
+use pm_accounts::utils::word_to_masm;
+use pm_types::{AggregationMode, FeedConfig, FeedId, FeedKind};
+use pm_utils_cli::{
+    get_oracle_id, retry_policy, submit_transaction_with_retry, sync_state_with_retry, Done,
+    PragmaKeyStore, PRAGMA_ACCOUNTS_STORAGE_FILE,
+};
+
+#[derive(clap::Parser, Debug, Clone)]
+#[clap(about = "Configures how the Oracle aggregates the values published for a generic feed")]
+pub struct SetFeedConfigCmd {
+    /// Name of the feed (e.g., BTC-PERP/funding)
+    pub feed: String,
+
+    /// Kind of the feed values: price, funding-rate, interest-rate-index or proof-of-reserve
+    #[clap(long, default_value = "price")]
+    pub kind: FeedKind,
+
+    /// Aggregation mode: median, mean, min or max. Defaults to min for
+    /// proofs of reserve and to median otherwise
+    #[clap(long)]
+    pub mode: Option<AggregationMode>,
+
+    /// Maximum age in seconds of the aggregated values, 0 for the Oracle default (1 hour)
+    #[clap(long, default_value_t = 0)]
+    pub max_age: u64,
+
+    /// Decimals of the feed values
+    #[clap(long, default_value_t = 8)]
+    pub decimals: u32,
+}
+
+impl SetFeedConfigCmd {
+    /// Stores the config of a feed in the Oracle
+    ///
+    /// Configuring a feed again replaces its config. Values published with
+    /// another kind than the configured one are ignored by the aggregation.
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The Oracle ID cannot be retrieved from configuration
+    /// - The feed name is invalid
+    /// - The transaction script compilation fails
+    /// - The transaction submission fails
+    pub async fn call(
+        &self,
+        client: &mut Client<PragmaKeyStore>,
+        network: &str,
+    ) -> anyhow::Result<Done> {
+        let retry = retry_policy(network);
+        let oracle_id = get_oracle_id(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network)?;
+        let feed_id = FeedId::from_name(&self.feed)?;
+        let config = FeedConfig {
+            kind: self.kind,
+            mode: self.mode.unwrap_or_else(|| self.kind.default_aggregation()),
+            max_age: self.max_age,
+            decimals: self.decimals,
+        };
+
+        let tx_script_code = format!(
+            "
+            use oracle_component::oracle_module
+            use miden::core::sys
+            begin
+                push.{config}
+                push.{feed_id}
+                call.oracle_module::set_feed_config
+                exec.sys::truncate_stack
+            end
+            ",
+            config = word_to_masm(config.to_push_word()?),
+            feed_id = word_to_masm(feed_id.to_word()),
+        );
+        let oracle_lib = get_oracle_component_library();
+        let config_script = CodeBuilder::default()
+            .with_dynamically_linked_library(&oracle_lib)
+            .map_err(|e| anyhow::anyhow!("Error while setting up the component library: {e:?}"))?
+            .compile_tx_script(tx_script_code)
+            .map_err(|e| anyhow::anyhow!("Error while compiling the script: {e:?}"))?;
+
+        let transaction_request = TransactionRequestBuilder::new()
+            .custom_script(config_script)
+            .build()
+            .map_err(|e| anyhow::anyhow!("Error while building transaction request: {e:?}"))?;
+
+        submit_transaction_with_retry(client, oracle_id, transaction_request, &retry)
+            .await
+            .map_err(|e| anyhow::anyhow!("Error while submitting transaction: {e:?}"))?;
+
+        sync_state_with_retry(client, &retry)
+            .await
+            .map_err(|e| anyhow::anyhow!("Error while syncing state after config: {e:?}"))?;
+
+        Ok(Done::new(format!(
+            "{feed_id} configured as a {} feed aggregated with its {}",
+            config.kind, config.mode
+        )))
+    }
+}
@@ -0,0 +1,47 @@
+use std::path::PathBuf;
+
+use pm_utils_cli::{open_keystore, Done, UnsignedTransaction};
+
+#[derive(clap::Parser, Debug, Clone)]
+#[clap(about = "Signs a transaction exported with --export-unsigned (runs offline)")]
+pub struct SignCmd {
+    /// The unsigned transaction
+    pub input: PathBuf,
+    /// Where to write the signed transaction
+    #[clap(long, short, default_value = "signed.bin")]
+    pub output: PathBuf,
+    /// Keystore directory holding the key of the Oracle. Defaults to the
+    /// keystore of the project
+    #[clap(long)]
+    pub keystore_path: Option<String>,
+}
+
+impl SignCmd {
+    /// Signs an exported transaction with the keys of its account
+    ///
+    /// Needs neither a network connection nor a client store, only the
+    /// keystore holding the key (any `PM_KEYSTORE` backend), so it can run on
+    /// an air-gapped host.
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The unsigned transaction cannot be read
+    /// - The keystore holds no key of the account
+    /// - The signed transaction cannot be written
+    pub async fn call(&self) -> anyhow::Result<Done> {
+        let unsigned = UnsignedTransaction::read(&self.input)?;
+        let keystore = open_keystore(self.keystore_path.clone())?;
+
+        eprintln!("🔏 Signing a transaction of {}", unsigned.account_id);
+        eprintln!("   Message: {}", unsigned.message().to_hex());
+
+        let signed = unsigned.sign(&keystore).await?;
+        signed.write(&self.output)?;
+
+        Ok(Done::new(format!(
+            "Signed transaction written to {}, submit it with `submit`",
+            self.output.display()
+        )))
+    }
+}
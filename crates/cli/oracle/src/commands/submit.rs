@@ -0,0 +1,43 @@
+use std::path::PathBuf;
+
+use miden_client::Client;
+use pm_utils_cli::{retry_policy, sync_state_with_retry, Done, PragmaKeyStore, SignedTransaction};
+
+#[derive(clap::Parser, Debug, Clone)]
+#[clap(about = "Submits a transaction signed offline with sign")]
+pub struct SubmitCmd {
+    /// The signed transaction
+    pub input: PathBuf,
+}
+
+impl SubmitCmd {
+    /// Submits a signed transaction to the network
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The signed transaction cannot be read
+    /// - The state of the account changed since the transaction was exported,
+    ///   which invalidates the signature
+    /// - The transaction submission fails
+    pub async fn call(
+        &self,
+        client: &mut Client<PragmaKeyStore>,
+        network: &str,
+    ) -> anyhow::Result<Done> {
+        let retry = retry_policy(network);
+        let signed = SignedTransaction::read(&self.input)?;
+
+        sync_state_with_retry(client, &retry).await?;
+        signed.submit(client, &retry).await?;
+
+        sync_state_with_retry(client, &retry)
+            .await
+            .map_err(|e| anyhow::anyhow!("Error while syncing state after submit: {e:?}"))?;
+
+        Ok(Done::new(format!(
+            "Transaction of {} submitted",
+            signed.account_id
+        )))
+    }
+}
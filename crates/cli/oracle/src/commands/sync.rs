@@ -1,17 +1,21 @@
-use miden_client::{keystore::FilesystemKeyStore, Client};
+use miden_client::Client;
+use pm_utils_cli::{retry_policy, sync_state_with_retry, PragmaKeyStore, SyncOutput};
 
 #[derive(clap::Parser, Debug, Clone)]
 #[clap(about = "Syncs the local state with the blockchain")]
 pub struct SyncCmd {}
 
 impl SyncCmd {
-    pub async fn call(&self, client: &mut Client<FilesystemKeyStore>) -> anyhow::Result<()> {
-        let _ = client
-            .sync_state()
+    pub async fn call(
+        &self,
+        client: &mut Client<PragmaKeyStore>,
+        network: &str,
+    ) -> anyhow::Result<SyncOutput> {
+        let retry = retry_policy(network);
+        let summary = sync_state_with_retry(client, &retry)
             .await
             .map_err(|e| anyhow::anyhow!("Could not sync state: {}", e))?;
 
-        println!("🔁 Sync successful!\n");
-        Ok(())
+        Ok(SyncOutput::from(&summary))
     }
 }
@@ -0,0 +1,94 @@
+use miden_client::Client;
+use pm_consumer::{OracleReader, MAX_VOLATILITY_WINDOW, VOLATILITY_DECIMALS};
+use pm_utils_cli::{
+    get_asset_registry, get_oracle_id, plain_record, PragmaKeyStore, PRAGMA_ACCOUNTS_STORAGE_FILE,
+};
+use serde::Serialize;
+use std::path::Path;
+
+#[derive(clap::Parser, Debug, Clone)]
+#[clap(about = "Compute the realized volatility for a given faucet_id")]
+pub struct VolatilityCmd {
+    /// Pair symbol (e.g., BTC/USD) or asset key (e.g., 1:0)
+    pub faucet_id: String,
+
+    /// Number of returns between consecutive publisher entries to compute the
+    /// volatility over (1 to 15)
+    #[clap(short, long, default_value_t = MAX_VOLATILITY_WINDOW)]
+    pub window: u32,
+}
+
+/// Realized volatility of an asset.
+///
+/// JSON schema: `{"faucet_id": string, "pair"?: string, "is_tracked": bool,
+/// "volatility": u64, "window": u32, "decimals": u32}`.
+#[derive(Debug, Clone, Serialize)]
+pub struct VolatilityResult {
+    pub faucet_id: String,
+    /// Pair symbol of the asset, if registered
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pair: Option<String>,
+    pub is_tracked: bool,
+    /// Volatility per return between two entries, 0 when not tracked
+    pub volatility: u64,
+    /// Number of returns the volatility is computed over
+    pub window: u32,
+    pub decimals: u32,
+}
+
+impl VolatilityCmd {
+    /// Computes the realized volatility of an asset
+    ///
+    /// Each publisher derives a volatility from the relative changes between
+    /// its last entries, and the oracle returns the median of those. The
+    /// volatility is per return between two entries, it is not annualized.
+    pub async fn call(
+        &self,
+        client: &mut Client<PragmaKeyStore>,
+        network: &str,
+    ) -> anyhow::Result<VolatilityResult> {
+        let oracle_id = get_oracle_id(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network)?;
+        let registry = get_asset_registry(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE))?;
+        let asset_key = registry.resolve(&self.faucet_id)?;
+
+        let volatility = OracleReader::new(client, oracle_id)
+            .volatility(asset_key, self.window)
+            .await?;
+
+        Ok(VolatilityResult {
+            faucet_id: volatility.faucet_id,
+            pair: registry
+                .get_by_key(asset_key)
+                .map(|asset| asset.pair.to_string()),
+            is_tracked: volatility.is_tracked,
+            volatility: volatility.volatility,
+            window: volatility.window,
+            decimals: VOLATILITY_DECIMALS,
+        })
+    }
+}
+
+impl VolatilityResult {
+    pub fn table(&self) -> String {
+        let label = self.pair.as_deref().unwrap_or(&self.faucet_id);
+        if !self.is_tracked {
+            return format!("{label} volatility not tracked (no fresh publisher history)");
+        }
+        let percent = self.volatility as f64 / 10f64.powi(self.decimals as i32) * 100.0;
+        format!(
+            "{label} volatility over {} returns: {} ({} decimals, {percent:.4}%)",
+            self.window, self.volatility, self.decimals
+        )
+    }
+
+    pub fn plain(&self) -> String {
+        plain_record([
+            self.faucet_id.clone(),
+            self.pair.clone().unwrap_or_else(|| "-".to_string()),
+            self.is_tracked.to_string(),
+            self.volatility.to_string(),
+            self.window.to_string(),
+            self.decimals.to_string(),
+        ])
+    }
+}
@@ -1,15 +1,25 @@
 pub mod commands;
 
 use clap::Parser;
-use commands::SubCommand;
+use commands::{CommandOutput, SubCommand};
+use pm_utils_cli::OutputFormat;
 
 #[derive(Parser, Debug)]
 #[command(name = "pm-oracle")]
 #[command(about = "Pragma Miden oracle CLI")]
 struct Cli {
-    /// Network to use (local, devnet or testnet)
+    /// Network to use (local, devnet, testnet or a profile added with `pm-oracle network add`)
     #[clap(short = 'n', long = "network", default_value = "devnet", global = true)]
     pub network: String,
+    /// Format of the result printed on stdout
+    #[clap(
+        short = 'o',
+        long = "output",
+        value_enum,
+        default_value_t = OutputFormat::Table,
+        global = true
+    )]
+    pub output: OutputFormat,
     #[command(subcommand)]
     command: SubCommand,
 }
@@ -17,18 +27,20 @@ struct Cli {
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    let format = match &cli.command {
+        SubCommand::MedianBatch(cmd) if cmd.json => OutputFormat::Json,
+        _ => cli.output,
+    };
+
     let output = cli.command.call(&cli.network).await?;
-    match output {
-        commands::CommandOutput::Entry(entry) => {
-            println!(
-                "Price: {}, Decimals: {}, Timestamp: {}",
-                entry.price, entry.decimals, entry.timestamp
-            );
-        }
-        commands::CommandOutput::Felt(f) => {
-            println!("{}", f);
-        }
-        _ => {}
+    let rendered = output.render(format)?;
+    if !rendered.is_empty() {
+        println!("{rendered}");
+    }
+
+    // The report is printed first, the exit code is what alerting relies on.
+    if let CommandOutput::Health(report) = &output {
+        report.ensure_healthy()?;
     }
     Ok(())
 }
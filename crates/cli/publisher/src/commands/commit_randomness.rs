@@ -0,0 +1,147 @@
+use std::path::Path;
+
+use miden_client::{transaction::TransactionRequestBuilder, Client, Felt, Word};
+use miden_standards::code_builder::CodeBuilder;
+
+use miden_client::account::AccountId;
+use pm_accounts::{
+    publisher::{get_publisher_component_library, randomness_commitment},
+    utils::word_to_masm,
+};
+use pm_utils_cli::{
+    get_publisher_id, get_randomness_secret, retry_policy, set_randomness_secret,
+    submit_transaction_with_retry, Done, PragmaKeyStore, RetryPolicy, PRAGMA_ACCOUNTS_STORAGE_FILE,
+};
+
+#[derive(clap::Parser, Debug, Clone)]
+#[clap(about = "Commit to a fresh randomness for a round (Callable by the publisher itself)")]
+pub struct CommitRandomnessCmd {
+    /// Round to commit to
+    pub round: u64,
+    /// Optional publisher ID. If not provided, uses the first publisher from config
+    #[clap(long)]
+    pub publisher_id: Option<String>,
+}
+
+impl CommitRandomnessCmd {
+    /// Commits to a fresh randomness for a round
+    ///
+    /// The randomness is generated locally and saved in the configuration
+    /// file, so that `reveal-randomness` can reveal it once every publisher
+    /// has committed. Only its commitment is published.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - A mutable reference to the Miden client, must be initialized first
+    /// * `network` - The network identifier (e.g., "devnet", "testnet")
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The publisher ID cannot be retrieved from configuration
+    /// - A randomness was already saved for the round
+    /// - The randomness cannot be saved
+    /// - The transaction script compilation fails
+    /// - The transaction submission fails (e.g. the round is already committed)
+    pub async fn call(
+        &self,
+        client: &mut Client<PragmaKeyStore>,
+        network: &str,
+    ) -> anyhow::Result<Done> {
+        let retry = retry_policy(network);
+        let storage_path = Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE);
+        let publisher_id = if let Some(id) = &self.publisher_id {
+            AccountId::from_hex(id)?
+        } else {
+            get_publisher_id(storage_path, network)?
+        };
+
+        // Never overwrite a saved randomness: it could not be revealed anymore.
+        if get_randomness_secret(storage_path, network, &publisher_id, self.round)?.is_some() {
+            anyhow::bail!(
+                "A randomness was already committed for round {}",
+                self.round
+            );
+        }
+
+        // Felts of 63 bits are always canonical.
+        let secret: Word = rand::random::<[u64; 4]>()
+            .map(|random| Felt::new(random >> 1).expect("63-bit values are valid felts"))
+            .into();
+        if secret == Word::default() {
+            anyhow::bail!("Generated an empty randomness, please retry");
+        }
+        set_randomness_secret(storage_path, network, &publisher_id, self.round, secret)?;
+
+        publish_randomness(
+            client,
+            publisher_id,
+            self.round,
+            Word::default(),
+            randomness_commitment(secret),
+            &retry,
+        )
+        .await?;
+
+        Ok(Done::new(format!(
+            "Committed to a randomness for round {}",
+            self.round
+        )))
+    }
+}
+
+/// Submits a `publish_randomness` transaction for `round`.
+///
+/// `value` and `commitment` are words as stored by the publisher: a zero
+/// `value` commits to `commitment`, any other value reveals it.
+pub(crate) async fn publish_randomness(
+    client: &mut Client<PragmaKeyStore>,
+    publisher_id: AccountId,
+    round: u64,
+    value: Word,
+    commitment: Word,
+    retry: &RetryPolicy,
+) -> anyhow::Result<()> {
+    let tx_script_code = format!(
+        "
+            use publisher_component::publisher_module
+            use miden::core::sys
+
+            begin
+                push.{commitment}
+                push.{value}
+                push.{round}
+
+                call.publisher_module::publish_randomness
+
+                exec.sys::truncate_stack
+            end
+            ",
+        commitment = word_to_masm(to_push_word(commitment)),
+        value = word_to_masm(to_push_word(value)),
+    );
+    let publisher_lib = get_publisher_component_library();
+    let randomness_script = CodeBuilder::default()
+        .with_statically_linked_library(&publisher_lib)
+        .map_err(|e| anyhow::anyhow!("Error while setting up the component library: {e:?}"))?
+        .compile_tx_script(tx_script_code)
+        .map_err(|e| anyhow::anyhow!("Error while compiling the script: {e:?}"))?;
+
+    let transaction_request = TransactionRequestBuilder::new()
+        .custom_script(randomness_script)
+        .build()
+        .map_err(|e| anyhow::anyhow!("Error while building transaction request: {e:?}"))?;
+
+    submit_transaction_with_retry(client, publisher_id, transaction_request, retry)
+        .await
+        .map_err(|e| anyhow::anyhow!("Error while submitting transaction: {e:?}"))?;
+
+    Ok(())
+}
+
+/// Word to push so that `word` ends up on the stack as stored.
+fn to_push_word(word: Word) -> Word {
+    let mut elements: [Felt; 4] = word.into();
+    elements.reverse();
+    elements.into()
+}
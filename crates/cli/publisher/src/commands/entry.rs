@@ -1,8 +1,15 @@
 use chrono::{DateTime, Utc};
-use miden_client::{keystore::FilesystemKeyStore, Client};
-use pm_types::Entry;
-use pm_utils_cli::{get_publisher_id, PRAGMA_ACCOUNTS_STORAGE_FILE};
+use miden_client::{
+    account::{Account, AccountId},
+    Client,
+};
+use pm_types::{AssetKey, Entry, FeedId, FeedValue};
+use pm_utils_cli::{
+    get_asset_registry, get_publisher_id, plain_record, retry_policy, sync_state_with_retry,
+    PragmaKeyStore, PRAGMA_ACCOUNTS_STORAGE_FILE,
+};
 use prettytable::{Cell, Row, Table};
+use serde::Serialize;
 use std::path::Path;
 
 #[derive(clap::Parser, Debug, Clone)]
@@ -10,17 +17,54 @@ use std::path::Path;
     about = "Retrieve an entry for a given faucet_id (published by this publisher). This version read directly within the rust storage of the publisher"
 )]
 pub struct EntryCmd {
-    // Input faucet_id (format example: "1:0" for BTC/USD)
+    // Input pair symbol or faucet_id (format example: "BTC/USD" or "1:0"),
+    // or the feed name with --feed
     pub faucet_id: String,
+    /// Retrieve the last value published for a generic feed instead of a price entry
+    #[clap(long)]
+    pub feed: bool,
+}
+
+/// Last value the publisher stored, read from its local storage.
+///
+/// JSON schema, for a price entry: `{"publisher_id": string, "pair": string,
+/// "faucet_id": string, "price": u64, "decimals": u32, "timestamp": u64}`,
+/// all zero if the asset was never published. For a feed (`--feed`):
+/// `{"publisher_id": string, "feed_id": string, "kind": string, "value": i64,
+/// "decimals": u32, "timestamp": u64}`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum StoredValue {
+    Entry(StoredEntry),
+    Feed(StoredFeed),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StoredEntry {
+    pub publisher_id: String,
+    pub pair: String,
+    #[serde(flatten)]
+    pub entry: Entry,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StoredFeed {
+    pub publisher_id: String,
+    pub feed_id: String,
+    pub kind: String,
+    pub value: i64,
+    pub decimals: u32,
+    pub timestamp: u64,
 }
 
 impl EntryCmd {
     pub async fn call(
         &self,
-        client: &mut Client<FilesystemKeyStore>,
+        client: &mut Client<PragmaKeyStore>,
         network: &str,
-    ) -> anyhow::Result<()> {
-        client.sync_state().await.unwrap();
+    ) -> anyhow::Result<StoredValue> {
+        let retry = retry_policy(network);
+        sync_state_with_retry(client, &retry).await.unwrap();
         let publisher_id = get_publisher_id(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network)?;
 
         let publisher = client
@@ -29,36 +73,69 @@ impl EntryCmd {
             .unwrap()
             .expect("Publisher account not found");
 
-        let parts: Vec<&str> = self.faucet_id.split(':').collect();
-        if parts.len() != 2 {
-            return Err(anyhow::anyhow!(
-                "Invalid faucet_id format. Expected PREFIX:SUFFIX (e.g., 1:0)"
-            ));
+        if self.feed {
+            return stored_feed(
+                &publisher,
+                publisher_id,
+                FeedId::from_name(&self.faucet_id)?,
+            )
+            .map(StoredValue::Feed);
         }
-        let prefix = parts[0]
-            .parse::<u64>()
-            .map_err(|_| anyhow::anyhow!("Invalid faucet_id prefix"))?;
-        let suffix = parts[1]
-            .parse::<u64>()
-            .map_err(|_| anyhow::anyhow!("Invalid faucet_id suffix"))?;
-
-        let faucet_id_word: miden_client::Word = [
-            miden_client::Felt::new(0)?,
-            miden_client::Felt::new(0)?,
-            miden_client::Felt::new(suffix)?,
-            miden_client::Felt::new(prefix)?,
-        ]
-        .into();
-
-        let publisher_entries_slot =
-            miden_protocol::account::StorageSlotName::new("pragma::publisher::entries")
-                .map_err(|e| anyhow::anyhow!("Invalid storage slot name: {e:?}"))?;
-        let entry_word = publisher
-            .storage()
-            .get_map_item(&publisher_entries_slot, faucet_id_word)
-            .unwrap();
-        let mut entry = Entry::from(entry_word);
-        entry.faucet_id = self.faucet_id.clone();
+
+        let registry = get_asset_registry(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE))?;
+        let asset_key = registry.resolve(&self.faucet_id)?;
+
+        let entry = stored_entry(&publisher, asset_key)?.unwrap_or(Entry {
+            faucet_id: asset_key.to_string(),
+            price: 0,
+            decimals: 0,
+            timestamp: 0,
+        });
+
+        Ok(StoredValue::Entry(StoredEntry {
+            publisher_id: publisher_id.to_hex(),
+            pair: registry.label(asset_key),
+            entry,
+        }))
+    }
+}
+
+impl StoredValue {
+    pub fn table(&self) -> String {
+        match self {
+            StoredValue::Entry(stored) => stored.table(),
+            StoredValue::Feed(stored) => stored.table(),
+        }
+    }
+
+    /// A single line. Price entries: publisher id, pair, faucet id, price,
+    /// decimals, timestamp. Feeds: publisher id, feed id, kind, value,
+    /// decimals, timestamp.
+    pub fn plain(&self) -> String {
+        match self {
+            StoredValue::Entry(stored) => plain_record([
+                stored.publisher_id.clone(),
+                stored.pair.clone(),
+                stored.entry.faucet_id.clone(),
+                stored.entry.price.to_string(),
+                stored.entry.decimals.to_string(),
+                stored.entry.timestamp.to_string(),
+            ]),
+            StoredValue::Feed(stored) => plain_record([
+                stored.publisher_id.clone(),
+                stored.feed_id.clone(),
+                stored.kind.clone(),
+                stored.value.to_string(),
+                stored.decimals.to_string(),
+                stored.timestamp.to_string(),
+            ]),
+        }
+    }
+}
+
+impl StoredEntry {
+    fn table(&self) -> String {
+        let entry = &self.entry;
 
         // Create the main info table
         let mut table = Table::new();
@@ -67,12 +144,12 @@ impl EntryCmd {
         // Add publisher info
         table.add_row(Row::new(vec![
             Cell::new("Publisher ID").style_spec("Fc"),
-            Cell::new(&format!("{}", publisher_id)).style_spec("Fy"),
+            Cell::new(&self.publisher_id).style_spec("Fy"),
         ]));
 
         table.add_row(Row::new(vec![
             Cell::new("Faucet ID").style_spec("Fc"),
-            Cell::new(&format!("💱 {}", self.faucet_id)).style_spec("Fy"),
+            Cell::new(&format!("💱 {}", self.pair)).style_spec("Fy"),
         ]));
 
         let price_float = entry.price as f64 / 10f64.powi(entry.decimals as i32);
@@ -98,8 +175,103 @@ impl EntryCmd {
             Cell::new(&format!("🕒 {}", formatted_time)).style_spec("Fy"),
         ]));
 
-        table.printstd();
+        table.to_string()
+    }
+}
+
+/// Reads the last entry `publisher` stored for `asset_key` in its local
+/// storage, or `None` if it never published the asset.
+///
+/// # Errors
+///
+/// Fails if the entries storage map cannot be read.
+pub(crate) fn stored_entry(
+    publisher: &Account,
+    asset_key: AssetKey,
+) -> anyhow::Result<Option<Entry>> {
+    let publisher_entries_slot =
+        miden_protocol::account::StorageSlotName::new("pragma::publisher::entries")
+            .map_err(|e| anyhow::anyhow!("Invalid storage slot name: {e:?}"))?;
+    // Entries are stored in reversed felt order relative to the pushed words:
+    // keyed by [prefix, suffix, 0, 0] and holding [timestamp, decimals, price, 0].
+    let stored_entry = publisher
+        .storage()
+        .get_map_item(&publisher_entries_slot, asset_key.to_storage_key())
+        .map_err(|e| anyhow::anyhow!("Failed to retrieve entry {asset_key}: {e:?}"))?;
+    if stored_entry == miden_client::Word::default() {
+        return Ok(None);
+    }
+    let mut entry_elements: [miden_client::Felt; 4] = stored_entry.into();
+    entry_elements.reverse();
+    let mut entry = Entry::from(miden_client::Word::from(entry_elements));
+    entry.faucet_id = asset_key.to_string();
+    Ok(Some(entry))
+}
+
+fn stored_feed(
+    publisher: &Account,
+    publisher_id: AccountId,
+    feed_id: FeedId,
+) -> anyhow::Result<StoredFeed> {
+    let publisher_feeds_slot =
+        miden_protocol::account::StorageSlotName::new("pragma::publisher::feeds")
+            .map_err(|e| anyhow::anyhow!("Invalid storage slot name: {e:?}"))?;
+    let stored_value = publisher
+        .storage()
+        .get_map_item(&publisher_feeds_slot, feed_id.to_storage_key())
+        .map_err(|e| anyhow::anyhow!("Failed to retrieve feed {feed_id}: {e:?}"))?;
+    if stored_value == miden_client::Word::default() {
+        anyhow::bail!("No value published for feed {feed_id}");
+    }
+    let feed_value = FeedValue::from_word(stored_value)?;
+
+    Ok(StoredFeed {
+        publisher_id: publisher_id.to_hex(),
+        feed_id: feed_id.to_string(),
+        kind: feed_value.kind.to_string(),
+        value: feed_value.value,
+        decimals: feed_value.decimals,
+        timestamp: feed_value.timestamp,
+    })
+}
+
+impl StoredFeed {
+    fn table(&self) -> String {
+        let mut table = Table::new();
+        table.set_format(*prettytable::format::consts::FORMAT_BOX_CHARS);
+
+        table.add_row(Row::new(vec![
+            Cell::new("Publisher ID").style_spec("Fc"),
+            Cell::new(&self.publisher_id).style_spec("Fy"),
+        ]));
+
+        table.add_row(Row::new(vec![
+            Cell::new("Feed").style_spec("Fc"),
+            Cell::new(&format!("📡 {} ({})", self.feed_id, self.kind)).style_spec("Fy"),
+        ]));
+
+        let value_float = self.value as f64 / 10f64.powi(self.decimals as i32);
+        table.add_row(Row::new(vec![
+            Cell::new("Value").style_spec("Fc"),
+            Cell::new(&format!(
+                "{:.width$}",
+                value_float,
+                width = self.decimals as usize
+            ))
+            .style_spec("Fy"),
+        ]));
+
+        table.add_row(Row::new(vec![
+            Cell::new("Decimals").style_spec("Fc"),
+            Cell::new(&format!("🔢 {}", self.decimals)).style_spec("Fy"),
+        ]));
+
+        let dt = DateTime::<Utc>::from_timestamp(self.timestamp as i64, 0).unwrap();
+        table.add_row(Row::new(vec![
+            Cell::new("Timestamp").style_spec("Fc"),
+            Cell::new(&format!("🕒 {}", dt.format("%Y-%m-%d %H:%M:%S UTC"))).style_spec("Fy"),
+        ]));
 
-        Ok(())
+        table.to_string()
     }
 }
@@ -1,10 +1,12 @@
-use miden_client::{keystore::FilesystemKeyStore, Client, Felt};
+use miden_client::Client;
 use miden_protocol::vm::AdviceInputs;
 use miden_standards::code_builder::CodeBuilder;
 use pm_accounts::publisher::get_publisher_component_library;
 use pm_accounts::utils::word_to_masm;
 use pm_types::Entry;
-use pm_utils_cli::{get_publisher_id, PRAGMA_ACCOUNTS_STORAGE_FILE};
+use pm_utils_cli::{
+    get_asset_registry, get_publisher_id, PragmaKeyStore, PRAGMA_ACCOUNTS_STORAGE_FILE,
+};
 use std::collections::BTreeMap;
 use std::path::Path;
 
@@ -13,7 +15,7 @@ use std::path::Path;
     about = "Retrieve an entry for a given faucet_id (published by this publisher). This version executes an onchain program to retrieve the information"
 )]
 pub struct GetEntryCmd {
-    // Input faucet_id (format example: "1:0" for BTC/USD)
+    // Input pair symbol or faucet_id (format example: "BTC/USD" or "1:0")
     pub faucet_id: String,
 }
 
@@ -41,37 +43,19 @@ impl GetEntryCmd {
     ///
     /// This function can fail if:
     /// - The publisher ID cannot be retrieved from configuration
-    /// - The faucet_id string cannot be parsed
+    /// - The faucet_id is neither a registered pair nor a valid asset key
     /// - The transaction script compilation fails
     /// - The program execution fails
     /// - The returned stack doesn't contain the expected values
     pub async fn call(
         &self,
-        client: &mut Client<FilesystemKeyStore>,
+        client: &mut Client<PragmaKeyStore>,
         network: &str,
     ) -> anyhow::Result<Entry> {
         let publisher_id = get_publisher_id(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network)?;
 
-        let parts: Vec<&str> = self.faucet_id.split(':').collect();
-        if parts.len() != 2 {
-            return Err(anyhow::anyhow!(
-                "Invalid faucet_id format. Expected PREFIX:SUFFIX (e.g., 1:0)"
-            ));
-        }
-        let prefix = parts[0]
-            .parse::<u64>()
-            .map_err(|_| anyhow::anyhow!("Invalid faucet_id prefix"))?;
-        let suffix = parts[1]
-            .parse::<u64>()
-            .map_err(|_| anyhow::anyhow!("Invalid faucet_id suffix"))?;
-
-        let faucet_id_word: miden_client::Word = [
-            Felt::new(0)?,
-            Felt::new(0)?,
-            Felt::new(suffix)?,
-            Felt::new(prefix)?,
-        ]
-        .into();
+        let asset_key = get_asset_registry(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE))?
+            .resolve(&self.faucet_id)?;
 
         let tx_script_code = format!(
             "
@@ -85,7 +69,7 @@ impl GetEntryCmd {
                 exec.sys::truncate_stack
             end
             ",
-            faucet_id = word_to_masm(faucet_id_word),
+            faucet_id = word_to_masm(asset_key.to_word()),
         );
 
         let publisher_lib = get_publisher_component_library();
@@ -104,9 +88,8 @@ impl GetEntryCmd {
             )
             .await
             .map_err(|e| anyhow::anyhow!("Failed to execute get_entry program: {}", e))?;
-        println!("Here is the output stack: {:?}", output_stack);
         Ok(Entry {
-            faucet_id: self.faucet_id.clone(),
+            faucet_id: asset_key.to_string(),
             price: output_stack[2].as_canonical_u64(),
             decimals: output_stack[1].as_canonical_u64() as u32,
             timestamp: output_stack[0].as_canonical_u64(),
@@ -0,0 +1,82 @@
+use std::path::Path;
+
+use miden_client::{transaction::TransactionRequestBuilder, Client};
+use miden_standards::code_builder::CodeBuilder;
+
+use miden_client::account::AccountId;
+use pm_accounts::publisher::get_publisher_component_library;
+use pm_utils_cli::{
+    get_publisher_id, retry_policy, submit_transaction_with_retry, Done, PragmaKeyStore,
+    PRAGMA_ACCOUNTS_STORAGE_FILE,
+};
+
+#[derive(clap::Parser, Debug, Clone)]
+#[clap(
+    about = "Signal that the publisher is alive without publishing (Callable by the publisher itself)"
+)]
+pub struct HeartbeatCmd {
+    /// Optional publisher ID. If not provided, uses the first publisher from config
+    #[clap(long)]
+    pub publisher_id: Option<String>,
+}
+
+impl HeartbeatCmd {
+    /// Records the current block timestamp as the publisher's last activity
+    ///
+    /// `publish_entry` already refreshes the heartbeat, so this is only needed
+    /// when the publisher has nothing new to publish but wants to stay healthy.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - A mutable reference to the Miden client, must be initialized first
+    /// * `network` - The network identifier (e.g., "devnet", "testnet")
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The publisher ID cannot be retrieved from configuration
+    /// - The transaction script compilation fails
+    /// - The transaction submission fails
+    pub async fn call(
+        &self,
+        client: &mut Client<PragmaKeyStore>,
+        network: &str,
+    ) -> anyhow::Result<Done> {
+        let retry = retry_policy(network);
+        let publisher_id = if let Some(id) = &self.publisher_id {
+            AccountId::from_hex(id)?
+        } else {
+            get_publisher_id(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network)?
+        };
+
+        let tx_script_code = "
+            use publisher_component::publisher_module
+            use miden::core::sys
+
+            begin
+                call.publisher_module::heartbeat
+                exec.sys::truncate_stack
+            end
+            ";
+        let publisher_lib = get_publisher_component_library();
+        let heartbeat_script = CodeBuilder::default()
+            .with_statically_linked_library(&publisher_lib)
+            .map_err(|e| anyhow::anyhow!("Error while setting up the component library: {e:?}"))?
+            .compile_tx_script(tx_script_code)
+            .map_err(|e| anyhow::anyhow!("Error while compiling the script: {e:?}"))?;
+
+        let transaction_request = TransactionRequestBuilder::new()
+            .custom_script(heartbeat_script)
+            .build()
+            .map_err(|e| anyhow::anyhow!("Error while building transaction request: {e:?}"))?;
+
+        submit_transaction_with_retry(client, publisher_id, transaction_request, &retry)
+            .await
+            .map_err(|e| anyhow::anyhow!("Error while submitting transaction: {e:?}"))?;
+
+        Ok(Done::new(format!(
+            "Heartbeat of publisher {} sent",
+            publisher_id.to_hex()
+        )))
+    }
+}
@@ -1,9 +1,17 @@
+use std::fmt::Write;
 use std::path::Path;
 
 use colored::*;
-use miden_client::{keystore::FilesystemKeyStore, Client};
-use pm_accounts::publisher::PublisherAccountBuilder;
-use pm_utils_cli::{add_publisher_id, PRAGMA_ACCOUNTS_STORAGE_FILE};
+use miden_client::{
+    account::AccountId, crypto::FeltRng, transaction::TransactionRequestBuilder, Client,
+};
+use pm_accounts::{notes::create_registration_request_note, publisher::PublisherAccountBuilder};
+use pm_utils_cli::{
+    add_publisher_id, get_asset_registry, get_oracle_id, retry_policy,
+    submit_transaction_with_retry, sync_state_with_retry, PragmaKeyStore,
+    PRAGMA_ACCOUNTS_STORAGE_FILE,
+};
+use serde::Serialize;
 
 #[derive(clap::Parser, Debug, Clone)]
 #[clap(about = "Creates a new Publisher Account")]
@@ -12,6 +20,31 @@ pub struct InitCmd {
     // In this case, just store the oracle id in the storage.
     // If not provided and the oracle_id is empty in the storage, error!
     pub oracle_id: Option<String>,
+    /// Send a registration request note to the Oracle once the publisher is created
+    #[clap(long)]
+    pub request_registration: bool,
+}
+
+/// Result of `init`.
+///
+/// JSON schema: `{"publisher_id": string, "storage_file": string,
+/// "registration_request": {"note_id": string, "oracle_id": string} | null}`,
+/// the request being null without `--request-registration`.
+#[derive(Debug, Clone, Serialize)]
+pub struct InitOutput {
+    pub publisher_id: String,
+    /// Configuration file the publisher id was saved to.
+    pub storage_file: String,
+    pub registration_request: Option<RegistrationRequest>,
+    /// Assets of the registry, listed in the quick start guide.
+    #[serde(skip)]
+    pub faucet_mapping: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RegistrationRequest {
+    pub note_id: String,
+    pub oracle_id: String,
 }
 
 impl InitCmd {
@@ -21,33 +54,31 @@ impl InitCmd {
     /// 1. Syncs the client state with the network
     /// 2. Creates a new publisher account
     /// 3. Stores the publisher ID in the local configuration
+    /// 4. Optionally sends a registration request note to the Oracle
     ///
     /// # Arguments
     ///
     /// * `client` - A mutable reference to the Miden client
     /// * `network` - The network identifier (e.g., "devnet", "testnet")
     ///
-    /// # Returns
-    ///
-    /// * `anyhow::Result<()>` - Success or an error
-    ///
     /// # Errors
     ///
     /// This function can fail if:
     /// - The client fails to sync state with the network
     /// - The publisher account creation fails
     /// - The configuration file cannot be updated
+    /// - The registration request cannot be sent
     pub async fn call(
         &self,
-        client: &mut Client<FilesystemKeyStore>,
+        client: &mut Client<PragmaKeyStore>,
         network: &str,
-    ) -> anyhow::Result<()> {
+    ) -> anyhow::Result<InitOutput> {
+        let retry = retry_policy(network);
         // TODO: Refine this condition & logic
         // if JsonStorage::exists(PRAGMA_ACCOUNTS_STORAGE) && JsonStorage::new(PRAGMA_ACCOUNTS_STORAGE).get_key(PUBLISHER_ACCOUNT_ID).is_some() {
         //     bail!("A Publisher has already been created! Delete it if you wanna start over.");
         // }
-        client
-            .sync_state()
+        sync_state_with_retry(client, &retry)
             .await
             .map_err(|e| anyhow::anyhow!("Could not sync state during init: {}", e))?;
 
@@ -64,10 +95,64 @@ impl InitCmd {
             &created_publisher_id,
         )?;
 
+        let registration_request = if self.request_registration {
+            let oracle_id = match &self.oracle_id {
+                Some(id) => AccountId::from_hex(id)?,
+                None => get_oracle_id(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network)?,
+            };
+            let serial_num = client.rng().draw_word();
+            let request_note =
+                create_registration_request_note(created_publisher_id, oracle_id, serial_num)?;
+            let note_id = request_note.id();
+
+            let transaction_request = TransactionRequestBuilder::new()
+                .own_output_notes(vec![request_note])
+                .build()
+                .map_err(|e| anyhow::anyhow!("Error while building transaction request: {e:?}"))?;
+            submit_transaction_with_retry(
+                client,
+                created_publisher_id,
+                transaction_request,
+                &retry,
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Error while submitting transaction: {e:?}"))?;
+
+            Some(RegistrationRequest {
+                note_id: note_id.to_string(),
+                oracle_id: oracle_id.to_hex(),
+            })
+        } else {
+            None
+        };
+
+        let faucet_mapping = get_asset_registry(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE))?
+            .assets()
+            .iter()
+            .map(|asset| {
+                format!(
+                    "{} = {} ({}, {} decimals)",
+                    asset.asset_key, asset.pair, asset.name, asset.decimals
+                )
+            })
+            .collect();
+
+        Ok(InitOutput {
+            publisher_id: created_publisher_id.to_hex(),
+            storage_file: PRAGMA_ACCOUNTS_STORAGE_FILE.to_string(),
+            registration_request,
+            faucet_mapping,
+        })
+    }
+}
+
+impl InitOutput {
+    pub fn table(&self) -> String {
         // Clear screen for better presentation
-        print!("\x1B[2J\x1B[1;1H");
+        let mut out = String::from("\x1B[2J\x1B[1;1H");
 
-        println!(
+        writeln!(
+            out,
             "{}",
             r#"
         ==============================================================
@@ -80,9 +165,11 @@ impl InitCmd {
 
         "#
             .bright_cyan()
-        );
+        )
+        .unwrap();
 
-        println!(
+        writeln!(
+            out,
             "{}",
             r#"
             🎉 Welcome to the Pragma Oracle Network! 🎉
@@ -91,10 +178,12 @@ impl InitCmd {
             Your role is to provide accurate and timely price data to the network.
             "#
             .bright_yellow()
-        );
+        )
+        .unwrap();
 
-        println!("\n{}", "📝 Your Publisher Details".bright_green());
-        println!(
+        writeln!(out, "\n{}", "📝 Your Publisher Details".bright_green()).unwrap();
+        writeln!(
+            out,
             "{}",
             format!(
                 "
@@ -102,19 +191,42 @@ impl InitCmd {
                 │ ID: {}
                 │ Storage: {}
                 ╰────────────────────────────────────────────────────────────╯",
-                created_publisher_id.to_string().bright_white(),
-                PRAGMA_ACCOUNTS_STORAGE_FILE.bright_white()
+                self.publisher_id.bright_white(),
+                self.storage_file.bright_white()
             )
             .bright_blue()
-        );
+        )
+        .unwrap();
+
+        if let Some(request) = &self.registration_request {
+            writeln!(out, "\n{}", "📨 Registration Request".bright_green()).unwrap();
+            writeln!(
+            out,
+                "{}",
+                format!(
+                    "
+                ╭────────────────────────────────────────────────────────────╮
+                │ Note ID: {}
+                │ Oracle ID: {}
+                ╰────────────────────────────────────────────────────────────╯
+                Waiting for the Oracle owner to accept it with `pm-oracle pending-registrations --accept`.",
+                    request.note_id.bright_white(),
+                    request.oracle_id.bright_white()
+                )
+                .bright_blue()
+            ).unwrap();
+        }
 
-        println!("\n{}", "🚀 Quick Start Guide".bright_green());
-        println!(
+        writeln!(out, "\n{}", "🚀 Quick Start Guide".bright_green()).unwrap();
+        writeln!(
+            out,
             "{}",
             r#"To start publishing price data, use the following command format:"#.bright_yellow()
-        );
+        )
+        .unwrap();
 
-        println!(
+        writeln!(
+            out,
             "{}",
             r#"
                 📊 Command Structure:
@@ -123,10 +235,12 @@ impl InitCmd {
                 ╰────────────────────────────────────────────────────────────╯
                 "#
             .bright_blue()
-        );
+        )
+        .unwrap();
 
-        println!("{}", "📌 Example:".bright_yellow());
-        println!(
+        writeln!(out, "{}", "📌 Example:".bright_yellow()).unwrap();
+        writeln!(
+            out,
             "{}",
             r#"
                 ╭────────────────────────────────────────────────────────────╮
@@ -134,30 +248,50 @@ impl InitCmd {
                 ╰────────────────────────────────────────────────────────────╯
                 "#
             .bright_blue()
-        );
+        )
+        .unwrap();
 
-        println!(
+        writeln!(
+            out,
             "{}",
             r#"
                 💡 Parameters Explained:
-                • FAUCET_ID: Asset identifier (e.g., 1:0 for BTC/USD)
+                • FAUCET_ID: Asset pair or identifier (e.g., BTC/USD or 1:0)
                 • PRICE    : Current price (e.g., 95000000000)
                 • DECIMALS : Number of decimal places (e.g., 6)
                 • TIMESTAMP: Current Unix timestamp
-                
-                📋 Faucet ID Mapping:
-                • 1:0 = BTC/USD  • 2:0 = ETH/USD  • 3:0 = SOL/USD
-                • 4:0 = BNB/USD  • 5:0 = XRP/USD  • 6:0 = HYPE/USD
                 "#
             .bright_yellow()
-        );
+        )
+        .unwrap();
+
+        writeln!(
+            out,
+            "{}",
+            "                📋 Faucet ID Mapping:".bright_yellow()
+        )
+        .unwrap();
+        for asset in &self.faucet_mapping {
+            writeln!(
+                out,
+                "{}",
+                format!("                • {asset}").bright_yellow()
+            )
+            .unwrap();
+        }
 
-        println!(
+        write!(
+            out,
             "\n{}",
             "✨ You're all set! Start publishing price data to contribute to the network! ✨"
                 .bright_green()
-        );
+        )
+        .unwrap();
+
+        out
+    }
 
-        Ok(())
+    pub fn plain(&self) -> String {
+        self.publisher_id.clone()
     }
 }
@@ -0,0 +1,76 @@
+use std::path::Path;
+
+use miden_client::{
+    account::AccountId, crypto::FeltRng, transaction::TransactionRequestBuilder, Client,
+};
+use pm_accounts::notes::create_leave_note;
+use pm_utils_cli::{
+    get_oracle_id, get_publisher_id, retry_policy, submit_transaction_with_retry, Done,
+    PragmaKeyStore, PRAGMA_ACCOUNTS_STORAGE_FILE,
+};
+
+#[derive(clap::Parser, Debug, Clone)]
+#[clap(
+    about = "Leave the Oracle by sending it a signed leave note (Callable by the publisher itself)"
+)]
+pub struct LeaveCmd {
+    /// Optional oracle ID. If not provided, uses the oracle from config
+    pub oracle_id: Option<String>,
+    /// Optional publisher ID. If not provided, uses the first publisher from config
+    #[clap(long)]
+    pub publisher_id: Option<String>,
+}
+
+impl LeaveCmd {
+    /// Sends a leave note from the publisher to the Oracle
+    ///
+    /// The note is created by a transaction executed against the publisher
+    /// account, so it carries the publisher as its sender. Once the Oracle
+    /// consumes it, the publisher is soft-deleted from the registry.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - A mutable reference to the Miden client, must be initialized first
+    /// * `network` - The network identifier (e.g., "devnet", "testnet")
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The publisher or Oracle ID cannot be retrieved from configuration
+    /// - The leave note script compilation fails
+    /// - The transaction submission fails
+    pub async fn call(
+        &self,
+        client: &mut Client<PragmaKeyStore>,
+        network: &str,
+    ) -> anyhow::Result<Done> {
+        let retry = retry_policy(network);
+        let publisher_id = if let Some(id) = &self.publisher_id {
+            AccountId::from_hex(id)?
+        } else {
+            get_publisher_id(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network)?
+        };
+        let oracle_id = if let Some(id) = &self.oracle_id {
+            AccountId::from_hex(id)?
+        } else {
+            get_oracle_id(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network)?
+        };
+
+        let serial_num = client.rng().draw_word();
+        let leave_note = create_leave_note(publisher_id, oracle_id, serial_num)?;
+        let note_id = leave_note.id();
+
+        let transaction_request = TransactionRequestBuilder::new()
+            .own_output_notes(vec![leave_note])
+            .build()
+            .map_err(|e| anyhow::anyhow!("Error while building transaction request: {e:?}"))?;
+
+        submit_transaction_with_retry(client, publisher_id, transaction_request, &retry)
+            .await
+            .map_err(|e| anyhow::anyhow!("Error while submitting transaction: {e:?}"))?;
+
+        Ok(Done::new(format!(
+            "Leave note {note_id} sent to Oracle {oracle_id}, which drops this publisher once it consumes the note"
+        )))
+    }
+}
@@ -1,32 +1,91 @@
+pub mod commit_randomness;
 pub mod entry;
 pub mod get_entry;
+pub mod heartbeat;
 pub mod init;
+pub mod leave;
 pub mod publish;
 pub mod publish_batch;
+pub mod reveal_randomness;
+pub mod rotate_key;
+pub mod run;
 pub mod sync;
 
-use std::path::PathBuf;
-
 use clap::Parser;
-use entry::EntryCmd;
+use commit_randomness::CommitRandomnessCmd;
+use entry::{EntryCmd, StoredValue};
 use get_entry::GetEntryCmd;
-use init::InitCmd;
+use heartbeat::HeartbeatCmd;
+use init::{InitCmd, InitOutput};
+use leave::LeaveCmd;
 use pm_types::Entry;
-use pm_utils_cli::{setup_devnet_client, setup_local_client, setup_testnet_client, STORE_FILENAME};
+use pm_utils_cli::{
+    entry_plain, entry_table, setup_network_client, to_json, Done, OutputFormat, SyncOutput,
+};
 use publish::PublishCmd;
 use publish_batch::PublishBatchCmd;
+use reveal_randomness::RevealRandomnessCmd;
+use rotate_key::RotateKeyCmd;
+use run::RunCmd;
+use serde::Serialize;
 use sync::SyncCmd;
 
+/// Result of a command, printed on stdout in the `--output` format.
+///
+/// The JSON document of each variant follows the schema documented on its
+/// type, without any wrapping. The schemas are stable: fields may be added,
+/// never renamed or removed.
 // `commands` is shared by the pm-publisher binary (which dispatches through
 // SubCommand::call) and the pyo3 lib (which calls each subcommand directly), so
 // these dispatch helpers read as dead code in the lib target only.
 #[allow(dead_code)]
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
 pub enum CommandOutput {
-    /// No specific output value
+    /// `run`, which prints the result of each published batch instead
     None,
-    // Entry
+    /// `init`
+    Init(InitOutput),
+    /// `sync`
+    Sync(SyncOutput),
+    /// Commands submitting a transaction
+    Done(Done),
+    /// `get-entry`
     Entry(Entry),
+    /// `get`
+    Stored(StoredValue),
+}
+
+#[allow(dead_code)]
+impl CommandOutput {
+    /// Renders the output in `format`, without trailing newline. `None`
+    /// renders empty.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the output cannot be serialized to JSON.
+    pub fn render(&self, format: OutputFormat) -> anyhow::Result<String> {
+        if let Self::None = self {
+            return Ok(String::new());
+        }
+        if format == OutputFormat::Json {
+            return to_json(self);
+        }
+        let table = format == OutputFormat::Table;
+        Ok(match self {
+            Self::None => String::new(),
+            Self::Init(init) if table => init.table(),
+            Self::Init(init) => init.plain(),
+            Self::Sync(sync) if table => sync.table(),
+            Self::Sync(sync) => sync.plain(),
+            Self::Done(done) if table => done.table(),
+            Self::Done(done) => done.plain(),
+            Self::Entry(entry) if table => entry_table(entry),
+            Self::Entry(entry) => entry_plain(entry),
+            Self::Stored(stored) if table => stored.table(),
+            Self::Stored(stored) => stored.plain(),
+        })
+    }
 }
 
 #[derive(Debug, Parser, Clone)]
@@ -43,58 +102,42 @@ pub enum SubCommand {
     Sync(SyncCmd),
     #[clap(name = "get-entry", bin_name = "get-entry")]
     Get(GetEntryCmd),
+    #[clap(name = "heartbeat", bin_name = "heartbeat")]
+    Heartbeat(HeartbeatCmd),
+    #[clap(name = "leave", bin_name = "leave")]
+    Leave(LeaveCmd),
+    #[clap(name = "commit-randomness", bin_name = "commit-randomness")]
+    CommitRandomness(CommitRandomnessCmd),
+    #[clap(name = "reveal-randomness", bin_name = "reveal-randomness")]
+    RevealRandomness(RevealRandomnessCmd),
+    #[clap(name = "rotate-key", bin_name = "rotate-key")]
+    RotateKey(RotateKeyCmd),
+    #[clap(name = "run", bin_name = "run")]
+    Run(RunCmd),
 }
 
 impl SubCommand {
     #[allow(dead_code)] // entry point of the pm-publisher binary; unused by the lib target
-    pub async fn call(&self, network: &str) -> anyhow::Result<CommandOutput> {
-        let crate_path = PathBuf::new();
-        let store_config = crate_path.join(STORE_FILENAME);
-        let mut client = match network {
-            "testnet" => {
-                println!("Using testnet client");
-                setup_testnet_client(Some(store_config), None).await?
-            }
-            "devnet" => {
-                println!("Using devnet client");
-                setup_devnet_client(Some(store_config), None).await?
-            }
-            "local" => {
-                println!("Using local client");
-                setup_local_client(Some(store_config), None).await?
+    pub async fn call(&self, network: &str, output: OutputFormat) -> anyhow::Result<CommandOutput> {
+        eprintln!("Using {network} client");
+        let mut client = setup_network_client(network, None, None).await?;
+        let client = &mut client;
+        Ok(match self {
+            Self::Init(cmd) => CommandOutput::Init(cmd.call(client, network).await?),
+            Self::Publish(cmd) => CommandOutput::Done(cmd.call(client, network).await?),
+            Self::PublishBatch(cmd) => CommandOutput::Done(cmd.call(client, network).await?),
+            Self::Entry(cmd) => CommandOutput::Stored(cmd.call(client, network).await?),
+            Self::Sync(cmd) => CommandOutput::Sync(cmd.call(client, network).await?),
+            Self::Get(cmd) => CommandOutput::Entry(cmd.call(client, network).await?),
+            Self::Heartbeat(cmd) => CommandOutput::Done(cmd.call(client, network).await?),
+            Self::Leave(cmd) => CommandOutput::Done(cmd.call(client, network).await?),
+            Self::CommitRandomness(cmd) => CommandOutput::Done(cmd.call(client, network).await?),
+            Self::RevealRandomness(cmd) => CommandOutput::Done(cmd.call(client, network).await?),
+            Self::RotateKey(cmd) => CommandOutput::Done(cmd.call(client, network).await?),
+            Self::Run(cmd) => {
+                cmd.call(client, network, output).await?;
+                CommandOutput::None
             }
-            other => {
-                return Err(anyhow::anyhow!(
-                    "Unknown network '{}'. Must be 'local', 'devnet' or 'testnet'",
-                    other
-                ));
-            }
-        };
-        match self {
-            Self::Init(cmd) => {
-                cmd.call(&mut client, network).await?;
-                Ok(CommandOutput::None)
-            }
-            Self::Publish(cmd) => {
-                cmd.call(&mut client, network).await?;
-                Ok(CommandOutput::None)
-            }
-            Self::PublishBatch(cmd) => {
-                cmd.call(&mut client, network).await?;
-                Ok(CommandOutput::None)
-            }
-            Self::Entry(cmd) => {
-                cmd.call(&mut client, network).await?;
-                Ok(CommandOutput::None)
-            }
-            Self::Sync(cmd) => {
-                cmd.call(&mut client).await?;
-                Ok(CommandOutput::None)
-            }
-            Self::Get(cmd) => {
-                let entry = cmd.call(&mut client, network).await?;
-                Ok(CommandOutput::Entry(entry))
-            }
-        }
+        })
     }
 }
@@ -1,35 +1,49 @@
 use std::path::Path;
 
-use miden_client::{
-    keystore::FilesystemKeyStore, transaction::TransactionRequestBuilder, Client, Word,
-};
+use miden_client::{transaction::TransactionRequestBuilder, Client, Word};
 use miden_standards::code_builder::CodeBuilder;
 
 use miden_client::account::AccountId;
 use miden_client::Felt;
 use pm_accounts::{publisher::get_publisher_component_library, utils::word_to_masm};
-use pm_utils_cli::{get_publisher_id, PRAGMA_ACCOUNTS_STORAGE_FILE};
+use pm_types::{FeedId, FeedKind, FeedValue};
+use pm_utils_cli::{
+    dry_run_transaction, get_asset_registry, get_publisher_id, retry_policy,
+    submit_transaction_with_retry, Done, PragmaKeyStore, PRAGMA_ACCOUNTS_STORAGE_FILE,
+};
 
 #[derive(clap::Parser, Debug, Clone)]
 #[clap(about = "Publish an entry(Callable by the publisher itself)")]
 pub struct PublishCmd {
-    pub faucet_id: String, //"1:0"
-    pub price: u64,
+    /// Pair symbol or faucet_id ("BTC/USD" or "1:0"), or the feed name with --feed
+    pub faucet_id: String,
+    /// Price, or the feed value with --feed (negative for signed feeds)
+    #[clap(allow_negative_numbers = true)]
+    pub price: i64,
     pub decimals: u32,
     pub timestamp: u64,
     /// Optional publisher ID. If not provided, uses the first publisher from config
     #[clap(long)]
     pub publisher_id: Option<String>,
+    /// Publish a value of a generic feed of this kind (price, funding-rate,
+    /// interest-rate-index or proof-of-reserve) instead of a price entry
+    #[clap(long, value_name = "KIND")]
+    pub feed: Option<FeedKind>,
+    /// Execute the transaction locally and print the storage changes it would
+    /// make, without proving nor submitting it
+    #[clap(long)]
+    pub dry_run: bool,
 }
 
 impl PublishCmd {
-    /// Publishes a price entry to the network
+    /// Publishes a price entry, or a generic feed value with `--feed`, to the network
     ///
     /// This function performs the following operations:
     /// 1. Retrieves the publisher account ID from configuration
-    /// 2. Constructs an Entry object from the command parameters
-    /// 3. Creates a transaction script that calls the publish_entry function
-    /// 4. Submits the transaction to the Miden network
+    /// 2. Constructs an Entry (or a FeedValue) from the command parameters
+    /// 3. Creates a transaction script that calls the publish_entry (or publish_feed) function
+    /// 4. Submits the transaction to the Miden network, or only executes it
+    ///    locally and prints its storage changes with `--dry-run`
     ///
     /// # Arguments
     ///
@@ -44,43 +58,57 @@ impl PublishCmd {
     ///
     /// This function can fail if:
     /// - The publisher ID cannot be retrieved from configuration
-    /// - The pair string cannot be parsed into a valid Pair
+    /// - The faucet_id is neither a registered pair nor a valid asset key
+    /// - The feed name is invalid or the value does not fit the feed kind
     /// - The entry cannot be converted to a Word
     /// - The transaction script compilation fails
     /// - The transaction request building fails
     /// - The transaction creation or submission fails
     pub async fn call(
         &self,
-        client: &mut Client<FilesystemKeyStore>,
+        client: &mut Client<PragmaKeyStore>,
         network: &str,
-    ) -> anyhow::Result<()> {
+    ) -> anyhow::Result<Done> {
+        let retry = retry_policy(network);
         let publisher_id = if let Some(id) = &self.publisher_id {
             AccountId::from_hex(id)?
         } else {
             get_publisher_id(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network)?
         };
 
-        let parts: Vec<&str> = self.faucet_id.split(':').collect();
-        if parts.len() != 2 {
-            return Err(anyhow::anyhow!(
-                "Invalid faucet_id format. Expected PREFIX:SUFFIX (e.g., 1:0)"
-            ));
-        }
-
-        let prefix = parts[0]
-            .parse::<u64>()
-            .map_err(|_| anyhow::anyhow!("Invalid faucet_id prefix: {}", parts[0]))?;
-        let suffix = parts[1]
-            .parse::<u64>()
-            .map_err(|_| anyhow::anyhow!("Invalid faucet_id suffix: {}", parts[1]))?;
-
-        let entry_as_word: Word = [
-            Felt::new(0)?,
-            Felt::new(self.price)?,
-            Felt::new(self.decimals as u64)?,
-            Felt::new(self.timestamp)?,
-        ]
-        .into();
+        let (key, value, procedure, label) = if let Some(kind) = self.feed {
+            let feed_id = FeedId::from_name(&self.faucet_id)?;
+            let feed_value = FeedValue {
+                kind,
+                value: self.price,
+                decimals: self.decimals,
+                timestamp: self.timestamp,
+            };
+            (
+                feed_id.to_word(),
+                feed_value.to_push_word()?,
+                "publish_feed",
+                format!("{kind} feed {feed_id}"),
+            )
+        } else {
+            let registry = get_asset_registry(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE))?;
+            let asset_key = registry.resolve(&self.faucet_id)?;
+            let price = u64::try_from(self.price)
+                .map_err(|_| anyhow::anyhow!("Price must not be negative: {}", self.price))?;
+            let entry_as_word: Word = [
+                Felt::new(0)?,
+                Felt::new(price)?,
+                Felt::new(self.decimals as u64)?,
+                Felt::new(self.timestamp)?,
+            ]
+            .into();
+            (
+                asset_key.to_word(),
+                entry_as_word,
+                "publish_entry",
+                registry.label(asset_key),
+            )
+        };
 
         let tx_script_code = format!(
             "
@@ -88,18 +116,17 @@ impl PublishCmd {
                 use miden::core::sys
         
                 begin
-                    push.{entry}
-                    push.0.0.{suffix}.{prefix}
+                    push.{value}
+                    push.{key}
 
-                    call.publisher_module::publish_entry
+                    call.publisher_module::{procedure}
         
                     dropw
                     exec.sys::truncate_stack                    
                 end
                 ",
-            prefix = prefix,
-            suffix = suffix,
-            entry = word_to_masm(entry_as_word)
+            key = word_to_masm(key),
+            value = word_to_masm(value)
         );
         let publisher_lib = get_publisher_component_library();
         let publish_script = CodeBuilder::default()
@@ -113,13 +140,14 @@ impl PublishCmd {
             .build()
             .map_err(|e| anyhow::anyhow!("Error while building transaction request: {e:?}"))?;
 
-        client
-            .submit_new_transaction(publisher_id, transaction_request)
+        if self.dry_run {
+            return dry_run_transaction(client, publisher_id, transaction_request).await;
+        }
+
+        submit_transaction_with_retry(client, publisher_id, transaction_request, &retry)
             .await
             .map_err(|e| anyhow::anyhow!("Error while submitting transaction: {e:?}"))?;
 
-        println!(" Publish successful!");
-
-        Ok(())
+        Ok(Done::new(format!("Publish successful for {label}")))
     }
 }
@@ -1,14 +1,16 @@
 use std::path::Path;
 
-use miden_client::{
-    keystore::FilesystemKeyStore, transaction::TransactionRequestBuilder, Client, Word,
-};
+use miden_client::{transaction::TransactionRequestBuilder, Client, Word};
 use miden_standards::code_builder::CodeBuilder;
 
 use miden_client::account::AccountId;
 use miden_client::Felt;
 use pm_accounts::{publisher::get_publisher_component_library, utils::word_to_masm};
-use pm_utils_cli::{get_publisher_id, PRAGMA_ACCOUNTS_STORAGE_FILE};
+use pm_types::AssetKey;
+use pm_utils_cli::{
+    dry_run_transaction, get_asset_registry, get_publisher_id, retry_policy,
+    submit_transaction_with_retry, Done, PragmaKeyStore, PRAGMA_ACCOUNTS_STORAGE_FILE,
+};
 
 #[derive(clap::Parser, Debug, Clone)]
 #[clap(
@@ -16,12 +18,17 @@ use pm_utils_cli::{get_publisher_id, PRAGMA_ACCOUNTS_STORAGE_FILE};
 )]
 pub struct PublishBatchCmd {
     /// Faucet IDs to publish in format: "1:0:98000000000:6:1234567890 2:0:1900000000:6:1234567890"
-    /// Format per entry: FAUCET_ID:PRICE:DECIMALS:TIMESTAMP (e.g., "1:0" for BTC/USD)
+    /// Format per entry: FAUCET_ID:PRICE:DECIMALS:TIMESTAMP, FAUCET_ID being a pair
+    /// (e.g., "BTC/USD"), PREFIX:SUFFIX (e.g., "1:0") or a faucet account id
     #[clap(required = true)]
     pub entries: Vec<String>,
     /// Optional publisher ID. If not provided, uses the first publisher from config
     #[clap(long)]
     pub publisher_id: Option<String>,
+    /// Execute the transaction locally and print the storage changes it would
+    /// make, without proving nor submitting it
+    #[clap(long)]
+    pub dry_run: bool,
 }
 
 impl PublishBatchCmd {
@@ -31,7 +38,8 @@ impl PublishBatchCmd {
     /// 1. Retrieves the publisher account ID from configuration
     /// 2. Parses all entry strings into Entry objects
     /// 3. Creates a single transaction script that calls publish_entry for each pair
-    /// 4. Submits one transaction to the Miden network
+    /// 4. Submits one transaction to the Miden network, or only executes it
+    ///    locally and prints its storage changes with `--dry-run`
     ///
     /// # Arguments
     ///
@@ -54,40 +62,37 @@ impl PublishBatchCmd {
     #[allow(dead_code)]
     pub async fn call(
         &self,
-        client: &mut Client<FilesystemKeyStore>,
+        client: &mut Client<PragmaKeyStore>,
         network: &str,
-    ) -> anyhow::Result<()> {
+    ) -> anyhow::Result<Done> {
         let mut typed_entries = Vec::with_capacity(self.entries.len());
         for entry_str in &self.entries {
-            let parts: Vec<&str> = entry_str.split(':').collect();
-            if parts.len() != 5 {
+            // The faucet id is whatever precedes the last three fields, so it can
+            // be either PREFIX:SUFFIX or a faucet account id.
+            let parts: Vec<&str> = entry_str.rsplitn(4, ':').collect();
+            if parts.len() != 4 {
                 return Err(anyhow::anyhow!(
-                    "Invalid entry format: {}. Expected FAUCET_PREFIX:FAUCET_SUFFIX:PRICE:DECIMALS:TIMESTAMP",
+                    "Invalid entry format: {}. Expected FAUCET_ID:PRICE:DECIMALS:TIMESTAMP",
                     entry_str
                 ));
             }
-            let prefix = parts[0]
-                .parse::<u64>()
-                .map_err(|e| anyhow::anyhow!("Invalid faucet_id prefix '{}': {}", parts[0], e))?;
-            let suffix = parts[1]
-                .parse::<u64>()
-                .map_err(|e| anyhow::anyhow!("Invalid faucet_id suffix '{}': {}", parts[1], e))?;
             let price = parts[2]
                 .parse::<u64>()
                 .map_err(|e| anyhow::anyhow!("Invalid price '{}': {}", parts[2], e))?;
-            let decimals = parts[3]
+            let decimals = parts[1]
                 .parse::<u32>()
-                .map_err(|e| anyhow::anyhow!("Invalid decimals '{}': {}", parts[3], e))?;
-            let timestamp = parts[4]
+                .map_err(|e| anyhow::anyhow!("Invalid decimals '{}': {}", parts[1], e))?;
+            let timestamp = parts[0]
                 .parse::<u64>()
-                .map_err(|e| anyhow::anyhow!("Invalid timestamp '{}': {}", parts[4], e))?;
-            typed_entries.push((format!("{}:{}", prefix, suffix), price, decimals, timestamp));
+                .map_err(|e| anyhow::anyhow!("Invalid timestamp '{}': {}", parts[0], e))?;
+            typed_entries.push((parts[3].to_string(), price, decimals, timestamp));
         }
         publish_batch(
             client,
             network,
             &typed_entries,
             self.publisher_id.as_deref(),
+            self.dry_run,
         )
         .await
     }
@@ -96,46 +101,35 @@ impl PublishBatchCmd {
 /// Publish a batch of price entries in a single Miden transaction.
 ///
 /// Each entry is `(faucet_id, price, decimals, timestamp)` where `faucet_id`
-/// is `"PREFIX:SUFFIX"` (e.g. `"1:0"` for BTC/USD).
+/// is either a registered pair (e.g. `"BTC/USD"`) or any string accepted by
+/// [`AssetKey`]: `"PREFIX:SUFFIX"` (e.g. `"1:0"`) or a faucet account id.
+///
+/// With `dry_run`, the transaction is only executed locally and its storage
+/// changes are printed.
 ///
 /// Exposed as a free function so it can be called directly by other crates
 /// (notably the pyo3 binding) without having to round-trip through the
 /// CLI's string format.
 pub async fn publish_batch(
-    client: &mut Client<FilesystemKeyStore>,
+    client: &mut Client<PragmaKeyStore>,
     network: &str,
     entries: &[(String, u64, u32, u64)],
     publisher_id_override: Option<&str>,
-) -> anyhow::Result<()> {
+    dry_run: bool,
+) -> anyhow::Result<Done> {
+    let retry = retry_policy(network);
     let publisher_id = if let Some(id) = publisher_id_override {
         AccountId::from_hex(id)?
     } else {
         get_publisher_id(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network)?
     };
 
+    let registry = get_asset_registry(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE))?;
     let mut entries_data = Vec::with_capacity(entries.len());
     for (faucet_id, price, decimals, timestamp) in entries {
-        let parts: Vec<&str> = faucet_id.split(':').collect();
-        if parts.len() != 2 {
-            return Err(anyhow::anyhow!(
-                "Invalid faucet_id format '{}'. Expected PREFIX:SUFFIX (e.g., 1:0)",
-                faucet_id
-            ));
-        }
-        let prefix = parts[0]
-            .parse::<u64>()
-            .map_err(|e| anyhow::anyhow!("Invalid faucet_id prefix '{}': {}", parts[0], e))?;
-        let suffix = parts[1]
-            .parse::<u64>()
-            .map_err(|e| anyhow::anyhow!("Invalid faucet_id suffix '{}': {}", parts[1], e))?;
-
-        let faucet_id_word: Word = [
-            Felt::new(0)?,
-            Felt::new(0)?,
-            Felt::new(suffix)?,
-            Felt::new(prefix)?,
-        ]
-        .into();
+        let asset_key: AssetKey = registry
+            .resolve(faucet_id)
+            .map_err(|e| anyhow::anyhow!("Invalid faucet_id '{faucet_id}': {e}"))?;
         let entry_word: Word = [
             Felt::new(0)?,
             Felt::new(*price)?,
@@ -143,19 +137,19 @@ pub async fn publish_batch(
             Felt::new(*timestamp)?,
         ]
         .into();
-        entries_data.push((faucet_id_word, entry_word));
+        entries_data.push((asset_key, entry_word));
     }
 
     let mut publish_calls = String::new();
-    for (faucet_id_word, entry_word) in &entries_data {
+    for (asset_key, entry_word) in &entries_data {
         publish_calls.push_str(&format!(
             "
                 push.{entry}
-                push.{faucet_id_word}
+                push.{asset_key}
                 call.publisher_module::publish_entry
                 dropw
             ",
-            faucet_id_word = word_to_masm(*faucet_id_word),
+            asset_key = word_to_masm(asset_key.to_word()),
             entry = word_to_masm(*entry_word)
         ));
     }
@@ -195,14 +189,16 @@ pub async fn publish_batch(
         .build()
         .map_err(|e| anyhow::anyhow!("Error while building transaction request: {e:?}"))?;
 
-    client
-        .submit_new_transaction(publisher_id, transaction_request)
+    if dry_run {
+        return dry_run_transaction(client, publisher_id, transaction_request).await;
+    }
+
+    submit_transaction_with_retry(client, publisher_id, transaction_request, &retry)
         .await
         .map_err(|e| anyhow::anyhow!("Error while submitting transaction: {e:?}"))?;
 
-    println!(
-        "✓ Batch publish successful! ({} entries)",
+    Ok(Done::new(format!(
+        "Batch publish successful ({} entries)",
         entries_data.len()
-    );
-    Ok(())
+    )))
 }
@@ -0,0 +1,78 @@
+use std::path::Path;
+
+use miden_client::Client;
+
+use miden_client::account::AccountId;
+use pm_accounts::publisher::randomness_commitment;
+use pm_utils_cli::{
+    get_publisher_id, get_randomness_secret, retry_policy, Done, PragmaKeyStore,
+    PRAGMA_ACCOUNTS_STORAGE_FILE,
+};
+
+use super::commit_randomness::publish_randomness;
+
+#[derive(clap::Parser, Debug, Clone)]
+#[clap(about = "Reveal the randomness committed to for a round (Callable by the publisher itself)")]
+pub struct RevealRandomnessCmd {
+    /// Round to reveal the randomness of
+    pub round: u64,
+    /// Optional publisher ID. If not provided, uses the first publisher from config
+    #[clap(long)]
+    pub publisher_id: Option<String>,
+}
+
+impl RevealRandomnessCmd {
+    /// Reveals the randomness saved by `commit-randomness` for a round
+    ///
+    /// Once revealed, the randomness is combined by the oracle with the ones
+    /// of the other publishers (see `pm-oracle-cli randomness`).
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - A mutable reference to the Miden client, must be initialized first
+    /// * `network` - The network identifier (e.g., "devnet", "testnet")
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The publisher ID cannot be retrieved from configuration
+    /// - No randomness was committed to for the round
+    /// - The transaction script compilation fails
+    /// - The transaction submission fails (e.g. the round is already revealed)
+    pub async fn call(
+        &self,
+        client: &mut Client<PragmaKeyStore>,
+        network: &str,
+    ) -> anyhow::Result<Done> {
+        let retry = retry_policy(network);
+        let storage_path = Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE);
+        let publisher_id = if let Some(id) = &self.publisher_id {
+            AccountId::from_hex(id)?
+        } else {
+            get_publisher_id(storage_path, network)?
+        };
+
+        let secret = get_randomness_secret(storage_path, network, &publisher_id, self.round)?
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No randomness committed for round {}. Run commit-randomness first",
+                    self.round
+                )
+            })?;
+
+        publish_randomness(
+            client,
+            publisher_id,
+            self.round,
+            secret,
+            randomness_commitment(secret),
+            &retry,
+        )
+        .await?;
+
+        Ok(Done::new(format!(
+            "Revealed the randomness of round {}",
+            self.round
+        )))
+    }
+}
@@ -0,0 +1,165 @@
+use std::path::Path;
+
+use miden_client::{
+    account::AccountId, auth::AuthSecretKey, crypto::rpo_falcon512::SecretKey, keystore::Keystore,
+    transaction::TransactionRequestBuilder, Client, Word,
+};
+use miden_standards::code_builder::CodeBuilder;
+
+use pm_accounts::publisher::get_publisher_component_library;
+use pm_utils_cli::{
+    get_publisher_id, retry_policy, submit_transaction_with_retry, Done, PragmaKeyStore,
+    RetryPolicy, PRAGMA_ACCOUNTS_STORAGE_FILE,
+};
+
+#[derive(clap::Parser, Debug, Clone)]
+#[clap(
+    about = "Replace the key of the publisher, keeping its account id and registry slot (Callable by the publisher itself)"
+)]
+pub struct RotateKeyCmd {
+    /// Optional publisher ID. If not provided, uses the first publisher from config
+    #[clap(long)]
+    pub publisher_id: Option<String>,
+    /// Keep the old key in the keystore after the rotation
+    #[clap(long)]
+    pub keep_old_key: bool,
+}
+
+impl RotateKeyCmd {
+    /// Generates a new key for the publisher and installs it in the auth
+    /// component of the account, in place
+    ///
+    /// The new key is proposed in a first transaction signed with the current
+    /// key, then installed in a second one. The new key is stored in the
+    /// keystore before the second transaction, and the old key is removed
+    /// from it once the rotation is committed, unless `--keep-old-key`.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - A mutable reference to the Miden client, must be initialized first
+    /// * `network` - The network identifier (e.g., "devnet", "testnet")
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The publisher ID cannot be retrieved from configuration
+    /// - The keystore of the client holds no key of the publisher, of a
+    ///   supported scheme, or cannot store the new one
+    /// - The transaction script compilation fails
+    /// - A transaction submission fails
+    pub async fn call(
+        &self,
+        client: &mut Client<PragmaKeyStore>,
+        network: &str,
+    ) -> anyhow::Result<Done> {
+        let retry = retry_policy(network);
+        let publisher_id = if let Some(id) = &self.publisher_id {
+            AccountId::from_hex(id)?
+        } else {
+            get_publisher_id(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network)?
+        };
+
+        // The keystore the client signs with, which must also hold the new
+        // key to sign the second transaction.
+        let keystore = client
+            .authenticator()
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("The client holds no keystore to rotate the key in"))?;
+        let old_key_commitments = keystore
+            .get_account_key_commitments(&publisher_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to read the keystore: {e:?}"))?;
+        let Some(old_key_commitment) = old_key_commitments.first().copied() else {
+            anyhow::bail!("No key of {publisher_id} found in the keystore");
+        };
+        let old_key = keystore
+            .get_key(old_key_commitment)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to read the keystore: {e:?}"))?;
+
+        // Same signature scheme as the current key, which the auth component
+        // keeps.
+        let new_key = match old_key {
+            Some(AuthSecretKey::Falcon512Poseidon2(_)) => {
+                AuthSecretKey::Falcon512Poseidon2(SecretKey::with_rng(client.rng()))
+            }
+            Some(AuthSecretKey::EcdsaK256Keccak(_)) => AuthSecretKey::new_ecdsa_k256_keccak(),
+            Some(_) => anyhow::bail!(
+                "The key of {publisher_id} uses a signature scheme rotate-key does not support"
+            ),
+            None => anyhow::bail!(
+                "The keystore does not hand out the key of {publisher_id}, rotate it on the signer side"
+            ),
+        };
+        let new_key_commitment: Word = new_key.public_key().to_commitment().into();
+
+        // Pushed as a word literal, in the element order the auth component
+        // reads it from storage.
+        let propose_script = format!(
+            "
+            use publisher_component::publisher_module
+            use miden::core::sys
+
+            begin
+                push.{new_key_commitment}
+                call.publisher_module::propose_key
+                exec.sys::truncate_stack
+            end
+            "
+        );
+        let rotate_script = "
+            use publisher_component::publisher_module
+            use miden::core::sys
+
+            begin
+                call.publisher_module::rotate_key
+                exec.sys::truncate_stack
+            end
+            ";
+
+        submit_publisher_script(client, publisher_id, &propose_script, &retry).await?;
+        eprintln!("🔑 New key {} proposed", new_key_commitment.to_hex());
+
+        keystore
+            .add_key(&new_key, publisher_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to store the new key: {e:?}"))?;
+        submit_publisher_script(client, publisher_id, rotate_script, &retry).await?;
+
+        if !self.keep_old_key {
+            keystore
+                .remove_key(old_key_commitment)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to remove the old key: {e:?}"))?;
+        }
+
+        Ok(Done::new(format!(
+            "Key of {publisher_id} rotated, the old key can no longer sign for it"
+        )))
+    }
+}
+
+async fn submit_publisher_script(
+    client: &mut Client<PragmaKeyStore>,
+    publisher_id: AccountId,
+    tx_script_code: &str,
+    retry: &RetryPolicy,
+) -> anyhow::Result<()> {
+    let publisher_lib = get_publisher_component_library();
+    let tx_script = CodeBuilder::default()
+        .with_statically_linked_library(&publisher_lib)
+        .map_err(|e| anyhow::anyhow!("Error while setting up the component library: {e:?}"))?
+        .compile_tx_script(tx_script_code)
+        .map_err(|e| anyhow::anyhow!("Error while compiling the script: {e:?}"))?;
+
+    let transaction_request = TransactionRequestBuilder::new()
+        .custom_script(tx_script)
+        .build()
+        .map_err(|e| anyhow::anyhow!("Error while building transaction request: {e:?}"))?;
+
+    submit_transaction_with_retry(client, publisher_id, transaction_request, retry)
+        .await
+        .map_err(|e| anyhow::anyhow!("Error while submitting transaction: {e:?}"))?;
+
+    Ok(())
+}
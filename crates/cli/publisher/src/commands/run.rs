@@ -0,0 +1,54 @@
+use std::path::PathBuf;
+
+use miden_client::Client;
+use pm_utils_cli::{OutputFormat, PragmaKeyStore};
+
+use crate::pusher::{config::PusherConfig, Pusher};
+
+#[derive(clap::Parser, Debug, Clone)]
+#[clap(
+    about = "Run a price pusher publishing the configured assets until stopped (Callable by the publisher itself)"
+)]
+pub struct RunCmd {
+    /// Path to the pusher configuration (TOML)
+    #[clap(long, default_value = "pusher.toml")]
+    pub config: PathBuf,
+    /// Optional publisher ID. If not provided, uses the first publisher from config
+    #[clap(long)]
+    pub publisher_id: Option<String>,
+}
+
+impl RunCmd {
+    /// Runs the price pusher until SIGINT or SIGTERM
+    ///
+    /// Every `interval` seconds, the price of each configured asset is
+    /// aggregated from its sources and compared with the last entry the
+    /// publisher stored for it. The assets that moved by at least their
+    /// `deviation_bps`, or whose entry is older than their `heartbeat`, are
+    /// published in a single batch. Every decision is logged on stderr, and
+    /// the result of each batch printed on stdout in `output`.
+    ///
+    /// # Arguments
+    ///
+    /// * `client` - A mutable reference to the Miden client, reused for every publish
+    /// * `network` - The network identifier (e.g., "devnet", "testnet")
+    /// * `output` - The format of the results printed on stdout
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - The configuration cannot be read or is invalid
+    /// - A pair of the configuration is not registered
+    /// - The publisher ID cannot be resolved
+    /// - The signal handlers cannot be installed
+    pub async fn call(
+        &self,
+        client: &mut Client<PragmaKeyStore>,
+        network: &str,
+        output: OutputFormat,
+    ) -> anyhow::Result<()> {
+        let config = PusherConfig::load(&self.config)?;
+        let mut pusher = Pusher::new(config, self.publisher_id.clone())?;
+        pusher.run(client, network, output).await
+    }
+}
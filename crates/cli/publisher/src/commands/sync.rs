@@ -1,36 +1,22 @@
 use clap::Parser;
 
-use miden_client::{keystore::FilesystemKeyStore, Client};
+use miden_client::Client;
+use pm_utils_cli::{retry_policy, sync_state_with_retry, PragmaKeyStore, SyncOutput};
 
 #[derive(Debug, Clone, Parser)]
 #[clap(about = "Sync local state with the blockchain")]
 pub struct SyncCmd {}
 
 impl SyncCmd {
-    pub async fn call(&self, client: &mut Client<FilesystemKeyStore>) -> anyhow::Result<()> {
-        let new_details = client
-            .sync_state()
+    pub async fn call(
+        &self,
+        client: &mut Client<PragmaKeyStore>,
+        network: &str,
+    ) -> anyhow::Result<SyncOutput> {
+        let retry = retry_policy(network);
+        let new_details = sync_state_with_retry(client, &retry)
             .await
             .map_err(|e| anyhow::anyhow!("Could not sync state: {}", e))?;
-        println!("🔁 Sync successful!\n");
-
-        println!("State synced to block {}", new_details.block_num);
-        println!(
-            "Tracked notes updated: {}",
-            new_details.committed_notes.len()
-        );
-        println!(
-            "Tracked notes consumed: {}",
-            new_details.consumed_notes.len()
-        );
-        println!(
-            "Tracked accounts updated: {}",
-            new_details.updated_accounts.len()
-        );
-        println!(
-            "Commited transactions: {}",
-            new_details.committed_transactions.len()
-        );
-        Ok(())
+        Ok(SyncOutput::from(&new_details))
     }
 }
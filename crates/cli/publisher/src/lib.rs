@@ -1,5 +1,5 @@
 use miden_client::account::AccountId;
-use miden_client::{keystore::FilesystemKeyStore, Client};
+use miden_client::Client;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use std::collections::HashMap;
@@ -8,11 +8,16 @@ use std::sync::{Arc, Mutex as StdMutex, OnceLock};
 use tokio::runtime::{Builder, Runtime};
 use tokio::sync::Mutex as AsyncMutex;
 mod commands;
+mod pusher;
 use crate::commands::{
-    entry::EntryCmd, get_entry::GetEntryCmd, init::InitCmd, publish::PublishCmd,
-    publish_batch::publish_batch as do_publish_batch, sync::SyncCmd,
+    entry::EntryCmd, get_entry::GetEntryCmd, heartbeat::HeartbeatCmd, init::InitCmd,
+    leave::LeaveCmd, publish::PublishCmd, publish_batch::publish_batch as do_publish_batch,
+    sync::SyncCmd,
+};
+use pm_utils_cli::{
+    import_account_with_retry, override_network_profile, resolve_network_profile, retry_policy,
+    setup_network_client, to_json, PragmaKeyStore, STORE_FILENAME,
 };
-use pm_utils_cli::{setup_devnet_client, setup_local_client, setup_testnet_client, STORE_FILENAME};
 
 /// Single shared Tokio runtime for the lifetime of the Python process.
 /// Creating one runtime per pyo3 call (the previous behaviour) was
@@ -39,7 +44,7 @@ fn rt() -> &'static Runtime {
 /// calls instead of rebuilding it on every call. The long-running price-pusher
 /// publishes every few seconds, so rebuilding a client each time (reopening the
 /// store, re-logging "Initializing testnet client") was pure overhead.
-type CachedClient = Arc<AsyncMutex<Client<FilesystemKeyStore>>>;
+type CachedClient = Arc<AsyncMutex<Client<PragmaKeyStore>>>;
 static CLIENTS: OnceLock<StdMutex<HashMap<String, CachedClient>>> = OnceLock::new();
 
 async fn cached_client(
@@ -64,14 +69,35 @@ async fn cached_client(
     Ok(cache.lock().unwrap().entry(key).or_insert(client).clone())
 }
 
+/// Drops the cached clients of `network`, whose RPC timeout is set when they
+/// are built.
+fn forget_clients(network: &str) {
+    if let Some(cache) = CLIENTS.get() {
+        let prefix = format!("{network}|");
+        cache
+            .lock()
+            .unwrap()
+            .retain(|key, _| !key.starts_with(&prefix));
+    }
+}
+
 /// Initialize publisher and return a client handle
+///
+/// `rpc_timeout_ms`, `max_attempts`, `initial_backoff_ms` and `max_backoff_ms`
+/// override the timeout and retry policy of the network profile for every
+/// later call of the process.
 #[pyfunction]
 #[pyo3(name = "init")]
+#[allow(clippy::too_many_arguments)]
 fn py_init(
     oracle_id: String,
     storage_path: Option<String>,
     keystore_path: Option<String>,
     network: Option<String>,
+    rpc_timeout_ms: Option<u64>,
+    max_attempts: Option<u32>,
+    initial_backoff_ms: Option<u64>,
+    max_backoff_ms: Option<u64>,
 ) -> PyResult<()> {
     rt().block_on(async {
         // Convert storage_path to PathBuf, default to current dir if None
@@ -79,11 +105,29 @@ fn py_init(
 
         // Use appropriate client setup based on network parameter
         let network_str = network.as_deref().unwrap_or("testnet");
+
+        if rpc_timeout_ms.is_some()
+            || max_attempts.is_some()
+            || initial_backoff_ms.is_some()
+            || max_backoff_ms.is_some()
+        {
+            let mut profile = resolve_network_profile(network_str)
+                .map_err(|e| PyValueError::new_err(format!("Init failed: {}", e)))?;
+            profile.timeout_ms = rpc_timeout_ms.unwrap_or(profile.timeout_ms);
+            profile.retry.max_attempts = max_attempts.unwrap_or(profile.retry.max_attempts);
+            profile.retry.initial_backoff_ms =
+                initial_backoff_ms.unwrap_or(profile.retry.initial_backoff_ms);
+            profile.retry.max_backoff_ms = max_backoff_ms.unwrap_or(profile.retry.max_backoff_ms);
+            override_network_profile(network_str, profile);
+            forget_clients(network_str);
+        }
+
         let client_arc = cached_client(network_str, store_config, keystore_path).await?;
         let mut client = client_arc.lock().await;
 
         let cmd = InitCmd {
             oracle_id: Some(oracle_id),
+            request_registration: false,
         };
 
         cmd.call(&mut client, network_str)
@@ -117,10 +161,13 @@ fn py_publish(
 
         let cmd = PublishCmd {
             faucet_id,
-            price,
+            price: i64::try_from(price)
+                .map_err(|_| PyValueError::new_err(format!("Price {price} is out of range")))?,
             decimals,
             timestamp,
             publisher_id: None,
+            feed: None,
+            dry_run: false,
         };
 
         cmd.call(&mut client, network_str)
@@ -155,17 +202,12 @@ fn py_get_entry(
             .await
             .map_err(|e| PyValueError::new_err(format!("Get entry failed: {}", e)))?;
 
-        Ok(serde_json::json!({
-            "faucet_id": entry.faucet_id,
-            "price": entry.price,
-            "decimals": entry.decimals,
-            "timestamp": entry.timestamp,
-        })
-        .to_string())
+        to_json(&entry).map_err(|e| PyValueError::new_err(format!("Get entry failed: {}", e)))
     })
 }
 
-/// Get entry details
+/// Get entry details. Returns the entry stored by the publisher serialized as
+/// a JSON string, following the schema of `pm-publisher get --output json`.
 #[pyfunction]
 #[pyo3(name = "entry")]
 fn py_entry(
@@ -182,19 +224,24 @@ fn py_entry(
         let client_arc = cached_client(network_str, store_config, keystore_path).await?;
         let mut client = client_arc.lock().await;
 
-        let cmd = EntryCmd { faucet_id };
-        cmd.call(&mut client, network_str)
+        let cmd = EntryCmd {
+            faucet_id,
+            feed: false,
+        };
+        let stored = cmd
+            .call(&mut client, network_str)
             .await
             .map_err(|e| PyValueError::new_err(format!("Entry failed: {}", e)))?;
 
-        Ok("Entry details retrieved successfully!".to_string())
+        to_json(&stored).map_err(|e| PyValueError::new_err(format!("Entry failed: {}", e)))
     })
 }
 
 /// Publish a batch of price entries in a single Miden transaction.
 ///
 /// `entries` is a list of `(faucet_id, price, decimals, timestamp)` tuples,
-/// where `faucet_id` is the `"PREFIX:SUFFIX"` string (e.g. `"1:0"`).
+/// where `faucet_id` is a registered pair (e.g. `"BTC/USD"`), the
+/// `"PREFIX:SUFFIX"` string (e.g. `"1:0"`) or a faucet account id.
 #[pyfunction]
 #[pyo3(name = "publish_batch")]
 fn py_publish_batch(
@@ -212,7 +259,7 @@ fn py_publish_batch(
         let client_arc = cached_client(network_str, store_config, keystore_path).await?;
         let mut client = client_arc.lock().await;
 
-        do_publish_batch(&mut client, network_str, &entries, None)
+        do_publish_batch(&mut client, network_str, &entries, None, false)
             .await
             .map_err(|e| PyValueError::new_err(format!("Publish batch failed: {}", e)))?;
 
@@ -220,6 +267,58 @@ fn py_publish_batch(
     })
 }
 
+/// Refresh the publisher heartbeat without publishing any entry.
+#[pyfunction]
+#[pyo3(name = "heartbeat")]
+fn py_heartbeat(
+    storage_path: Option<String>,
+    keystore_path: Option<String>,
+    network: Option<String>,
+) -> PyResult<()> {
+    rt().block_on(async {
+        let store_config = get_store_config(storage_path);
+        let network_str = network.as_deref().unwrap_or("testnet");
+        let client_arc = cached_client(network_str, store_config, keystore_path).await?;
+        let mut client = client_arc.lock().await;
+
+        let cmd = HeartbeatCmd { publisher_id: None };
+        cmd.call(&mut client, network_str)
+            .await
+            .map_err(|e| PyValueError::new_err(format!("Heartbeat failed: {}", e)))?;
+
+        Ok(())
+    })
+}
+
+/// Leave the oracle with a leave note, which drops the publisher from the
+/// registry once the oracle consumes it. Uses the oracle from config unless
+/// `oracle_id` is given.
+#[pyfunction]
+#[pyo3(name = "leave")]
+fn py_leave(
+    oracle_id: Option<String>,
+    storage_path: Option<String>,
+    keystore_path: Option<String>,
+    network: Option<String>,
+) -> PyResult<()> {
+    rt().block_on(async {
+        let store_config = get_store_config(storage_path);
+        let network_str = network.as_deref().unwrap_or("testnet");
+        let client_arc = cached_client(network_str, store_config, keystore_path).await?;
+        let mut client = client_arc.lock().await;
+
+        let cmd = LeaveCmd {
+            oracle_id,
+            publisher_id: None,
+        };
+        cmd.call(&mut client, network_str)
+            .await
+            .map_err(|e| PyValueError::new_err(format!("Leave failed: {}", e)))?;
+
+        Ok(())
+    })
+}
+
 /// Import an existing on-chain public account (oracle or publisher) into the
 /// local store so its state is tracked across restarts. This is required when
 /// the local SQLite store has been wiped (e.g. ephemeral pod storage in K8s)
@@ -243,8 +342,7 @@ fn py_import_account(
             PyValueError::new_err(format!("Invalid account_id '{}': {}", account_id, e))
         })?;
 
-        client
-            .import_account_by_id(id)
+        import_account_with_retry(&mut client, id, &retry_policy(network_str))
             .await
             .map_err(|e| PyValueError::new_err(format!("Import account failed: {}", e)))?;
 
@@ -270,7 +368,7 @@ fn py_sync(
         let mut client = client_arc.lock().await;
 
         let cmd = SyncCmd {};
-        cmd.call(&mut client)
+        cmd.call(&mut client, network_str)
             .await
             .map_err(|e| PyValueError::new_err(format!("Sync failed: {}", e)))?;
 
@@ -287,43 +385,23 @@ fn pm_publisher(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
     m.add_wrapped(wrap_pyfunction!(py_get_entry))?;
     m.add_wrapped(wrap_pyfunction!(py_entry))?;
     m.add_wrapped(wrap_pyfunction!(py_sync))?;
+    m.add_wrapped(wrap_pyfunction!(py_heartbeat))?;
+    m.add_wrapped(wrap_pyfunction!(py_leave))?;
     m.add_wrapped(wrap_pyfunction!(py_import_account))?;
     Ok(())
 }
 
 // Utilitary functions:
-// Use appropriate client setup based on network parameter
+// Use the client setup of the network profile (built-in or from pragma_miden.json)
 async fn setup_client(
     network: &str,
     store_config: PathBuf,
     keystore_path: Option<String>,
-) -> PyResult<Client<FilesystemKeyStore>> {
-    match network {
-        "devnet" => {
-            println!("Initializing devnet client");
-            setup_devnet_client(Some(store_config), keystore_path)
-                .await
-                .map_err(|e| PyValueError::new_err(format!("Failed to setup devnet client: {}", e)))
-        }
-        "testnet" => {
-            println!("Initializing testnet client");
-            setup_testnet_client(Some(store_config), keystore_path)
-                .await
-                .map_err(|e| {
-                    PyValueError::new_err(format!("Failed to setup testnet client: {}", e))
-                })
-        }
-        "local" => {
-            println!("Initializing local client");
-            setup_local_client(Some(store_config), keystore_path)
-                .await
-                .map_err(|e| PyValueError::new_err(format!("Failed to setup local client: {}", e)))
-        }
-        other => Err(PyValueError::new_err(format!(
-            "Unknown network '{}'. Must be 'local', 'devnet' or 'testnet'",
-            other
-        ))),
-    }
+) -> PyResult<Client<PragmaKeyStore>> {
+    eprintln!("Initializing {network} client");
+    setup_network_client(network, Some(store_config), keystore_path)
+        .await
+        .map_err(|e| PyValueError::new_err(format!("Failed to setup {network} client: {e}")))
 }
 
 // Helper function to setup store configuration path
@@ -1,15 +1,27 @@
 use clap::Parser;
 pub mod commands;
+pub mod pusher;
 use commands::SubCommand;
+use pm_utils_cli::OutputFormat;
 
 #[derive(Parser, Debug)]
 #[command(name = "pm-publisher")]
 #[command(about = "Pragma Miden publisher CLI")]
 struct Cli {
-    /// Network to use (local, devnet or testnet)
+    /// Network to use (local, devnet, testnet or a profile added with `pm-oracle network add`)
     #[clap(short = 'n', long = "network", default_value = "devnet", global = true)]
     pub network: String,
 
+    /// Format of the result printed on stdout
+    #[clap(
+        short = 'o',
+        long = "output",
+        value_enum,
+        default_value_t = OutputFormat::Table,
+        global = true
+    )]
+    pub output: OutputFormat,
+
     #[command(subcommand)]
     command: SubCommand,
 }
@@ -17,6 +29,10 @@ struct Cli {
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
-    cli.command.call(&cli.network).await?;
+    let output = cli.command.call(&cli.network, cli.output).await?;
+    let rendered = output.render(cli.output)?;
+    if !rendered.is_empty() {
+        println!("{rendered}");
+    }
     Ok(())
 }
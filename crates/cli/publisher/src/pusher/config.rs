@@ -0,0 +1,306 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use anyhow::Context;
+use pm_types::AggregationMode;
+use serde::{Deserialize, Deserializer};
+
+use super::policy::PublishPolicy;
+use super::source::SourceConfig;
+
+/// Configuration of `pm-publisher run`, read from a TOML file:
+///
+/// ```toml
+/// interval = 10
+/// deviation_bps = 50
+/// heartbeat = 300
+///
+/// [[assets]]
+/// pair = "BTC/USD"
+/// decimals = 8
+/// aggregation = "median"
+/// deviation_bps = 25
+/// sources = [
+///     { type = "http", url = "https://api.example.com/btc", json_path = "$.price" },
+///     { type = "fixed", price = 98000.5 },
+/// ]
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PusherConfig {
+    /// Seconds between two polls of the price sources.
+    #[serde(default = "default_interval")]
+    pub interval: u64,
+    /// Move from the last stored entry, in basis points, from which an asset
+    /// is published again. Assets can override it.
+    #[serde(default = "default_deviation_bps")]
+    pub deviation_bps: u64,
+    /// Age of the last stored entry, in seconds, from which an asset is
+    /// published again even if its price did not move. Assets can override it.
+    #[serde(default = "default_heartbeat")]
+    pub heartbeat: u64,
+    /// Seconds after which a request to an HTTP source is abandoned.
+    #[serde(default = "default_http_timeout")]
+    pub http_timeout: u64,
+    pub assets: Vec<AssetConfig>,
+}
+
+/// An asset published by the pusher.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AssetConfig {
+    /// Pair symbol or asset key, as accepted by `pm-publisher publish`.
+    pub pair: String,
+    /// Number of decimals the prices are published with.
+    #[serde(default = "default_decimals")]
+    pub decimals: u32,
+    pub sources: Vec<SourceConfig>,
+    /// How the prices of the sources are aggregated: median (the default),
+    /// mean, min or max.
+    #[serde(
+        default = "default_aggregation",
+        deserialize_with = "deserialize_aggregation"
+    )]
+    pub aggregation: AggregationMode,
+    /// Number of sources that must answer for the asset to be published.
+    #[serde(default = "default_min_sources")]
+    pub min_sources: usize,
+    /// Deviation threshold of the asset, instead of the global one.
+    pub deviation_bps: Option<u64>,
+    /// Heartbeat of the asset, instead of the global one.
+    pub heartbeat: Option<u64>,
+}
+
+fn default_interval() -> u64 {
+    10
+}
+
+fn default_deviation_bps() -> u64 {
+    50
+}
+
+fn default_heartbeat() -> u64 {
+    300
+}
+
+fn default_http_timeout() -> u64 {
+    5
+}
+
+fn default_decimals() -> u32 {
+    8
+}
+
+fn default_aggregation() -> AggregationMode {
+    AggregationMode::Median
+}
+
+fn default_min_sources() -> usize {
+    1
+}
+
+fn deserialize_aggregation<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<AggregationMode, D::Error> {
+    String::deserialize(deserializer)?
+        .parse()
+        .map_err(serde::de::Error::custom)
+}
+
+impl PusherConfig {
+    /// Reads and validates the configuration at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the file cannot be read or parsed, or if the configuration is
+    /// invalid (see [`PusherConfig::validate`]).
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read pusher config: {}", path.display()))?;
+        let config: Self = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse pusher config: {}", path.display()))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Thresholds of `asset`: its own when set, the global ones otherwise.
+    pub fn policy(&self, asset: &AssetConfig) -> PublishPolicy {
+        PublishPolicy {
+            deviation_bps: asset.deviation_bps.unwrap_or(self.deviation_bps),
+            heartbeat: asset.heartbeat.unwrap_or(self.heartbeat),
+        }
+    }
+
+    /// # Errors
+    ///
+    /// Fails if the interval is zero, if no asset or the same asset twice is
+    /// configured, or if an asset has no sources or requires more sources
+    /// than it has.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.interval == 0 {
+            anyhow::bail!("The pusher interval must be at least 1 second");
+        }
+        if self.assets.is_empty() {
+            anyhow::bail!("The pusher config must list at least one asset");
+        }
+        let mut pairs = HashSet::new();
+        for asset in &self.assets {
+            if !pairs.insert(asset.pair.trim().to_uppercase()) {
+                anyhow::bail!("Asset {} is configured twice", asset.pair);
+            }
+            if asset.sources.is_empty() {
+                anyhow::bail!("Asset {} has no sources", asset.pair);
+            }
+            if asset.min_sources == 0 || asset.min_sources > asset.sources.len() {
+                anyhow::bail!(
+                    "Asset {} requires {} sources but has {}",
+                    asset.pair,
+                    asset.min_sources,
+                    asset.sources.len()
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_config_with_defaults() {
+        let config: PusherConfig = toml::from_str(
+            r#"
+            [[assets]]
+            pair = "BTC/USD"
+            sources = [{ type = "fixed", price = 98000.5 }]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.interval, 10);
+        assert_eq!(config.deviation_bps, 50);
+        assert_eq!(config.heartbeat, 300);
+        assert_eq!(config.http_timeout, 5);
+        assert_eq!(config.assets[0].decimals, 8);
+        assert_eq!(
+            config.assets[0].sources,
+            [SourceConfig::Fixed { price: 98000.5 }]
+        );
+        assert_eq!(config.assets[0].aggregation, AggregationMode::Median);
+        assert_eq!(config.assets[0].min_sources, 1);
+        assert_eq!(
+            config.policy(&config.assets[0]),
+            PublishPolicy {
+                deviation_bps: 50,
+                heartbeat: 300
+            }
+        );
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn test_parse_every_source_type() {
+        let config: PusherConfig = toml::from_str(
+            r#"
+            [[assets]]
+            pair = "ETH/USD"
+            aggregation = "mean"
+            min_sources = 2
+            sources = [
+                { type = "http", url = "http://localhost/eth", json_path = "$.price", headers = { "x-api-key" = "key" } },
+                { type = "file", path = "prices.csv" },
+                { type = "file", path = "prices.json", json_path = "$.eth" },
+            ]
+            "#,
+        )
+        .unwrap();
+        let asset = &config.assets[0];
+        assert_eq!(asset.aggregation, AggregationMode::Mean);
+        assert_eq!(asset.sources.len(), 3);
+        assert_eq!(
+            asset.sources[1],
+            SourceConfig::File {
+                path: PathBuf::from("prices.csv"),
+                json_path: None
+            }
+        );
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn test_per_asset_thresholds_override_the_global_ones() {
+        let config: PusherConfig = toml::from_str(
+            r#"
+            deviation_bps = 100
+            heartbeat = 600
+
+            [[assets]]
+            pair = "BTC/USD"
+            deviation_bps = 10
+            sources = [{ type = "fixed", price = 1.0 }]
+
+            [[assets]]
+            pair = "ETH/USD"
+            heartbeat = 60
+            sources = [{ type = "fixed", price = 1.0 }]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.policy(&config.assets[0]),
+            PublishPolicy {
+                deviation_bps: 10,
+                heartbeat: 600
+            }
+        );
+        assert_eq!(
+            config.policy(&config.assets[1]),
+            PublishPolicy {
+                deviation_bps: 100,
+                heartbeat: 60
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_configs_are_rejected() {
+        let no_assets: PusherConfig = toml::from_str("assets = []").unwrap();
+        assert!(no_assets.validate().is_err());
+
+        let duplicated: PusherConfig = toml::from_str(
+            r#"
+            [[assets]]
+            pair = "BTC/USD"
+            sources = [{ type = "fixed", price = 1.0 }]
+
+            [[assets]]
+            pair = "btc/usd"
+            sources = [{ type = "fixed", price = 2.0 }]
+            "#,
+        )
+        .unwrap();
+        assert!(duplicated.validate().is_err());
+
+        let too_many_required: PusherConfig = toml::from_str(
+            r#"
+            [[assets]]
+            pair = "BTC/USD"
+            min_sources = 2
+            sources = [{ type = "fixed", price = 1.0 }]
+            "#,
+        )
+        .unwrap();
+        assert!(too_many_required.validate().is_err());
+
+        assert!(toml::from_str::<PusherConfig>(
+            "[[assets]]\npair = \"BTC/USD\"\naggregation = \"mode\"\nsources = []"
+        )
+        .is_err());
+
+        assert!(toml::from_str::<PusherConfig>("interval = 10\nunknown = 1\nassets = []").is_err());
+    }
+}
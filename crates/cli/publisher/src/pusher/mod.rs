@@ -0,0 +1,202 @@
+//! Long-running price pusher behind `pm-publisher run`.
+//!
+//! Every `interval` seconds, the pusher polls the sources of each configured
+//! asset, compares the aggregated price with the last entry the publisher
+//! stored for the asset, and publishes, in a single `publish_batch`
+//! transaction, the assets whose price moved enough or whose heartbeat
+//! elapsed. The same client is reused for every tick.
+
+pub mod config;
+pub mod policy;
+pub mod source;
+
+use std::future::Future;
+use std::path::Path;
+use std::time::Duration;
+
+use miden_client::{account::AccountId, Client};
+use pm_types::AssetKey;
+use pm_utils_cli::{
+    get_asset_registry, get_publisher_id, retry_policy, sync_state_with_retry, Done, OutputFormat,
+    PragmaKeyStore, PRAGMA_ACCOUNTS_STORAGE_FILE,
+};
+use tokio::time::MissedTickBehavior;
+
+use crate::commands::{entry::stored_entry, publish_batch::publish_batch, CommandOutput};
+use config::PusherConfig;
+use policy::PublishPolicy;
+use source::AssetSources;
+
+/// An asset published by the pusher.
+struct PushedAsset {
+    key: AssetKey,
+    sources: AssetSources,
+    policy: PublishPolicy,
+}
+
+pub struct Pusher {
+    config: PusherConfig,
+    assets: Vec<PushedAsset>,
+    publisher_id: Option<String>,
+}
+
+impl Pusher {
+    /// Builds the sources and resolves the pairs of the configured assets.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a source cannot be built or a pair is not registered.
+    pub fn new(config: PusherConfig, publisher_id: Option<String>) -> anyhow::Result<Self> {
+        let registry = get_asset_registry(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE))?;
+        let http_client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.http_timeout))
+            .build()?;
+        let assets = config
+            .assets
+            .iter()
+            .map(|asset| {
+                Ok(PushedAsset {
+                    key: registry.resolve(&asset.pair)?,
+                    sources: AssetSources::from_config(asset, &http_client)?,
+                    policy: config.policy(asset),
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self {
+            config,
+            assets,
+            publisher_id,
+        })
+    }
+
+    /// Publishes on every tick until SIGINT or SIGTERM.
+    ///
+    /// The result of each published batch is printed on stdout in `output`,
+    /// everything else is logged on stderr. A failed tick is logged and
+    /// retried on the next one. A signal received during a tick stops the
+    /// pusher once the tick completes.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the publisher ID cannot be resolved or the signal handlers
+    /// cannot be installed.
+    pub async fn run(
+        &mut self,
+        client: &mut Client<PragmaKeyStore>,
+        network: &str,
+        output: OutputFormat,
+    ) -> anyhow::Result<()> {
+        let publisher_id = match &self.publisher_id {
+            Some(id) => AccountId::from_hex(id)?,
+            None => get_publisher_id(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network)?,
+        };
+
+        let shutdown = shutdown_signal()?;
+        tokio::pin!(shutdown);
+
+        let mut interval = tokio::time::interval(Duration::from_secs(self.config.interval));
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        eprintln!(
+            "🚀 Pushing {} assets every {}s",
+            self.assets.len(),
+            self.config.interval
+        );
+        loop {
+            tokio::select! {
+                biased;
+                _ = &mut shutdown => break,
+                _ = interval.tick() => {}
+            }
+            match self.tick(client, network, publisher_id).await {
+                Ok(Some(done)) => println!("{}", CommandOutput::Done(done).render(output)?),
+                Ok(None) => {}
+                Err(e) => eprintln!("⚠️ Tick failed: {e:#}"),
+            }
+        }
+        eprintln!("👋 Pusher stopped");
+
+        Ok(())
+    }
+
+    /// Polls every source and publishes the assets the policy selects.
+    /// Returns `None` if none was published.
+    async fn tick(
+        &self,
+        client: &mut Client<PragmaKeyStore>,
+        network: &str,
+        publisher_id: AccountId,
+    ) -> anyhow::Result<Option<Done>> {
+        let retry = retry_policy(network);
+        let now = chrono::Utc::now().timestamp() as u64;
+
+        // The stored entries are compared with the synced state, so that an
+        // entry published on the previous tick is taken into account.
+        sync_state_with_retry(client, &retry).await?;
+        let publisher = client
+            .get_account(publisher_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Publisher account {publisher_id} not found"))?;
+
+        let mut entries = Vec::new();
+        for asset in &self.assets {
+            let pair = &asset.sources.pair;
+            let (_, price, decimals, timestamp) = match asset.sources.fetch_entry(now).await {
+                Ok(entry) => entry,
+                Err(e) => {
+                    eprintln!("⚠️ Failed to price {pair}: {e:#}");
+                    continue;
+                }
+            };
+            let last = stored_entry(&publisher, asset.key)?;
+            let decision = asset
+                .policy
+                .decide(last.as_ref(), price, decimals, timestamp);
+            if decision.should_publish() {
+                eprintln!("📤 {pair}: {decision}");
+                // Published under the asset key the stored entry was read at.
+                entries.push((asset.key.to_string(), price, decimals, timestamp));
+            } else {
+                eprintln!("💤 {pair}: {decision}");
+            }
+        }
+        if entries.is_empty() {
+            return Ok(None);
+        }
+
+        let done = publish_batch(
+            client,
+            network,
+            &entries,
+            Some(&publisher_id.to_hex()),
+            false,
+        )
+        .await?;
+        Ok(Some(done))
+    }
+}
+
+/// Resolves on the first SIGINT or SIGTERM.
+///
+/// The handlers are installed when this is called, so a signal received while
+/// a tick runs is not missed.
+#[cfg(unix)]
+fn shutdown_signal() -> anyhow::Result<impl Future<Output = ()>> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut interrupt = signal(SignalKind::interrupt())?;
+    let mut terminate = signal(SignalKind::terminate())?;
+    Ok(async move {
+        tokio::select! {
+            _ = interrupt.recv() => {}
+            _ = terminate.recv() => {}
+        }
+    })
+}
+
+#[cfg(not(unix))]
+fn shutdown_signal() -> anyhow::Result<impl Future<Output = ()>> {
+    Ok(async {
+        let _ = tokio::signal::ctrl_c().await;
+    })
+}
@@ -0,0 +1,189 @@
+use std::fmt;
+
+use pm_types::Entry;
+
+/// When an asset is published: when its price moved by at least
+/// `deviation_bps` basis points from the last entry the publisher stored, or
+/// when that entry is `heartbeat` seconds old.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublishPolicy {
+    pub deviation_bps: u64,
+    pub heartbeat: u64,
+}
+
+/// Why an asset is published, or why it is not.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decision {
+    /// The publisher never stored an entry for the asset.
+    FirstEntry,
+    /// The price is published with other decimals than the stored entry.
+    DecimalsChanged { from: u32, to: u32 },
+    /// The stored entry is older than the heartbeat.
+    Heartbeat { age: u64, heartbeat: u64 },
+    /// The price moved beyond the threshold.
+    Deviation { moved_bps: u64, threshold_bps: u64 },
+    /// The price did not move enough and the stored entry is recent.
+    Skip {
+        moved_bps: u64,
+        threshold_bps: u64,
+        age: u64,
+        heartbeat: u64,
+    },
+}
+
+impl Decision {
+    pub fn should_publish(&self) -> bool {
+        !matches!(self, Self::Skip { .. })
+    }
+}
+
+impl fmt::Display for Decision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FirstEntry => write!(f, "publish, no entry stored yet"),
+            Self::DecimalsChanged { from, to } => {
+                write!(f, "publish, decimals changed from {from} to {to}")
+            }
+            Self::Heartbeat { age, heartbeat } => {
+                write!(f, "publish, last entry is {age}s old (heartbeat {heartbeat}s)")
+            }
+            Self::Deviation {
+                moved_bps,
+                threshold_bps,
+            } => write!(f, "publish, moved {moved_bps} bps (threshold {threshold_bps} bps)"),
+            Self::Skip {
+                moved_bps,
+                threshold_bps,
+                age,
+                heartbeat,
+            } => write!(
+                f,
+                "skip, moved {moved_bps} bps (threshold {threshold_bps} bps), last entry is {age}s old (heartbeat {heartbeat}s)"
+            ),
+        }
+    }
+}
+
+impl PublishPolicy {
+    /// Decides whether `price` has to be published at `now`, given the last
+    /// entry the publisher stored for the asset, if any.
+    pub fn decide(&self, last: Option<&Entry>, price: u64, decimals: u32, now: u64) -> Decision {
+        let Some(last) = last else {
+            return Decision::FirstEntry;
+        };
+        if decimals != last.decimals {
+            return Decision::DecimalsChanged {
+                from: last.decimals,
+                to: decimals,
+            };
+        }
+        let age = now.saturating_sub(last.timestamp);
+        if age >= self.heartbeat {
+            return Decision::Heartbeat {
+                age,
+                heartbeat: self.heartbeat,
+            };
+        }
+        let moved_bps = deviation_bps(last.price, price);
+        if moved_bps >= self.deviation_bps {
+            return Decision::Deviation {
+                moved_bps,
+                threshold_bps: self.deviation_bps,
+            };
+        }
+        Decision::Skip {
+            moved_bps,
+            threshold_bps: self.deviation_bps,
+            age,
+            heartbeat: self.heartbeat,
+        }
+    }
+}
+
+/// Move from `last` to `price` in basis points of `last`, rounded down. Any
+/// move from a zero price is infinite.
+fn deviation_bps(last: u64, price: u64) -> u64 {
+    if last == 0 {
+        return if price == 0 { 0 } else { u64::MAX };
+    }
+    let moved = u128::from(price.abs_diff(last)) * 10_000 / u128::from(last);
+    u64::try_from(moved).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: PublishPolicy = PublishPolicy {
+        deviation_bps: 50,
+        heartbeat: 300,
+    };
+
+    fn last() -> Entry {
+        Entry {
+            faucet_id: "1:0".to_string(),
+            price: 10_000,
+            decimals: 2,
+            timestamp: 1_000,
+        }
+    }
+
+    #[test]
+    fn test_deviation_trigger() {
+        // 50 bps of 10_000 is 50.
+        for price in [10_049, 9_951] {
+            assert!(!POLICY
+                .decide(Some(&last()), price, 2, 1_010)
+                .should_publish());
+        }
+        assert_eq!(
+            POLICY.decide(Some(&last()), 10_050, 2, 1_010),
+            Decision::Deviation {
+                moved_bps: 50,
+                threshold_bps: 50
+            }
+        );
+        assert!(POLICY
+            .decide(Some(&last()), 9_950, 2, 1_010)
+            .should_publish());
+    }
+
+    #[test]
+    fn test_heartbeat_trigger() {
+        assert_eq!(
+            POLICY.decide(Some(&last()), 10_000, 2, 1_299),
+            Decision::Skip {
+                moved_bps: 0,
+                threshold_bps: 50,
+                age: 299,
+                heartbeat: 300
+            }
+        );
+        assert_eq!(
+            POLICY.decide(Some(&last()), 10_000, 2, 1_300),
+            Decision::Heartbeat {
+                age: 300,
+                heartbeat: 300
+            }
+        );
+    }
+
+    #[test]
+    fn test_first_entry_and_decimals_triggers() {
+        assert_eq!(POLICY.decide(None, 10_000, 2, 1_010), Decision::FirstEntry);
+        assert_eq!(
+            POLICY.decide(Some(&last()), 100_000, 3, 1_010),
+            Decision::DecimalsChanged { from: 2, to: 3 }
+        );
+    }
+
+    #[test]
+    fn test_deviation_bps() {
+        assert_eq!(deviation_bps(10_000, 10_100), 100);
+        assert_eq!(deviation_bps(10_000, 9_000), 1_000);
+        assert_eq!(deviation_bps(3, 4), 3_333);
+        assert_eq!(deviation_bps(0, 0), 0);
+        assert_eq!(deviation_bps(0, 1), u64::MAX);
+        assert_eq!(deviation_bps(1, u64::MAX), u64::MAX);
+    }
+}
@@ -0,0 +1,106 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use async_trait::async_trait;
+
+use super::{json_path::JsonPath, PriceSource};
+
+/// A source reading the price of an asset from a local file, re-read on
+/// every fetch so that another process can keep it up to date.
+///
+/// A `.csv` file holds one `PAIR,PRICE` row per asset, blank lines, `#`
+/// comments and a header row being ignored. Any other file is read as JSON
+/// and the price is selected with `json_path`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileSource {
+    pub path: PathBuf,
+    pub pair: String,
+    pub json_path: JsonPath,
+}
+
+impl FileSource {
+    fn is_csv(&self) -> bool {
+        self.path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("csv"))
+    }
+}
+
+#[async_trait]
+impl PriceSource for FileSource {
+    fn name(&self) -> String {
+        format!("file({})", self.path.display())
+    }
+
+    async fn fetch(&self) -> anyhow::Result<f64> {
+        let content = fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read {}", self.path.display()))?;
+        if self.is_csv() {
+            csv_price(&content, &self.pair)
+        } else {
+            let json = serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", self.path.display()))?;
+            self.json_path.select_number(&json)
+        }
+    }
+}
+
+/// Reads the price of `pair` in `PAIR,PRICE` rows.
+fn csv_price(content: &str, pair: &str) -> anyhow::Result<f64> {
+    let row = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once(','))
+        .find(|(row_pair, _)| row_pair.trim().eq_ignore_ascii_case(pair.trim()));
+    let (_, price) = row.ok_or_else(|| anyhow::anyhow!("No row for {pair}"))?;
+    price
+        .trim()
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid price for {pair}: {price}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(extension: &str, content: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "pm_pusher_prices_{}_{}.{extension}",
+            std::process::id(),
+            rand::random::<u64>()
+        ));
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn test_csv_file() {
+        let path = temp_file(
+            "csv",
+            "pair,price\n# comment\n\nBTC/USD, 98000.5\nETH/USD,3500\n",
+        );
+        let source = |pair: &str| FileSource {
+            path: path.clone(),
+            pair: pair.to_string(),
+            json_path: JsonPath::parse("$").unwrap(),
+        };
+        assert_eq!(source("btc/usd").fetch().await.unwrap(), 98000.5);
+        assert_eq!(source("ETH/USD").fetch().await.unwrap(), 3500.0);
+        assert!(source("SOL/USD").fetch().await.is_err());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_json_file() {
+        let path = temp_file("json", r#"{ "BTC/USD": { "price": "98000.5" } }"#);
+        let source = FileSource {
+            path: path.clone(),
+            pair: "BTC/USD".to_string(),
+            json_path: JsonPath::parse("$['BTC/USD'].price").unwrap(),
+        };
+        assert_eq!(source.fetch().await.unwrap(), 98000.5);
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,20 @@
+use async_trait::async_trait;
+
+use super::PriceSource;
+
+/// A source that always returns the same price, for tests and local setups.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FixedSource {
+    pub price: f64,
+}
+
+#[async_trait]
+impl PriceSource for FixedSource {
+    fn name(&self) -> String {
+        format!("fixed({})", self.price)
+    }
+
+    async fn fetch(&self) -> anyhow::Result<f64> {
+        Ok(self.price)
+    }
+}
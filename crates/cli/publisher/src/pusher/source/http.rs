@@ -0,0 +1,78 @@
+use std::collections::BTreeMap;
+
+use anyhow::Context;
+use async_trait::async_trait;
+
+use super::{json_path::JsonPath, PriceSource};
+
+/// A source reading the price of an asset in the JSON response of an HTTP
+/// endpoint, selected with `json_path`.
+#[derive(Debug, Clone)]
+pub struct HttpSource {
+    pub client: reqwest::Client,
+    pub url: String,
+    pub json_path: JsonPath,
+    /// Headers sent with every request, e.g. an API key.
+    pub headers: BTreeMap<String, String>,
+}
+
+#[async_trait]
+impl PriceSource for HttpSource {
+    fn name(&self) -> String {
+        format!("http({})", self.url)
+    }
+
+    async fn fetch(&self) -> anyhow::Result<f64> {
+        let mut request = self.client.get(&self.url);
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("Request to {} failed", self.url))?
+            .error_for_status()
+            .with_context(|| format!("Request to {} failed", self.url))?;
+        let body = response
+            .text()
+            .await
+            .with_context(|| format!("Failed to read the response of {}", self.url))?;
+        let json = serde_json::from_str(&body)
+            .with_context(|| format!("Invalid JSON response from {}", self.url))?;
+        self.json_path.select_number(&json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::mock_server::serve;
+    use super::*;
+
+    fn source(url: String, json_path: &str) -> HttpSource {
+        HttpSource {
+            client: reqwest::Client::new(),
+            url,
+            json_path: JsonPath::parse(json_path).unwrap(),
+            headers: BTreeMap::from([("x-api-key".to_string(), "secret".to_string())]),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_http_source_selects_the_price() {
+        let url = serve(200, r#"{ "bitcoin": { "usd": 98000.5 } }"#).await;
+        let price = source(url, "$.bitcoin.usd").fetch().await.unwrap();
+        assert_eq!(price, 98000.5);
+    }
+
+    #[tokio::test]
+    async fn test_http_source_fails_on_error_status_or_missing_price() {
+        let url = serve(500, r#"{ "bitcoin": { "usd": 98000.5 } }"#).await;
+        assert!(source(url, "$.bitcoin.usd").fetch().await.is_err());
+
+        let url = serve(200, r#"{ "bitcoin": {} }"#).await;
+        assert!(source(url, "$.bitcoin.usd").fetch().await.is_err());
+
+        let url = serve(200, "not json").await;
+        assert!(source(url, "$.bitcoin.usd").fetch().await.is_err());
+    }
+}
@@ -0,0 +1,147 @@
+use serde_json::Value;
+
+/// A step of a [`JsonPath`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+/// The subset of JSONPath needed to point at a price in an API response: a
+/// `$` root followed by `.key`, `['key']` and `[index]` segments, e.g.
+/// `$.bitcoin.usd` or `$.data[0]['price']`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonPath {
+    path: String,
+    segments: Vec<Segment>,
+}
+
+impl JsonPath {
+    /// # Errors
+    ///
+    /// Fails if the path does not start with `$` or has a malformed segment.
+    pub fn parse(path: &str) -> anyhow::Result<Self> {
+        let invalid = |reason: &str| anyhow::anyhow!("Invalid JSONPath {path}: {reason}");
+        let mut rest = path
+            .trim()
+            .strip_prefix('$')
+            .ok_or_else(|| invalid("must start with $"))?;
+
+        let mut segments = Vec::new();
+        while !rest.is_empty() {
+            if let Some(after_dot) = rest.strip_prefix('.') {
+                let end = after_dot.find(['.', '[']).unwrap_or(after_dot.len());
+                let key = &after_dot[..end];
+                if key.is_empty() {
+                    return Err(invalid("empty key"));
+                }
+                segments.push(Segment::Key(key.to_string()));
+                rest = &after_dot[end..];
+            } else if let Some(after_bracket) = rest.strip_prefix('[') {
+                let end = after_bracket
+                    .find(']')
+                    .ok_or_else(|| invalid("unclosed ["))?;
+                let inner = after_bracket[..end].trim();
+                let quoted = inner
+                    .strip_prefix('\'')
+                    .and_then(|inner| inner.strip_suffix('\''))
+                    .or_else(|| {
+                        inner
+                            .strip_prefix('"')
+                            .and_then(|inner| inner.strip_suffix('"'))
+                    });
+                let segment = match quoted {
+                    Some(key) => Segment::Key(key.to_string()),
+                    None => Segment::Index(
+                        inner
+                            .parse()
+                            .map_err(|_| invalid("expected a quoted key or an index"))?,
+                    ),
+                };
+                segments.push(segment);
+                rest = &after_bracket[end + 1..];
+            } else {
+                return Err(invalid("expected . or ["));
+            }
+        }
+
+        Ok(Self {
+            path: path.trim().to_string(),
+            segments,
+        })
+    }
+
+    /// Returns the value the path points at in `json`, if any.
+    pub fn select<'a>(&self, json: &'a Value) -> Option<&'a Value> {
+        self.segments
+            .iter()
+            .try_fold(json, |value, segment| match segment {
+                Segment::Key(key) => value.get(key),
+                Segment::Index(index) => value.get(index),
+            })
+    }
+
+    /// Reads the number the path points at in `json`. Numbers given as
+    /// strings (e.g. `"98000.5"`), as many APIs do, are accepted.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the path points at nothing or at something else than a number.
+    pub fn select_number(&self, json: &Value) -> anyhow::Result<f64> {
+        let value = self
+            .select(json)
+            .ok_or_else(|| anyhow::anyhow!("Nothing found at {}", self.path))?;
+        match value {
+            Value::Number(number) => number.as_f64(),
+            Value::String(number) => number.trim().parse().ok(),
+            _ => None,
+        }
+        .ok_or_else(|| anyhow::anyhow!("Expected a number at {}, found {value}", self.path))
+    }
+}
+
+impl std::fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_select_number() {
+        let json = json!({
+            "bitcoin": { "usd": 98000.5 },
+            "data": [{ "price": "3500.25" }, { "price": 1 }],
+            "BTC/USD": 7,
+        });
+        let select = |path: &str| JsonPath::parse(path).unwrap().select_number(&json);
+        assert_eq!(select("$.bitcoin.usd").unwrap(), 98000.5);
+        assert_eq!(select("$['bitcoin'][\"usd\"]").unwrap(), 98000.5);
+        assert_eq!(select("$.data[0].price").unwrap(), 3500.25);
+        assert_eq!(select("$.data[1]['price']").unwrap(), 1.0);
+        assert_eq!(select("$['BTC/USD']").unwrap(), 7.0);
+        assert!(select("$.bitcoin").is_err());
+        assert!(select("$.data[2].price").is_err());
+        assert!(select("$.ethereum.usd").is_err());
+    }
+
+    #[test]
+    fn test_invalid_paths_are_rejected() {
+        for path in [
+            "",
+            "bitcoin.usd",
+            "$.",
+            "$..usd",
+            "$[0",
+            "$[key]",
+            "$bitcoin",
+        ] {
+            assert!(JsonPath::parse(path).is_err(), "{path} should not parse");
+        }
+    }
+}
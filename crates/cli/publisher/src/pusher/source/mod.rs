@@ -0,0 +1,302 @@
+//! Price sources of the pusher.
+//!
+//! Each asset is priced by one or more [`PriceSource`]s, configured in the
+//! `sources` of the asset and aggregated by [`AssetSources`].
+
+pub mod file;
+pub mod fixed;
+pub mod http;
+pub mod json_path;
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use futures::future::join_all;
+use pm_types::AggregationMode;
+use serde::Deserialize;
+
+use super::config::AssetConfig;
+use file::FileSource;
+use fixed::FixedSource;
+use http::HttpSource;
+use json_path::JsonPath;
+
+/// Where the pusher gets the price of an asset from.
+#[async_trait]
+pub trait PriceSource: Send + Sync {
+    /// Short description of the source, used in logs.
+    fn name(&self) -> String;
+
+    /// Fetches the current price of the asset, as a decimal number.
+    async fn fetch(&self) -> anyhow::Result<f64>;
+}
+
+/// Configuration of a source of an asset, tagged by its `type`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case", deny_unknown_fields)]
+pub enum SourceConfig {
+    /// Always the same price, for tests and local setups.
+    Fixed { price: f64 },
+    /// The number at `json_path` in the JSON response of a GET on `url`.
+    Http {
+        url: String,
+        json_path: String,
+        #[serde(default)]
+        headers: BTreeMap<String, String>,
+    },
+    /// The `PAIR,PRICE` row of the asset in a `.csv` file, or the number at
+    /// `json_path` in a JSON file (by default, the value of the pair key).
+    File {
+        path: PathBuf,
+        json_path: Option<String>,
+    },
+}
+
+impl SourceConfig {
+    /// Builds the source of `pair`, HTTP sources sharing `http_client`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a JSONPath is invalid.
+    pub fn build(
+        &self,
+        pair: &str,
+        http_client: &reqwest::Client,
+    ) -> anyhow::Result<Box<dyn PriceSource>> {
+        Ok(match self {
+            Self::Fixed { price } => Box::new(FixedSource { price: *price }),
+            Self::Http {
+                url,
+                json_path,
+                headers,
+            } => Box::new(HttpSource {
+                client: http_client.clone(),
+                url: url.clone(),
+                json_path: JsonPath::parse(json_path)?,
+                headers: headers.clone(),
+            }),
+            Self::File { path, json_path } => {
+                let json_path = match json_path {
+                    Some(json_path) => JsonPath::parse(json_path)?,
+                    None => JsonPath::parse(&format!("$['{pair}']"))?,
+                };
+                Box::new(FileSource {
+                    path: path.clone(),
+                    pair: pair.to_string(),
+                    json_path,
+                })
+            }
+        })
+    }
+}
+
+/// The sources of an asset, aggregated into the entries given to
+/// `publish_batch`.
+pub struct AssetSources {
+    pub pair: String,
+    pub decimals: u32,
+    pub aggregation: AggregationMode,
+    /// Number of sources that must answer for the asset to be priced.
+    pub min_sources: usize,
+    pub sources: Vec<Box<dyn PriceSource>>,
+}
+
+impl AssetSources {
+    /// # Errors
+    ///
+    /// Fails if a source cannot be built.
+    pub fn from_config(asset: &AssetConfig, http_client: &reqwest::Client) -> anyhow::Result<Self> {
+        let sources = asset
+            .sources
+            .iter()
+            .map(|source| source.build(&asset.pair, http_client))
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self {
+            pair: asset.pair.clone(),
+            decimals: asset.decimals,
+            aggregation: asset.aggregation,
+            min_sources: asset.min_sources,
+            sources,
+        })
+    }
+
+    /// Fetches every source concurrently and aggregates their prices into a
+    /// `(pair, price, decimals, timestamp)` entry, as taken by `publish_batch`.
+    /// Sources that fail are logged and left out.
+    ///
+    /// # Errors
+    ///
+    /// Fails if fewer than `min_sources` sources answered, or if the
+    /// aggregated price cannot be scaled to the decimals of the asset.
+    pub async fn fetch_entry(&self, timestamp: u64) -> anyhow::Result<(String, u64, u32, u64)> {
+        let results = join_all(self.sources.iter().map(|source| source.fetch())).await;
+        let mut prices = Vec::with_capacity(results.len());
+        for (source, result) in self.sources.iter().zip(results) {
+            match result {
+                Ok(price) => prices.push(price),
+                Err(e) => eprintln!("⚠️ {} source {} failed: {e:#}", self.pair, source.name()),
+            }
+        }
+        let required = self.min_sources.max(1);
+        if prices.len() < required {
+            anyhow::bail!(
+                "{} of the {} sources answered, {required} required",
+                prices.len(),
+                self.sources.len(),
+            );
+        }
+
+        let price = scale_price(aggregate(&mut prices, self.aggregation), self.decimals)?;
+        Ok((self.pair.clone(), price, self.decimals, timestamp))
+    }
+}
+
+/// Aggregates a non-empty list of prices. The median of an even number of
+/// prices is the mean of the two middle ones.
+fn aggregate(prices: &mut [f64], mode: AggregationMode) -> f64 {
+    prices.sort_by(f64::total_cmp);
+    let len = prices.len();
+    match mode {
+        AggregationMode::Median if len.is_multiple_of(2) => {
+            (prices[len / 2 - 1] + prices[len / 2]) / 2.0
+        }
+        AggregationMode::Median => prices[len / 2],
+        AggregationMode::Mean => prices.iter().sum::<f64>() / len as f64,
+        AggregationMode::Min => prices[0],
+        AggregationMode::Max => prices[len - 1],
+    }
+}
+
+/// Scales a decimal price to an integer with `decimals` decimals, rounding
+/// to the nearest unit.
+///
+/// # Errors
+///
+/// Fails if the price is negative, not finite or too large once scaled.
+pub fn scale_price(price: f64, decimals: u32) -> anyhow::Result<u64> {
+    let scaled = (price * 10f64.powi(decimals as i32)).round();
+    if !scaled.is_finite() || scaled < 0.0 || scaled >= u64::MAX as f64 {
+        anyhow::bail!("Price {price} cannot be scaled to {decimals} decimals");
+    }
+    Ok(scaled as u64)
+}
+
+/// A local HTTP server answering a single canned response, to test the HTTP
+/// sources without reaching the network.
+#[cfg(test)]
+pub(crate) mod mock_server {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serves `body` with `status` to the first request, and returns the URL
+    /// to request.
+    pub async fn serve(status: u16, body: &str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/price", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {status} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            // The requests are small enough to be read at once.
+            let mut request = [0u8; 4096];
+            let _ = stream.read(&mut request).await.unwrap();
+            stream.write_all(response.as_bytes()).await.unwrap();
+            stream.shutdown().await.unwrap();
+        });
+        url
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mock_server::serve;
+    use super::*;
+
+    fn asset(sources: Vec<SourceConfig>, aggregation: AggregationMode) -> AssetConfig {
+        AssetConfig {
+            pair: "BTC/USD".to_string(),
+            decimals: 2,
+            sources,
+            aggregation,
+            min_sources: 1,
+            deviation_bps: None,
+            heartbeat: None,
+        }
+    }
+
+    fn http(url: String) -> SourceConfig {
+        SourceConfig::Http {
+            url,
+            json_path: "$.price".to_string(),
+            headers: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_scale_price() {
+        assert_eq!(scale_price(98000.5, 8).unwrap(), 9_800_050_000_000);
+        assert_eq!(scale_price(1.000_001, 6).unwrap(), 1_000_001);
+        assert_eq!(scale_price(0.0, 8).unwrap(), 0);
+        assert!(scale_price(-1.0, 8).is_err());
+        assert!(scale_price(f64::NAN, 8).is_err());
+        assert!(scale_price(1e30, 8).is_err());
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let prices = [3.0, 1.0, 4.0, 2.0];
+        for (mode, expected) in [
+            (AggregationMode::Median, 2.5),
+            (AggregationMode::Mean, 2.5),
+            (AggregationMode::Min, 1.0),
+            (AggregationMode::Max, 4.0),
+        ] {
+            assert_eq!(aggregate(&mut prices.clone(), mode), expected, "{mode}");
+        }
+        assert_eq!(
+            aggregate(&mut [3.0, 1.0, 2.0], AggregationMode::Median),
+            2.0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_entry_aggregates_http_and_fixed_sources() {
+        let sources = vec![
+            http(serve(200, r#"{ "price": 100.0 }"#).await),
+            http(serve(200, r#"{ "price": "102.5" }"#).await),
+            SourceConfig::Fixed { price: 90.0 },
+        ];
+        let asset = AssetSources::from_config(
+            &asset(sources, AggregationMode::Median),
+            &reqwest::Client::new(),
+        )
+        .unwrap();
+
+        let entry = asset.fetch_entry(1_700_000_000).await.unwrap();
+        assert_eq!(entry, ("BTC/USD".to_string(), 10_000, 2, 1_700_000_000));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_entry_skips_failed_sources() {
+        let sources = vec![
+            http(serve(503, "{}").await),
+            http(serve(200, r#"{ "price": 100.0 }"#).await),
+        ];
+        let mut config = asset(sources, AggregationMode::Mean);
+        let asset = AssetSources::from_config(&config, &reqwest::Client::new()).unwrap();
+        let (_, price, _, _) = asset.fetch_entry(0).await.unwrap();
+        assert_eq!(price, 10_000);
+
+        // Both sources are required now.
+        config.sources = vec![
+            http(serve(503, "{}").await),
+            http(serve(200, r#"{ "price": 100.0 }"#).await),
+        ];
+        config.min_sources = 2;
+        let asset = AssetSources::from_config(&config, &reqwest::Client::new()).unwrap();
+        assert!(asset.fetch_entry(0).await.is_err());
+    }
+}
@@ -0,0 +1,51 @@
+use anyhow::{Context, Result};
+use pm_types::{AssetInfo, AssetKey, AssetRegistry, Pair};
+use serde_json::Value;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::read_config_file;
+
+/// Returns the builtin asset registry, extended with the `assets` section of
+/// the configuration file.
+///
+/// Each configured asset is keyed by its pair and replaces any builtin asset
+/// with the same pair or asset key:
+///
+/// ```json
+/// "assets": {
+///     "SOL/USD": { "asset_key": "3:0", "decimals": 8, "name": "Solana" }
+/// }
+/// ```
+pub fn get_asset_registry(storage_path: &Path) -> Result<AssetRegistry> {
+    let config = read_config_file(storage_path)?;
+    let mut registry = AssetRegistry::builtin();
+
+    let Some(assets) = config.get("assets") else {
+        return Ok(registry);
+    };
+    let assets = assets
+        .as_object()
+        .ok_or_else(|| anyhow::anyhow!("The assets configuration must be an object"))?;
+    for (pair, asset) in assets {
+        registry
+            .register(parse_asset(pair, asset).with_context(|| format!("Invalid asset {pair}"))?);
+    }
+
+    Ok(registry)
+}
+
+fn parse_asset(pair: &str, asset: &Value) -> Result<AssetInfo> {
+    let asset_key = asset["asset_key"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Missing asset_key"))?;
+    let decimals = asset["decimals"]
+        .as_u64()
+        .ok_or_else(|| anyhow::anyhow!("Missing decimals"))?;
+    Ok(AssetInfo {
+        pair: Pair::from_str(pair)?,
+        asset_key: AssetKey::from_str(asset_key)?,
+        decimals: u32::try_from(decimals).context("Decimals do not fit in a u32")?,
+        name: asset["name"].as_str().unwrap_or_default().to_string(),
+    })
+}
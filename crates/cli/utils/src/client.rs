@@ -1,13 +1,14 @@
-use crate::STORE_FILENAME;
+use crate::keystore::{open_keystore, PragmaKeyStore, WatchOnlyKeyStore};
+use crate::{resolve_network_profile, DEFAULT_RPC_TIMEOUT_MS, STORE_FILENAME};
 use miden_client::grpc_support::{DEVNET_PROVER_ENDPOINT, TESTNET_PROVER_ENDPOINT};
 use miden_client::{
     account::{
         component::{AuthScheme, AuthSingleSig, BasicWallet},
         Account, AccountBuilder, AccountType,
     },
-    builder::ClientBuilder,
+    builder::{BuilderAuthenticator, ClientBuilder},
     crypto::{rpo_falcon512::SecretKey, RandomCoin},
-    keystore::{FilesystemKeyStore, Keystore},
+    keystore::Keystore,
     rpc::{Endpoint, GrpcClient},
     Client, ClientError, Felt, RemoteTransactionProver, Word,
 };
@@ -18,12 +19,6 @@ use std::{fs, path::PathBuf, sync::Arc};
 // Client Setup
 // ================================================================================================
 
-// Bounds every node RPC call, including SubmitProvenTx. 10s was too tight for
-// the heavier first submit after a cold start (account deploy) — observed real
-// >10s submits on testnet. 60s gives headroom and is itself bounded by the
-// SDK-side 180s publish_batch timeout.
-const RPC_TIMEOUT_MS: u64 = 60_000;
-
 /// Debug mode is off by default (it makes the assembler try to load MASM
 /// sources for richer traces — which logs `failed to load MASM sources` in
 /// the deployed wheel and adds execution overhead). Set `PM_MIDEN_DEBUG=1`
@@ -35,20 +30,31 @@ fn debug_mode() -> miden_client::DebugMode {
     }
 }
 
-/// Build a Miden client for the given network endpoint.
+/// Build a Miden client for the given network endpoint, bounding every RPC
+/// call to `timeout_ms`.
 ///
 /// When `prover_endpoint` is `Some`, transaction proving is delegated to
 /// Miden's hosted remote prover. Proving a `publish_batch` tx in-process
 /// (the default `LocalTransactionProver`) takes 60-120s and >4Gi RSS on the
 /// publisher pod, so testnet/devnet offload it; `local` keeps local proving
 /// since a local node has no hosted prover.
-async fn setup_client(
+///
+/// Transactions are signed by `keystore`, any backend implementing
+/// [`Keystore`], such as [`PragmaKeyStore`] or one of the backends it wraps
+/// (see [`crate::keystore`]). Watch-only clients sign with a
+/// [`WatchOnlyKeyStore`], on which transactions that need a signature fail
+/// with the summary to sign (see [`crate::offline`]).
+pub async fn setup_client<K>(
     endpoint: Endpoint,
     prover_endpoint: Option<&str>,
+    timeout_ms: u64,
     path: Option<PathBuf>,
-    keystore_path: Option<String>,
-) -> Result<Client<FilesystemKeyStore>, ClientError> {
-    let rpc_api = Arc::new(GrpcClient::new(&endpoint, RPC_TIMEOUT_MS));
+    keystore: Option<Arc<K>>,
+) -> Result<Client<K>, ClientError>
+where
+    K: BuilderAuthenticator,
+{
+    let rpc_api = Arc::new(GrpcClient::new(&endpoint, timeout_ms));
 
     let coin_seed: [u64; 4] = rand::random();
     // 0.15: Felt::new is fallible (rejects value >= field modulus). Shift right by
@@ -58,10 +64,6 @@ async fn setup_client(
     ));
 
     let path = path.unwrap_or_else(|| PathBuf::new().join(STORE_FILENAME));
-    let keystore_path_str = keystore_path.unwrap_or_else(default_keystore_path);
-    let keystore = FilesystemKeyStore::new(keystore_path_str.into())
-        .unwrap()
-        .into();
 
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).ok();
@@ -70,13 +72,16 @@ async fn setup_client(
         .await
         .map_err(ClientError::StoreError)?;
 
-    let mut builder = ClientBuilder::new()
-        .authenticator(keystore)
+    let mut builder = ClientBuilder::<K>::new()
         .rpc(rpc_api)
         .rng(rng)
         .store(Arc::new(store))
         .in_debug_mode(debug_mode());
 
+    if let Some(keystore) = keystore {
+        builder = builder.authenticator(keystore);
+    }
+
     if let Some(prover_url) = prover_endpoint {
         builder = builder.prover(Arc::new(RemoteTransactionProver::new(
             prover_url.to_string(),
@@ -88,7 +93,7 @@ async fn setup_client(
 
 /// Resolve the keystore directory by walking up to the project root
 /// (first ancestor containing a `Cargo.toml`), falling back to `./keystore`.
-fn default_keystore_path() -> String {
+pub(crate) fn default_keystore_path() -> String {
     let mut current_dir = std::env::current_dir().expect("Failed to get current directory");
     loop {
         if current_dir.join("Cargo.toml").exists() {
@@ -101,22 +106,38 @@ fn default_keystore_path() -> String {
     }
 }
 
+/// Error of a built-in network client whose keystore cannot be opened, e.g.
+/// for a missing or wrong passphrase or an unknown `PM_KEYSTORE` backend.
+fn keystore_error(error: anyhow::Error) -> ClientError {
+    ClientError::ClientInitializationError(format!("{error:#}"))
+}
+
 pub async fn setup_local_client(
     path: Option<PathBuf>,
     keystore_path: Option<String>,
-) -> Result<Client<FilesystemKeyStore>, ClientError> {
-    setup_client(Endpoint::localhost(), None, path, keystore_path).await
+) -> Result<Client<PragmaKeyStore>, ClientError> {
+    let keystore = Arc::new(open_keystore(keystore_path).map_err(keystore_error)?);
+    setup_client(
+        Endpoint::localhost(),
+        None,
+        DEFAULT_RPC_TIMEOUT_MS,
+        path,
+        Some(keystore),
+    )
+    .await
 }
 
 pub async fn setup_devnet_client(
     path: Option<PathBuf>,
     keystore_path: Option<String>,
-) -> Result<Client<FilesystemKeyStore>, ClientError> {
+) -> Result<Client<PragmaKeyStore>, ClientError> {
+    let keystore = Arc::new(open_keystore(keystore_path).map_err(keystore_error)?);
     setup_client(
         Endpoint::devnet(),
         Some(DEVNET_PROVER_ENDPOINT),
+        DEFAULT_RPC_TIMEOUT_MS,
         path,
-        keystore_path,
+        Some(keystore),
     )
     .await
 }
@@ -124,16 +145,77 @@ pub async fn setup_devnet_client(
 pub async fn setup_testnet_client(
     storage_path: Option<PathBuf>,
     keystore_path: Option<String>,
-) -> Result<Client<FilesystemKeyStore>, ClientError> {
+) -> Result<Client<PragmaKeyStore>, ClientError> {
+    let keystore = Arc::new(open_keystore(keystore_path).map_err(keystore_error)?);
     setup_client(
         Endpoint::testnet(),
         Some(TESTNET_PROVER_ENDPOINT),
+        DEFAULT_RPC_TIMEOUT_MS,
         storage_path,
-        keystore_path,
+        Some(keystore),
     )
     .await
 }
 
+/// Build a client for `network`, a built-in network (local, devnet or
+/// testnet) or a profile of the configuration file (see
+/// [`crate::NetworkProfile`]).
+///
+/// The client store is `path`, or else the store of the profile, or else the
+/// default store.
+///
+/// # Errors
+///
+/// Fails if the network is unknown, if the keystore cannot be opened or if
+/// the client cannot be built.
+pub async fn setup_network_client(
+    network: &str,
+    path: Option<PathBuf>,
+    keystore_path: Option<String>,
+) -> anyhow::Result<Client<PragmaKeyStore>> {
+    let keystore = Arc::new(open_keystore(keystore_path)?);
+    setup_profile_client(network, path, Some(keystore)).await
+}
+
+/// Build a client holding no keys for `network`, used to export transactions
+/// that are signed on another host: its [`WatchOnlyKeyStore`] makes the
+/// transactions stop at their signature.
+pub async fn setup_watch_only_client(
+    network: &str,
+    path: Option<PathBuf>,
+) -> anyhow::Result<Client<PragmaKeyStore>> {
+    let keystore = Arc::new(PragmaKeyStore::WatchOnly(WatchOnlyKeyStore));
+    setup_profile_client(network, path, Some(keystore)).await
+}
+
+/// Build a client for `network` signing with `keystore`, for callers that
+/// bring their own keystore backend instead of the one selected by
+/// `PM_KEYSTORE` (see [`setup_network_client`]).
+///
+/// # Errors
+///
+/// Fails if the network is unknown or if the client cannot be built.
+pub async fn setup_profile_client<K>(
+    network: &str,
+    path: Option<PathBuf>,
+    keystore: Option<Arc<K>>,
+) -> anyhow::Result<Client<K>>
+where
+    K: BuilderAuthenticator,
+{
+    let profile = resolve_network_profile(network)?;
+    let path = path.or_else(|| profile.store_path.clone());
+    let client = setup_client(
+        profile.endpoint()?,
+        profile.prover_url.as_deref(),
+        profile.timeout_ms,
+        path,
+        keystore,
+    )
+    .await?;
+    Ok(client)
+}
+
 pub async fn create_wallet<K>(
     client: &mut Client<K>,
     account_type: AccountType,
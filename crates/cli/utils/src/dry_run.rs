@@ -0,0 +1,82 @@
+use miden_client::{
+    account::AccountId, keystore::Keystore, transaction::TransactionRequest, Client, Word,
+};
+
+use crate::Done;
+
+/// Executes `transaction_request` against the local state of `account_id`
+/// without proving nor submitting it, and prints the storage changes it would
+/// make on stderr.
+///
+/// The local state is left untouched, so the same request can be submitted
+/// afterwards.
+///
+/// # Errors
+///
+/// Fails if the account is not tracked by the client or if the transaction
+/// fails to execute.
+pub async fn dry_run_transaction<K>(
+    client: &mut Client<K>,
+    account_id: AccountId,
+    transaction_request: TransactionRequest,
+) -> anyhow::Result<Done>
+where
+    K: Keystore + Sync + 'static,
+{
+    let account = client
+        .get_account(account_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Account {account_id} not found"))?;
+    let storage = account.storage();
+
+    let transaction_result = client
+        .execute_transaction(account_id, transaction_request)
+        .await
+        .map_err(|e| anyhow::anyhow!("Error while executing transaction: {e:?}"))?;
+    let storage_delta = transaction_result.account_delta().storage();
+
+    eprintln!("🧪 Storage changes of the transaction against {account_id}:");
+
+    let mut nb_of_changes = 0;
+    for (slot_name, new_value) in storage_delta.values() {
+        let old_value = storage.get_item(slot_name).unwrap_or_default();
+        eprintln!("  {slot_name}");
+        eprintln!(
+            "    {} -> {}",
+            format_word(old_value),
+            format_word(*new_value)
+        );
+        nb_of_changes += 1;
+    }
+    for (slot_name, map_delta) in storage_delta.maps() {
+        eprintln!("  {slot_name}");
+        for (key, new_value) in map_delta.entries() {
+            let key = Word::from(*key);
+            let old_value = storage.get_map_item(slot_name, key).unwrap_or_default();
+            eprintln!(
+                "    [{}] {} -> {}",
+                format_word(key),
+                format_word(old_value),
+                format_word(*new_value)
+            );
+            nb_of_changes += 1;
+        }
+    }
+
+    if nb_of_changes == 0 {
+        eprintln!("  No storage change");
+    }
+
+    Ok(Done::new(format!(
+        "Dry run of a transaction against {account_id}, nothing was submitted ({nb_of_changes} storage changes)"
+    )))
+}
+
+/// Formats a word as its felts in storage order, e.g. `[1, 0, 0, 0]`.
+fn format_word(word: Word) -> String {
+    let felts: Vec<String> = word
+        .iter()
+        .map(|felt| felt.as_canonical_u64().to_string())
+        .collect();
+    format!("[{}]", felts.join(", "))
+}
@@ -0,0 +1,332 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use miden_client::{
+    account::AccountId,
+    auth::{AuthSecretKey, PublicKey, PublicKeyCommitment, Signature, SigningInputs},
+    keystore::{KeyStoreError, Keystore},
+    Word,
+};
+use miden_protocol::utils::serde::{Deserializable, Serializable, SliceReader};
+use miden_tx::auth::TransactionAuthenticator;
+use miden_tx::AuthenticationError;
+
+const SALT_FILENAME: &str = "keystore.salt";
+const CHECK_FILENAME: &str = "keystore.check";
+const KEY_EXTENSION: &str = "enc";
+/// Encrypted in the check file, to tell a wrong passphrase at open.
+const CHECK_PLAINTEXT: &[u8] = b"pragma-miden keystore";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+/// Keystore writing each key in its own file, encrypted with XChaCha20-Poly1305
+/// under a key derived from a passphrase with Argon2id.
+///
+/// A key file `<commitment>.enc` holds a random nonce followed by the
+/// encrypted account id and secret key. The salt of the derivation is stored
+/// next to the keys, in `keystore.salt`.
+#[derive(Clone)]
+pub struct EncryptedKeyStore {
+    keys_directory: PathBuf,
+    cipher: Arc<XChaCha20Poly1305>,
+}
+
+impl fmt::Debug for EncryptedKeyStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EncryptedKeyStore")
+            .field("keys_directory", &self.keys_directory)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EncryptedKeyStore {
+    /// Opens the keystore in `keys_directory` with `passphrase`, creating it
+    /// if the directory holds no keystore yet.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the directory cannot be created or read, or if `passphrase`
+    /// is not the passphrase of the keystore.
+    pub fn open(keys_directory: PathBuf, passphrase: &str) -> anyhow::Result<Self> {
+        fs::create_dir_all(&keys_directory)
+            .map_err(|e| anyhow::anyhow!("Failed to create the keystore directory: {e:?}"))?;
+
+        let salt_path = keys_directory.join(SALT_FILENAME);
+        let salt = if salt_path.exists() {
+            fs::read(&salt_path)
+                .map_err(|e| anyhow::anyhow!("Failed to read the keystore salt: {e:?}"))?
+        } else {
+            let salt = rand::random::<[u8; SALT_LEN]>().to_vec();
+            fs::write(&salt_path, &salt)
+                .map_err(|e| anyhow::anyhow!("Failed to write the keystore salt: {e:?}"))?;
+            salt
+        };
+
+        let mut key = [0u8; 32];
+        Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+            .map_err(|e| anyhow::anyhow!("Failed to derive the keystore key: {e:?}"))?;
+        let keystore = Self {
+            keys_directory,
+            cipher: Arc::new(XChaCha20Poly1305::new(&key.into())),
+        };
+
+        let check_path = keystore.keys_directory.join(CHECK_FILENAME);
+        if check_path.exists() {
+            let check = fs::read(&check_path)
+                .map_err(|e| anyhow::anyhow!("Failed to read the keystore check: {e:?}"))?;
+            if keystore.decrypt(&check).ok().as_deref() != Some(CHECK_PLAINTEXT) {
+                anyhow::bail!("Wrong passphrase for the keystore");
+            }
+        } else {
+            let check = keystore
+                .encrypt(CHECK_PLAINTEXT)
+                .map_err(|e| anyhow::anyhow!("{e:?}"))?;
+            fs::write(&check_path, check)
+                .map_err(|e| anyhow::anyhow!("Failed to write the keystore check: {e:?}"))?;
+        }
+
+        Ok(keystore)
+    }
+
+    fn key_path(&self, pub_key_commitment: PublicKeyCommitment) -> PathBuf {
+        let commitment: Word = pub_key_commitment.into();
+        self.keys_directory
+            .join(format!("{}.{KEY_EXTENSION}", commitment.to_hex()))
+    }
+
+    fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, KeyStoreError> {
+        let nonce = rand::random::<[u8; NONCE_LEN]>();
+        let ciphertext = self
+            .cipher
+            .encrypt(XNonce::from_slice(&nonce), plaintext)
+            .map_err(|e| KeyStoreError::StorageError(format!("Failed to encrypt: {e:?}")))?;
+        Ok([nonce.as_slice(), &ciphertext].concat())
+    }
+
+    fn decrypt(&self, content: &[u8]) -> Result<Vec<u8>, KeyStoreError> {
+        if content.len() < NONCE_LEN {
+            return Err(KeyStoreError::DecodingError(
+                "Encrypted content is too short".to_string(),
+            ));
+        }
+        let (nonce, ciphertext) = content.split_at(NONCE_LEN);
+        self.cipher
+            .decrypt(XNonce::from_slice(nonce), ciphertext)
+            .map_err(|_| {
+                KeyStoreError::DecodingError(
+                    "Failed to decrypt, the passphrase may be wrong".to_string(),
+                )
+            })
+    }
+
+    fn read_key_file(&self, path: &Path) -> Result<(AccountId, AuthSecretKey), KeyStoreError> {
+        let content = fs::read(path).map_err(|e| {
+            KeyStoreError::StorageError(format!("Failed to read {}: {e:?}", path.display()))
+        })?;
+        let plaintext = self.decrypt(&content)?;
+        let mut reader = SliceReader::new(&plaintext);
+        AccountId::read_from(&mut reader)
+            .and_then(|account_id| Ok((account_id, AuthSecretKey::read_from(&mut reader)?)))
+            .map_err(|e| {
+                KeyStoreError::DecodingError(format!("Failed to parse {}: {e:?}", path.display()))
+            })
+    }
+
+    /// Every key file of the keystore.
+    fn key_files(&self) -> Result<Vec<PathBuf>, KeyStoreError> {
+        let entries = fs::read_dir(&self.keys_directory).map_err(|e| {
+            KeyStoreError::StorageError(format!("Failed to read the keystore: {e:?}"))
+        })?;
+        Ok(entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == KEY_EXTENSION))
+            .collect())
+    }
+
+    fn read_key(
+        &self,
+        pub_key_commitment: PublicKeyCommitment,
+    ) -> Result<Option<(AccountId, AuthSecretKey)>, KeyStoreError> {
+        let path = self.key_path(pub_key_commitment);
+        if !path.exists() {
+            return Ok(None);
+        }
+        self.read_key_file(&path).map(Some)
+    }
+}
+
+impl TransactionAuthenticator for EncryptedKeyStore {
+    async fn get_signature(
+        &self,
+        pub_key_commitment: PublicKeyCommitment,
+        signing_inputs: &SigningInputs,
+    ) -> Result<Signature, AuthenticationError> {
+        let (_, key) = self
+            .read_key(pub_key_commitment)
+            .map_err(|e| AuthenticationError::other(format!("{e:?}")))?
+            .ok_or(AuthenticationError::UnknownPublicKey(pub_key_commitment))?;
+        Ok(key.sign(signing_inputs.to_commitment()))
+    }
+
+    async fn get_public_key(
+        &self,
+        pub_key_commitment: PublicKeyCommitment,
+    ) -> Option<Arc<PublicKey>> {
+        let (_, key) = self.read_key(pub_key_commitment).ok()??;
+        Some(Arc::new(key.public_key()))
+    }
+}
+
+#[async_trait::async_trait]
+impl Keystore for EncryptedKeyStore {
+    async fn add_key(
+        &self,
+        key: &AuthSecretKey,
+        account_id: AccountId,
+    ) -> Result<(), KeyStoreError> {
+        let path = self.key_path(key.public_key().to_commitment());
+        let mut plaintext = account_id.to_bytes();
+        key.write_into(&mut plaintext);
+        fs::write(&path, self.encrypt(&plaintext)?).map_err(|e| {
+            KeyStoreError::StorageError(format!("Failed to write {}: {e:?}", path.display()))
+        })
+    }
+
+    async fn remove_key(
+        &self,
+        pub_key_commitment: PublicKeyCommitment,
+    ) -> Result<(), KeyStoreError> {
+        let path = self.key_path(pub_key_commitment);
+        if !path.exists() {
+            return Ok(());
+        }
+        fs::remove_file(&path).map_err(|e| {
+            KeyStoreError::StorageError(format!("Failed to remove {}: {e:?}", path.display()))
+        })
+    }
+
+    async fn get_key(
+        &self,
+        pub_key_commitment: PublicKeyCommitment,
+    ) -> Result<Option<AuthSecretKey>, KeyStoreError> {
+        Ok(self.read_key(pub_key_commitment)?.map(|(_, key)| key))
+    }
+
+    async fn get_account_id_by_key_commitment(
+        &self,
+        pub_key_commitment: PublicKeyCommitment,
+    ) -> Result<Option<AccountId>, KeyStoreError> {
+        Ok(self
+            .read_key(pub_key_commitment)?
+            .map(|(account_id, _)| account_id))
+    }
+
+    async fn get_account_key_commitments(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<BTreeSet<PublicKeyCommitment>, KeyStoreError> {
+        let mut pub_key_commitments = BTreeSet::new();
+        for path in self.key_files()? {
+            let (key_account_id, key) = self.read_key_file(&path)?;
+            if key_account_id == *account_id {
+                pub_key_commitments.insert(key.public_key().to_commitment());
+            }
+        }
+        Ok(pub_key_commitments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miden_client::account::{
+        component::{AuthScheme, AuthSingleSig, BasicWallet},
+        AccountBuilder,
+    };
+    use miden_client::crypto::rpo_falcon512::SecretKey;
+
+    use super::*;
+
+    fn account_id(secret_key: &SecretKey) -> AccountId {
+        AccountBuilder::new([1; 32])
+            .with_auth_component(AuthSingleSig::new(
+                secret_key.public_key().to_commitment().into(),
+                AuthScheme::Falcon512Poseidon2,
+            ))
+            .with_component(BasicWallet)
+            .build()
+            .unwrap()
+            .id()
+    }
+
+    #[tokio::test]
+    async fn test_keys_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let secret_key = SecretKey::new();
+        let account_id = account_id(&secret_key);
+        let key = AuthSecretKey::Falcon512Poseidon2(secret_key);
+        let pub_key_commitment = key.public_key().to_commitment();
+
+        let keystore = EncryptedKeyStore::open(dir.path().to_path_buf(), "passphrase").unwrap();
+        keystore.add_key(&key, account_id).await.unwrap();
+
+        // Reopened, as another run of the CLI would.
+        let keystore = EncryptedKeyStore::open(dir.path().to_path_buf(), "passphrase").unwrap();
+        assert_eq!(
+            keystore.get_key(pub_key_commitment).await.unwrap(),
+            Some(key)
+        );
+        assert_eq!(
+            keystore
+                .get_account_id_by_key_commitment(pub_key_commitment)
+                .await
+                .unwrap(),
+            Some(account_id)
+        );
+        assert_eq!(
+            keystore
+                .get_account_key_commitments(&account_id)
+                .await
+                .unwrap(),
+            BTreeSet::from([pub_key_commitment])
+        );
+
+        keystore.remove_key(pub_key_commitment).await.unwrap();
+        assert_eq!(keystore.get_key(pub_key_commitment).await.unwrap(), None);
+    }
+
+    #[test]
+    fn test_wrong_passphrase_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        EncryptedKeyStore::open(dir.path().to_path_buf(), "passphrase").unwrap();
+        assert!(EncryptedKeyStore::open(dir.path().to_path_buf(), "another passphrase").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_keys_are_not_written_in_the_clear() {
+        let dir = tempfile::tempdir().unwrap();
+        let secret_key = SecretKey::new();
+        let account_id = account_id(&secret_key);
+        let key = AuthSecretKey::Falcon512Poseidon2(secret_key);
+        let key_bytes = key.to_bytes();
+
+        let keystore = EncryptedKeyStore::open(dir.path().to_path_buf(), "passphrase").unwrap();
+        keystore.add_key(&key, account_id).await.unwrap();
+
+        for entry in fs::read_dir(dir.path()).unwrap() {
+            let content = fs::read(entry.unwrap().path()).unwrap();
+            assert!(
+                !content
+                    .windows(key_bytes.len())
+                    .any(|window| window == key_bytes),
+                "a file of the keystore holds the secret key in the clear"
+            );
+        }
+    }
+}
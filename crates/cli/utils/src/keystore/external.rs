@@ -0,0 +1,294 @@
+use std::collections::BTreeSet;
+use std::process::Stdio;
+use std::sync::Arc;
+
+use miden_client::{
+    account::AccountId,
+    auth::{AuthSecretKey, PublicKey, PublicKeyCommitment, Signature, SigningInputs},
+    keystore::{KeyStoreError, Keystore},
+    Word,
+};
+use miden_protocol::utils::serde::{Deserializable, Serializable};
+use miden_tx::auth::TransactionAuthenticator;
+use miden_tx::AuthenticationError;
+use serde_json::{json, Value};
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// Signer holding the keys in another process, e.g. a wrapper around an HSM
+/// or a signing service, so that they never reach the host of the CLI.
+///
+/// The signer command is run once per request. It reads one JSON request on
+/// stdin and writes one JSON response on stdout, or `{"error": "..."}`.
+/// Words and serialized values are hex strings:
+///
+/// | `method`                      | request fields                              | response              |
+/// |-------------------------------|---------------------------------------------|-----------------------|
+/// | `get_signature`               | `pub_key_commitment`, `signing_inputs`      | `signature`           |
+/// | `get_public_key`              | `pub_key_commitment`                        | `public_key` or null  |
+/// | `get_account_id`              | `pub_key_commitment`                        | `account_id` or null  |
+/// | `get_account_key_commitments` | `account_id`                                | `pub_key_commitments` |
+///
+/// Keys are created and removed on the signer side: adding or removing a key
+/// through the CLI fails.
+#[derive(Debug, Clone)]
+pub struct ExternalSigner {
+    program: String,
+    args: Arc<Vec<String>>,
+}
+
+impl ExternalSigner {
+    /// Signer run with `command`, a program followed by its arguments
+    /// separated by whitespace.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `command` is empty.
+    pub fn from_command(command: &str) -> anyhow::Result<Self> {
+        let mut parts = command.split_whitespace().map(str::to_string);
+        let program = parts
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("The signer command is empty"))?;
+        Ok(Self {
+            program,
+            args: Arc::new(parts.collect()),
+        })
+    }
+
+    async fn request(&self, request: Value) -> Result<Value, String> {
+        let mut child = Command::new(&self.program)
+            .args(self.args.iter())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| format!("Failed to run the signer {}: {e:?}", self.program))?;
+
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| "Failed to open the stdin of the signer".to_string())?;
+        stdin
+            .write_all(format!("{request}\n").as_bytes())
+            .await
+            .map_err(|e| format!("Failed to write to the signer: {e:?}"))?;
+        drop(stdin);
+
+        let output = child
+            .wait_with_output()
+            .await
+            .map_err(|e| format!("Failed to wait for the signer: {e:?}"))?;
+        if !output.status.success() {
+            return Err(format!("The signer exited with {}", output.status));
+        }
+        let response: Value = serde_json::from_slice(&output.stdout)
+            .map_err(|e| format!("Invalid response from the signer: {e:?}"))?;
+        if let Some(error) = response.get("error") {
+            return Err(format!("The signer failed: {error}"));
+        }
+        Ok(response)
+    }
+
+    /// Field `name` of `response`, `None` when absent or null.
+    fn field<'a>(response: &'a Value, name: &str) -> Result<Option<&'a str>, String> {
+        match response.get(name) {
+            None | Some(Value::Null) => Ok(None),
+            Some(Value::String(value)) => Ok(Some(value)),
+            Some(other) => Err(format!("Invalid {name} from the signer: {other}")),
+        }
+    }
+
+    async fn account_key_commitments(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<BTreeSet<PublicKeyCommitment>, String> {
+        let response = self
+            .request(json!({
+                "method": "get_account_key_commitments",
+                "account_id": account_id.to_hex(),
+            }))
+            .await?;
+        let commitments = response
+            .get("pub_key_commitments")
+            .and_then(Value::as_array)
+            .ok_or_else(|| "Missing pub_key_commitments from the signer".to_string())?;
+        commitments
+            .iter()
+            .map(|commitment| {
+                let commitment = commitment.as_str().ok_or_else(|| {
+                    format!("Invalid key commitment from the signer: {commitment}")
+                })?;
+                parse_commitment(commitment)
+            })
+            .collect()
+    }
+
+    async fn account_id(
+        &self,
+        pub_key_commitment: PublicKeyCommitment,
+    ) -> Result<Option<AccountId>, String> {
+        let response = self
+            .request(json!({
+                "method": "get_account_id",
+                "pub_key_commitment": commitment_to_hex(pub_key_commitment),
+            }))
+            .await?;
+        Self::field(&response, "account_id")?
+            .map(|id| {
+                AccountId::from_hex(id)
+                    .map_err(|e| format!("Invalid account_id from the signer: {e:?}"))
+            })
+            .transpose()
+    }
+
+    async fn public_key(
+        &self,
+        pub_key_commitment: PublicKeyCommitment,
+    ) -> Result<Option<PublicKey>, String> {
+        let response = self
+            .request(json!({
+                "method": "get_public_key",
+                "pub_key_commitment": commitment_to_hex(pub_key_commitment),
+            }))
+            .await?;
+        Self::field(&response, "public_key")?
+            .map(|public_key| deserialize_hex(public_key, "public_key"))
+            .transpose()
+    }
+}
+
+impl TransactionAuthenticator for ExternalSigner {
+    async fn get_signature(
+        &self,
+        pub_key_commitment: PublicKeyCommitment,
+        signing_inputs: &SigningInputs,
+    ) -> Result<Signature, AuthenticationError> {
+        let response = self
+            .request(json!({
+                "method": "get_signature",
+                "pub_key_commitment": commitment_to_hex(pub_key_commitment),
+                "signing_inputs": bytes_to_hex(&signing_inputs.to_bytes()),
+            }))
+            .await
+            .map_err(AuthenticationError::other)?;
+        let signature = Self::field(&response, "signature")
+            .map_err(AuthenticationError::other)?
+            .ok_or(AuthenticationError::UnknownPublicKey(pub_key_commitment))?;
+        deserialize_hex(signature, "signature").map_err(AuthenticationError::other)
+    }
+
+    async fn get_public_key(
+        &self,
+        pub_key_commitment: PublicKeyCommitment,
+    ) -> Option<Arc<PublicKey>> {
+        self.public_key(pub_key_commitment)
+            .await
+            .ok()
+            .flatten()
+            .map(Arc::new)
+    }
+}
+
+#[async_trait::async_trait]
+impl Keystore for ExternalSigner {
+    async fn add_key(
+        &self,
+        _key: &AuthSecretKey,
+        _account_id: AccountId,
+    ) -> Result<(), KeyStoreError> {
+        Err(KeyStoreError::StorageError(
+            "Keys are managed by the external signer".to_string(),
+        ))
+    }
+
+    async fn remove_key(
+        &self,
+        _pub_key_commitment: PublicKeyCommitment,
+    ) -> Result<(), KeyStoreError> {
+        Err(KeyStoreError::StorageError(
+            "Keys are managed by the external signer".to_string(),
+        ))
+    }
+
+    /// Secret keys never leave the signer.
+    async fn get_key(
+        &self,
+        _pub_key_commitment: PublicKeyCommitment,
+    ) -> Result<Option<AuthSecretKey>, KeyStoreError> {
+        Ok(None)
+    }
+
+    async fn get_account_id_by_key_commitment(
+        &self,
+        pub_key_commitment: PublicKeyCommitment,
+    ) -> Result<Option<AccountId>, KeyStoreError> {
+        self.account_id(pub_key_commitment)
+            .await
+            .map_err(KeyStoreError::StorageError)
+    }
+
+    async fn get_account_key_commitments(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<BTreeSet<PublicKeyCommitment>, KeyStoreError> {
+        self.account_key_commitments(account_id)
+            .await
+            .map_err(KeyStoreError::StorageError)
+    }
+}
+
+fn commitment_to_hex(pub_key_commitment: PublicKeyCommitment) -> String {
+    let commitment: Word = pub_key_commitment.into();
+    commitment.to_hex()
+}
+
+fn parse_commitment(hex: &str) -> Result<PublicKeyCommitment, String> {
+    Word::try_from(hex)
+        .map(PublicKeyCommitment::from)
+        .map_err(|e| format!("Invalid key commitment {hex} from the signer: {e:?}"))
+}
+
+fn bytes_to_hex(bytes: &[u8]) -> String {
+    let digits: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+    format!("0x{digits}")
+}
+
+fn deserialize_hex<T: Deserializable>(hex: &str, what: &str) -> Result<T, String> {
+    let digits = hex.trim_start_matches("0x");
+    if !digits.len().is_multiple_of(2) {
+        return Err(format!(
+            "Invalid {what} from the signer: odd number of digits"
+        ));
+    }
+    let bytes = (0..digits.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&digits[i..i + 2], 16))
+        .collect::<Result<Vec<u8>, _>>()
+        .map_err(|e| format!("Invalid {what} from the signer: {e:?}"))?;
+    T::read_from_bytes(&bytes).map_err(|e| format!("Invalid {what} from the signer: {e:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use miden_client::Felt;
+
+    use super::*;
+
+    #[test]
+    fn test_hex_round_trip() {
+        let word: Word = [1, 2, 3, 4].map(|x| Felt::new(x).unwrap()).into();
+        let decoded: Word = deserialize_hex(&bytes_to_hex(&word.to_bytes()), "word").unwrap();
+        assert_eq!(decoded, word);
+        assert!(deserialize_hex::<Word>("0x123", "word").is_err());
+        assert!(deserialize_hex::<Word>("0xzz", "word").is_err());
+    }
+
+    #[test]
+    fn test_empty_command_is_rejected() {
+        assert!(ExternalSigner::from_command("  ").is_err());
+        let signer = ExternalSigner::from_command("pm-signer --socket /run/signer.sock").unwrap();
+        assert_eq!(signer.program, "pm-signer");
+        assert_eq!(*signer.args, ["--socket", "/run/signer.sock"]);
+    }
+}
@@ -0,0 +1,211 @@
+//! Key storage backends of the CLIs and of the pyo3 binding.
+//!
+//! The backend is chosen with the `PM_KEYSTORE` environment variable:
+//! - `filesystem` (default): the plaintext keys of [`FilesystemKeyStore`]
+//! - `encrypted`: keys encrypted at rest with a key derived from
+//!   `PM_KEYSTORE_PASSPHRASE` (see [`EncryptedKeyStore`])
+//! - `external`: keys held by the signer run with `PM_SIGNER_COMMAND`, which
+//!   never hands them out (see [`ExternalSigner`])
+
+pub mod encrypted;
+pub mod external;
+pub mod watch_only;
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use miden_client::{
+    account::AccountId,
+    auth::{AuthSecretKey, PublicKey, PublicKeyCommitment, Signature, SigningInputs},
+    keystore::{FilesystemKeyStore, KeyStoreError, Keystore},
+};
+use miden_tx::auth::TransactionAuthenticator;
+use miden_tx::AuthenticationError;
+
+pub use encrypted::EncryptedKeyStore;
+pub use external::ExternalSigner;
+pub use watch_only::{SignatureRequested, WatchOnlyKeyStore};
+
+use crate::client::default_keystore_path;
+
+pub const KEYSTORE_ENV: &str = "PM_KEYSTORE";
+pub const KEYSTORE_PASSPHRASE_ENV: &str = "PM_KEYSTORE_PASSPHRASE";
+pub const SIGNER_COMMAND_ENV: &str = "PM_SIGNER_COMMAND";
+
+/// The keystore backend the clients are built with.
+#[derive(Debug, Clone)]
+pub enum PragmaKeyStore {
+    Filesystem(FilesystemKeyStore),
+    Encrypted(EncryptedKeyStore),
+    External(ExternalSigner),
+    /// Keyless backend of the watch-only clients (see [`WatchOnlyKeyStore`]).
+    WatchOnly(WatchOnlyKeyStore),
+}
+
+impl From<FilesystemKeyStore> for PragmaKeyStore {
+    fn from(keystore: FilesystemKeyStore) -> Self {
+        Self::Filesystem(keystore)
+    }
+}
+
+/// Opens the keystore backend selected by `PM_KEYSTORE`, in `keystore_path`
+/// or in the default keystore directory for the filesystem and encrypted
+/// backends.
+///
+/// # Errors
+///
+/// Fails if the backend is unknown, if its environment variable is missing,
+/// or if the keystore cannot be opened (e.g. a wrong passphrase).
+pub fn open_keystore(keystore_path: Option<String>) -> anyhow::Result<PragmaKeyStore> {
+    let backend = std::env::var(KEYSTORE_ENV).unwrap_or_else(|_| "filesystem".to_string());
+    let keystore_path = keystore_path.unwrap_or_else(default_keystore_path);
+    match backend.as_str() {
+        "filesystem" => Ok(PragmaKeyStore::Filesystem(
+            FilesystemKeyStore::new(keystore_path.into())
+                .map_err(|e| anyhow::anyhow!("Failed to open the keystore: {e:?}"))?,
+        )),
+        "encrypted" => {
+            let passphrase = std::env::var(KEYSTORE_PASSPHRASE_ENV).map_err(|_| {
+                anyhow::anyhow!("{KEYSTORE_PASSPHRASE_ENV} must be set for the encrypted keystore")
+            })?;
+            Ok(PragmaKeyStore::Encrypted(EncryptedKeyStore::open(
+                keystore_path.into(),
+                &passphrase,
+            )?))
+        }
+        "external" => {
+            let command = std::env::var(SIGNER_COMMAND_ENV).map_err(|_| {
+                anyhow::anyhow!("{SIGNER_COMMAND_ENV} must be set for the external signer")
+            })?;
+            Ok(PragmaKeyStore::External(ExternalSigner::from_command(
+                &command,
+            )?))
+        }
+        other => anyhow::bail!(
+            "Unknown keystore '{other}'. {KEYSTORE_ENV} must be 'filesystem', 'encrypted' or 'external'"
+        ),
+    }
+}
+
+impl TransactionAuthenticator for PragmaKeyStore {
+    async fn get_signature(
+        &self,
+        pub_key_commitment: PublicKeyCommitment,
+        signing_inputs: &SigningInputs,
+    ) -> Result<Signature, AuthenticationError> {
+        match self {
+            Self::Filesystem(keystore) => {
+                keystore
+                    .get_signature(pub_key_commitment, signing_inputs)
+                    .await
+            }
+            Self::Encrypted(keystore) => {
+                keystore
+                    .get_signature(pub_key_commitment, signing_inputs)
+                    .await
+            }
+            Self::External(signer) => {
+                signer
+                    .get_signature(pub_key_commitment, signing_inputs)
+                    .await
+            }
+            Self::WatchOnly(keystore) => {
+                keystore
+                    .get_signature(pub_key_commitment, signing_inputs)
+                    .await
+            }
+        }
+    }
+
+    async fn get_public_key(
+        &self,
+        pub_key_commitment: PublicKeyCommitment,
+    ) -> Option<Arc<PublicKey>> {
+        match self {
+            Self::Filesystem(keystore) => keystore.get_public_key(pub_key_commitment).await,
+            Self::Encrypted(keystore) => keystore.get_public_key(pub_key_commitment).await,
+            Self::External(signer) => signer.get_public_key(pub_key_commitment).await,
+            Self::WatchOnly(keystore) => keystore.get_public_key(pub_key_commitment).await,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Keystore for PragmaKeyStore {
+    async fn add_key(
+        &self,
+        key: &AuthSecretKey,
+        account_id: AccountId,
+    ) -> Result<(), KeyStoreError> {
+        match self {
+            Self::Filesystem(keystore) => keystore.add_key(key, account_id).await,
+            Self::Encrypted(keystore) => keystore.add_key(key, account_id).await,
+            Self::External(signer) => signer.add_key(key, account_id).await,
+            Self::WatchOnly(keystore) => keystore.add_key(key, account_id).await,
+        }
+    }
+
+    async fn remove_key(
+        &self,
+        pub_key_commitment: PublicKeyCommitment,
+    ) -> Result<(), KeyStoreError> {
+        match self {
+            Self::Filesystem(keystore) => keystore.remove_key(pub_key_commitment).await,
+            Self::Encrypted(keystore) => keystore.remove_key(pub_key_commitment).await,
+            Self::External(signer) => signer.remove_key(pub_key_commitment).await,
+            Self::WatchOnly(keystore) => keystore.remove_key(pub_key_commitment).await,
+        }
+    }
+
+    async fn get_key(
+        &self,
+        pub_key_commitment: PublicKeyCommitment,
+    ) -> Result<Option<AuthSecretKey>, KeyStoreError> {
+        match self {
+            Self::Filesystem(keystore) => keystore.get_key(pub_key_commitment).await,
+            Self::Encrypted(keystore) => keystore.get_key(pub_key_commitment).await,
+            Self::External(signer) => signer.get_key(pub_key_commitment).await,
+            Self::WatchOnly(keystore) => keystore.get_key(pub_key_commitment).await,
+        }
+    }
+
+    async fn get_account_id_by_key_commitment(
+        &self,
+        pub_key_commitment: PublicKeyCommitment,
+    ) -> Result<Option<AccountId>, KeyStoreError> {
+        match self {
+            Self::Filesystem(keystore) => {
+                keystore
+                    .get_account_id_by_key_commitment(pub_key_commitment)
+                    .await
+            }
+            Self::Encrypted(keystore) => {
+                keystore
+                    .get_account_id_by_key_commitment(pub_key_commitment)
+                    .await
+            }
+            Self::External(signer) => {
+                signer
+                    .get_account_id_by_key_commitment(pub_key_commitment)
+                    .await
+            }
+            Self::WatchOnly(keystore) => {
+                keystore
+                    .get_account_id_by_key_commitment(pub_key_commitment)
+                    .await
+            }
+        }
+    }
+
+    async fn get_account_key_commitments(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<BTreeSet<PublicKeyCommitment>, KeyStoreError> {
+        match self {
+            Self::Filesystem(keystore) => keystore.get_account_key_commitments(account_id).await,
+            Self::Encrypted(keystore) => keystore.get_account_key_commitments(account_id).await,
+            Self::External(signer) => signer.get_account_key_commitments(account_id).await,
+            Self::WatchOnly(keystore) => keystore.get_account_key_commitments(account_id).await,
+        }
+    }
+}
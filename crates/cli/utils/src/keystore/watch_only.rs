@@ -0,0 +1,93 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use miden_client::{
+    account::AccountId,
+    auth::{AuthSecretKey, PublicKey, PublicKeyCommitment, Signature, SigningInputs},
+    keystore::{KeyStoreError, Keystore},
+};
+use miden_protocol::transaction::TransactionSummary;
+use miden_tx::auth::TransactionAuthenticator;
+use miden_tx::AuthenticationError;
+
+/// Error a [`WatchOnlyKeyStore`] fails with when asked to sign a transaction,
+/// carrying the summary that was to be signed.
+#[derive(Debug, thiserror::Error)]
+#[error("signature of transaction summary {} requested", .0.to_commitment())]
+pub struct SignatureRequested(pub TransactionSummary);
+
+/// Keystore of watch-only clients, holding no keys.
+///
+/// Every signature request fails with [`SignatureRequested`], so that the
+/// transaction stops at its signature and can be exported to the host holding
+/// the key (see [`crate::UnsignedTransaction::export`]).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WatchOnlyKeyStore;
+
+impl TransactionAuthenticator for WatchOnlyKeyStore {
+    async fn get_signature(
+        &self,
+        pub_key_commitment: PublicKeyCommitment,
+        signing_inputs: &SigningInputs,
+    ) -> Result<Signature, AuthenticationError> {
+        match signing_inputs {
+            SigningInputs::TransactionSummary(summary) => {
+                Err(AuthenticationError::other_with_source(
+                    "The watch-only client holds no keys",
+                    SignatureRequested((**summary).clone()),
+                ))
+            }
+            _ => Err(AuthenticationError::UnknownPublicKey(pub_key_commitment)),
+        }
+    }
+
+    async fn get_public_key(
+        &self,
+        _pub_key_commitment: PublicKeyCommitment,
+    ) -> Option<Arc<PublicKey>> {
+        None
+    }
+}
+
+#[async_trait::async_trait]
+impl Keystore for WatchOnlyKeyStore {
+    async fn add_key(
+        &self,
+        _key: &AuthSecretKey,
+        _account_id: AccountId,
+    ) -> Result<(), KeyStoreError> {
+        Err(KeyStoreError::StorageError(
+            "The watch-only client holds no keys".to_string(),
+        ))
+    }
+
+    async fn remove_key(
+        &self,
+        _pub_key_commitment: PublicKeyCommitment,
+    ) -> Result<(), KeyStoreError> {
+        Err(KeyStoreError::StorageError(
+            "The watch-only client holds no keys".to_string(),
+        ))
+    }
+
+    async fn get_key(
+        &self,
+        _pub_key_commitment: PublicKeyCommitment,
+    ) -> Result<Option<AuthSecretKey>, KeyStoreError> {
+        Ok(None)
+    }
+
+    async fn get_account_id_by_key_commitment(
+        &self,
+        _pub_key_commitment: PublicKeyCommitment,
+    ) -> Result<Option<AccountId>, KeyStoreError> {
+        Ok(None)
+    }
+
+    async fn get_account_key_commitments(
+        &self,
+        _account_id: &AccountId,
+    ) -> Result<BTreeSet<PublicKeyCommitment>, KeyStoreError> {
+        Ok(BTreeSet::new())
+    }
+}
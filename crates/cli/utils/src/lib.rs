@@ -1,11 +1,27 @@
+pub mod assets;
 pub mod client;
 pub mod constants;
+pub mod dry_run;
+pub mod keystore;
 pub mod network;
+pub mod offline;
+pub mod output;
+pub mod profile;
+pub mod randomness;
+pub mod retry;
 pub mod storage;
 
+pub use assets::*;
 pub use client::*;
 pub use constants::*;
+pub use dry_run::*;
+pub use keystore::*;
 pub use network::*;
+pub use offline::*;
+pub use output::*;
+pub use profile::*;
+pub use randomness::*;
+pub use retry::*;
 pub use storage::*;
 
 use anyhow::Context;
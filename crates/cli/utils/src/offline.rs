@@ -0,0 +1,286 @@
+//! Offline signing of transactions.
+//!
+//! A watch-only host, holding no keys, executes the transaction until it asks
+//! for a signature and exports the summary to sign ([`UnsignedTransaction`],
+//! see [`crate::WatchOnlyKeyStore`]).
+//! The host holding the key signs that summary ([`UnsignedTransaction::sign`])
+//! without ever going online, and the watch-only host submits the transaction
+//! with the signature ([`SignedTransaction`]).
+//!
+//! The signature covers the account delta of the transaction, so the
+//! transaction has to be submitted before the state of the account changes.
+
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use anyhow::Context;
+use miden_client::{
+    account::AccountId,
+    keystore::Keystore,
+    transaction::{TransactionRequest, TransactionRequestBuilder, TransactionScript},
+    Client, ClientError, Felt, Word,
+};
+use miden_protocol::transaction::TransactionSummary;
+use miden_protocol::utils::serde::{
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+use miden_protocol::Hasher;
+use miden_tx::auth::{SigningInputs, TransactionAuthenticator};
+use miden_tx::TransactionExecutorError;
+
+use crate::keystore::SignatureRequested;
+use crate::{submit_transaction_with_retry, RetryPolicy};
+
+/// Prefixes of the exported files, so that a signed transaction is not
+/// mistaken for an unsigned one.
+const UNSIGNED_MAGIC: &[u8; 8] = b"PMUTX001";
+const SIGNED_MAGIC: &[u8; 8] = b"PMSTX001";
+
+/// A transaction of `account_id` waiting for the signature of `summary`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnsignedTransaction {
+    pub account_id: AccountId,
+    pub tx_script: TransactionScript,
+    pub summary: TransactionSummary,
+}
+
+/// A transaction of `account_id` along with the signatures it asks for, keyed
+/// in the advice map as the auth procedure of the account expects them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignedTransaction {
+    pub account_id: AccountId,
+    pub tx_script: TransactionScript,
+    pub signatures: Vec<(Word, Vec<Felt>)>,
+}
+
+impl UnsignedTransaction {
+    /// Executes `tx_script` against `account_id` with a watch-only client and
+    /// returns the transaction waiting for a signature.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the transaction fails to execute, or if it needs no signature
+    /// (e.g. because the client holds the key).
+    pub async fn export<K>(
+        client: &mut Client<K>,
+        account_id: AccountId,
+        tx_script: TransactionScript,
+    ) -> anyhow::Result<Self>
+    where
+        K: Keystore + Sync + 'static,
+    {
+        let transaction_request = TransactionRequestBuilder::new()
+            .custom_script(tx_script.clone())
+            .build()
+            .map_err(|e| anyhow::anyhow!("Error while building transaction request: {e:?}"))?;
+        let error = match client
+            .execute_transaction(account_id, transaction_request)
+            .await
+        {
+            Ok(_) => anyhow::bail!("The transaction of {account_id} needs no signature"),
+            Err(e) => e,
+        };
+        let summary = match &error {
+            ClientError::TransactionExecutorError(error) => requested_summary(error),
+            _ => None,
+        };
+        match summary {
+            Some(summary) => Ok(Self {
+                account_id,
+                tx_script,
+                summary,
+            }),
+            None => Err(anyhow::anyhow!(
+                "Error while executing transaction: {error:?}"
+            )),
+        }
+    }
+
+    /// Commitment to the summary, which is the message that gets signed.
+    pub fn message(&self) -> Word {
+        self.summary.to_commitment()
+    }
+
+    /// Signs the summary with every key of the account found in `keystore`,
+    /// whatever its backend.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `keystore` holds no key of the account or a key fails to sign.
+    pub async fn sign<K>(&self, keystore: &K) -> anyhow::Result<SignedTransaction>
+    where
+        K: Keystore + TransactionAuthenticator,
+    {
+        let pub_key_commitments = keystore
+            .get_account_key_commitments(&self.account_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to read the keystore: {e:?}"))?;
+        if pub_key_commitments.is_empty() {
+            anyhow::bail!("No key of {} found in the keystore", self.account_id);
+        }
+
+        let message = self.message();
+        let signing_inputs = SigningInputs::TransactionSummary(Box::new(self.summary.clone()));
+        let mut signatures = Vec::with_capacity(pub_key_commitments.len());
+        for pub_key_commitment in pub_key_commitments {
+            let signature = keystore
+                .get_signature(pub_key_commitment, &signing_inputs)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to sign the transaction: {e:?}"))?;
+            let pub_key_commitment: Word = pub_key_commitment.into();
+            signatures.push((
+                Hasher::merge(&[pub_key_commitment, message]),
+                signature.to_prepared_signature(message),
+            ));
+        }
+
+        Ok(SignedTransaction {
+            account_id: self.account_id,
+            tx_script: self.tx_script.clone(),
+            signatures,
+        })
+    }
+
+    /// # Errors
+    ///
+    /// Fails if the file cannot be written.
+    pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+        write_file(path, UNSIGNED_MAGIC, self)
+    }
+
+    /// # Errors
+    ///
+    /// Fails if the file cannot be read or does not hold an unsigned
+    /// transaction.
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        read_file(path, UNSIGNED_MAGIC, "an unsigned transaction")
+    }
+}
+
+/// Summary a transaction stopped at for lack of a signature: the one a
+/// [`crate::WatchOnlyKeyStore`] was asked to sign, or the one of an auth procedure
+/// emitting the unauthorized event.
+pub fn requested_summary(error: &TransactionExecutorError) -> Option<TransactionSummary> {
+    match error {
+        TransactionExecutorError::Unauthorized(summary) => Some((**summary).clone()),
+        TransactionExecutorError::TransactionProgramExecutionFailed(error) => {
+            let mut source: Option<&(dyn Error + 'static)> = Some(error);
+            while let Some(error) = source {
+                if let Some(SignatureRequested(summary)) = error.downcast_ref() {
+                    return Some(summary.clone());
+                }
+                source = error.source();
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
+impl SignedTransaction {
+    /// Request submitting the transaction with its signatures.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the request cannot be built.
+    pub fn transaction_request(&self) -> anyhow::Result<TransactionRequest> {
+        TransactionRequestBuilder::new()
+            .custom_script(self.tx_script.clone())
+            .extend_advice_map(self.signatures.clone())
+            .build()
+            .map_err(|e| anyhow::anyhow!("Error while building transaction request: {e:?}"))
+    }
+
+    /// Submits the transaction, retried according to `retry`. The client
+    /// needs no key.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the transaction fails to execute, e.g. because the state of
+    /// the account changed since it was exported, or to be submitted.
+    pub async fn submit<K>(&self, client: &mut Client<K>, retry: &RetryPolicy) -> anyhow::Result<()>
+    where
+        K: Keystore + Sync + 'static,
+    {
+        submit_transaction_with_retry(client, self.account_id, self.transaction_request()?, retry)
+            .await
+            .map_err(|e| anyhow::anyhow!("Error while submitting transaction: {e:?}"))?;
+        Ok(())
+    }
+
+    /// # Errors
+    ///
+    /// Fails if the file cannot be written.
+    pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+        write_file(path, SIGNED_MAGIC, self)
+    }
+
+    /// # Errors
+    ///
+    /// Fails if the file cannot be read or does not hold a signed transaction.
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        read_file(path, SIGNED_MAGIC, "a signed transaction")
+    }
+}
+
+fn write_file<T: Serializable>(path: &Path, magic: &[u8; 8], value: &T) -> anyhow::Result<()> {
+    let mut bytes = magic.to_vec();
+    value.write_into(&mut bytes);
+    fs::write(path, bytes).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn read_file<T: Deserializable>(path: &Path, magic: &[u8; 8], what: &str) -> anyhow::Result<T> {
+    let bytes = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let content = bytes
+        .strip_prefix(magic.as_slice())
+        .ok_or_else(|| anyhow::anyhow!("{} does not hold {what}", path.display()))?;
+    T::read_from_bytes(content)
+        .map_err(|e| anyhow::anyhow!("Failed to parse {}: {e:?}", path.display()))
+}
+
+impl Serializable for UnsignedTransaction {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.account_id.write_into(target);
+        self.tx_script.write_into(target);
+        self.summary.write_into(target);
+    }
+}
+
+impl Deserializable for UnsignedTransaction {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self {
+            account_id: AccountId::read_from(source)?,
+            tx_script: TransactionScript::read_from(source)?,
+            summary: TransactionSummary::read_from(source)?,
+        })
+    }
+}
+
+impl Serializable for SignedTransaction {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.account_id.write_into(target);
+        self.tx_script.write_into(target);
+        target.write_usize(self.signatures.len());
+        for (key, signature) in &self.signatures {
+            key.write_into(target);
+            signature.write_into(target);
+        }
+    }
+}
+
+impl Deserializable for SignedTransaction {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let account_id = AccountId::read_from(source)?;
+        let tx_script = TransactionScript::read_from(source)?;
+        let nb_of_signatures = source.read_usize()?;
+        let signatures = (0..nb_of_signatures)
+            .map(|_| Ok((Word::read_from(source)?, Vec::<Felt>::read_from(source)?)))
+            .collect::<Result<_, DeserializationError>>()?;
+        Ok(Self {
+            account_id,
+            tx_script,
+            signatures,
+        })
+    }
+}
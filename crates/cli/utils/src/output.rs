@@ -0,0 +1,185 @@
+use colored::*;
+use miden_client::sync::SyncSummary;
+use pm_types::Entry;
+use serde::Serialize;
+
+/// Format the CLIs print the result of a command in, chosen with `--output`.
+///
+/// Only the result is printed on stdout. Progress, warnings and dry run
+/// reports go to stderr, so that `--output json` prints a single JSON
+/// document.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable, with colors, banners and tables
+    #[default]
+    Table,
+    /// A single JSON document, following the schema of the command
+    Json,
+    /// Undecorated: one record per line, fields separated by tabs
+    Plain,
+}
+
+/// Serializes the result of a command on a single line.
+///
+/// # Errors
+///
+/// Fails if `value` cannot be serialized.
+pub fn to_json<T: Serialize>(value: &T) -> anyhow::Result<String> {
+    serde_json::to_string(value)
+        .map_err(|e| anyhow::anyhow!("Failed to serialize the output to JSON: {e:?}"))
+}
+
+/// Joins the fields of a record of the plain output.
+pub fn plain_record<I, T>(fields: I) -> String
+where
+    I: IntoIterator<Item = T>,
+    T: ToString,
+{
+    fields
+        .into_iter()
+        .map(|field| field.to_string())
+        .collect::<Vec<_>>()
+        .join("\t")
+}
+
+/// Result of a command changing the state, e.g. submitting a transaction.
+///
+/// JSON schema: `{"message": string}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Done {
+    pub message: String,
+}
+
+impl Done {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+
+    pub fn table(&self) -> String {
+        format!("✅ {}", self.message).bright_green().to_string()
+    }
+
+    pub fn plain(&self) -> String {
+        self.message.clone()
+    }
+}
+
+/// Result of a sync.
+///
+/// JSON schema: `{"block_num": u32, "committed_notes": u64,
+/// "consumed_notes": u64, "updated_accounts": u64,
+/// "committed_transactions": u64}`, the counts being those of the sync.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SyncOutput {
+    pub block_num: u32,
+    pub committed_notes: usize,
+    pub consumed_notes: usize,
+    pub updated_accounts: usize,
+    pub committed_transactions: usize,
+}
+
+impl From<&SyncSummary> for SyncOutput {
+    fn from(summary: &SyncSummary) -> Self {
+        Self {
+            block_num: summary.block_num.as_u32(),
+            committed_notes: summary.committed_notes.len(),
+            consumed_notes: summary.consumed_notes.len(),
+            updated_accounts: summary.updated_accounts.len(),
+            committed_transactions: summary.committed_transactions.len(),
+        }
+    }
+}
+
+impl SyncOutput {
+    pub fn table(&self) -> String {
+        format!(
+            "🔁 Sync successful!\n\n\
+             State synced to block {}\n\
+             Tracked notes updated: {}\n\
+             Tracked notes consumed: {}\n\
+             Tracked accounts updated: {}\n\
+             Commited transactions: {}",
+            self.block_num,
+            self.committed_notes,
+            self.consumed_notes,
+            self.updated_accounts,
+            self.committed_transactions
+        )
+    }
+
+    pub fn plain(&self) -> String {
+        plain_record([
+            self.block_num as usize,
+            self.committed_notes,
+            self.consumed_notes,
+            self.updated_accounts,
+            self.committed_transactions,
+        ])
+    }
+}
+
+/// Renders a price entry, whose JSON schema is `{"faucet_id": string,
+/// "price": u64, "decimals": u32, "timestamp": u64}`.
+pub fn entry_table(entry: &Entry) -> String {
+    format!(
+        "Price: {}, Decimals: {}, Timestamp: {}",
+        entry.price, entry.decimals, entry.timestamp
+    )
+}
+
+pub fn entry_plain(entry: &Entry) -> String {
+    plain_record([
+        entry.faucet_id.clone(),
+        entry.price.to_string(),
+        entry.decimals.to_string(),
+        entry.timestamp.to_string(),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_done_schema() {
+        let done = Done::new("Publisher 0x01 removed");
+        assert_eq!(
+            to_json(&done).unwrap(),
+            r#"{"message":"Publisher 0x01 removed"}"#
+        );
+        assert_eq!(done.plain(), "Publisher 0x01 removed");
+    }
+
+    #[test]
+    fn test_sync_schema() {
+        let sync = SyncOutput {
+            block_num: 42,
+            committed_notes: 1,
+            consumed_notes: 2,
+            updated_accounts: 3,
+            committed_transactions: 4,
+        };
+        assert_eq!(
+            to_json(&sync).unwrap(),
+            r#"{"block_num":42,"committed_notes":1,"consumed_notes":2,"updated_accounts":3,"committed_transactions":4}"#
+        );
+        assert_eq!(sync.plain(), "42\t1\t2\t3\t4");
+    }
+
+    #[test]
+    fn test_entry_schema() {
+        let entry = Entry {
+            faucet_id: "1:0".to_string(),
+            price: 6819900000000,
+            decimals: 8,
+            timestamp: 1700000000,
+        };
+        assert_eq!(
+            to_json(&entry).unwrap(),
+            r#"{"faucet_id":"1:0","price":6819900000000,"decimals":8,"timestamp":1700000000}"#
+        );
+        assert_eq!(entry_plain(&entry), "1:0\t6819900000000\t8\t1700000000");
+    }
+}
@@ -0,0 +1,295 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::{Context, Result};
+use miden_client::grpc_support::{DEVNET_PROVER_ENDPOINT, TESTNET_PROVER_ENDPOINT};
+use miden_client::rpc::Endpoint;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{read_config_file, write_config_file, RetryPolicy, PRAGMA_ACCOUNTS_STORAGE_FILE};
+
+/// Networks known without any profile in the configuration file.
+pub const BUILTIN_NETWORKS: [&str; 3] = ["local", "devnet", "testnet"];
+
+// Bounds every node RPC call, including SubmitProvenTx. 10s was too tight for
+// the heavier first submit after a cold start (account deploy) — observed real
+// >10s submits on testnet. 60s gives headroom and is itself bounded by the
+// SDK-side 180s publish_batch timeout.
+pub const DEFAULT_RPC_TIMEOUT_MS: u64 = 60_000;
+
+/// How to reach a network: its node, its prover and where the client keeps
+/// its state.
+///
+/// Profiles are stored in the configuration file under
+/// `networks.<name>.profile`, next to the account ids of the network, and
+/// override the built-in `local`, `devnet` and `testnet` networks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkProfile {
+    /// gRPC endpoint of the node, e.g. `https://rpc.testnet.miden.io`.
+    pub rpc_url: String,
+    /// Remote prover the proving is delegated to. Transactions are proved
+    /// locally without one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prover_url: Option<String>,
+    /// Timeout of every RPC call, in milliseconds.
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Client store of the network, `miden_storage/store.sqlite3` by default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub store_path: Option<PathBuf>,
+    /// How the calls failing with a transient error are retried.
+    #[serde(default)]
+    pub retry: RetryPolicy,
+}
+
+fn default_timeout_ms() -> u64 {
+    DEFAULT_RPC_TIMEOUT_MS
+}
+
+impl NetworkProfile {
+    /// Profile of a built-in network, `None` for any other name.
+    ///
+    /// Testnet and devnet delegate proving to Miden's hosted provers, while
+    /// `local` proves locally since a local node has no hosted prover.
+    pub fn builtin(network: &str) -> Option<Self> {
+        let (endpoint, prover_url) = match network {
+            "local" => (Endpoint::localhost(), None),
+            "devnet" => (Endpoint::devnet(), Some(DEVNET_PROVER_ENDPOINT)),
+            "testnet" => (Endpoint::testnet(), Some(TESTNET_PROVER_ENDPOINT)),
+            _ => return None,
+        };
+        Some(Self {
+            rpc_url: endpoint.to_string(),
+            prover_url: prover_url.map(str::to_string),
+            timeout_ms: DEFAULT_RPC_TIMEOUT_MS,
+            store_path: None,
+            retry: RetryPolicy::default(),
+        })
+    }
+
+    /// # Errors
+    ///
+    /// Fails if the RPC URL is not a valid endpoint.
+    pub fn endpoint(&self) -> Result<Endpoint> {
+        Endpoint::try_from(self.rpc_url.as_str())
+            .map_err(|e| anyhow::anyhow!("Invalid RPC URL {}: {e:?}", self.rpc_url))
+    }
+}
+
+/// Gets the profile of `network`: the one stored in the configuration file,
+/// or the built-in one.
+///
+/// # Errors
+///
+/// Fails if the configuration file cannot be read, if the stored profile is
+/// invalid, or if `network` is neither stored nor built-in.
+pub fn get_network_profile(storage_path: &Path, network: &str) -> Result<NetworkProfile> {
+    let config = read_config_file(storage_path)?;
+    match config["networks"][network].get("profile") {
+        Some(profile) => serde_json::from_value(profile.clone())
+            .with_context(|| format!("Invalid profile for network '{network}'")),
+        None => NetworkProfile::builtin(network).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unknown network '{network}'. Must be 'local', 'devnet', 'testnet' or a profile added with `pm-oracle network add`"
+            )
+        }),
+    }
+}
+
+/// Profiles overriding the configuration file for the rest of the process.
+static OVERRIDES: OnceLock<Mutex<HashMap<String, NetworkProfile>>> = OnceLock::new();
+
+/// Overrides the profile of `network` for the rest of the process, without
+/// writing the configuration file. The Python bindings take their timeout and
+/// retry policy at `init` this way.
+pub fn override_network_profile(network: &str, profile: NetworkProfile) {
+    OVERRIDES
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap()
+        .insert(network.to_string(), profile);
+}
+
+/// Gets the profile the clients of `network` use: its override, or else its
+/// profile in [`PRAGMA_ACCOUNTS_STORAGE_FILE`].
+///
+/// # Errors
+///
+/// Fails like [`get_network_profile`] when the network is not overridden.
+pub fn resolve_network_profile(network: &str) -> Result<NetworkProfile> {
+    let overridden = OVERRIDES
+        .get()
+        .and_then(|overrides| overrides.lock().unwrap().get(network).cloned());
+    match overridden {
+        Some(profile) => Ok(profile),
+        None => get_network_profile(Path::new(PRAGMA_ACCOUNTS_STORAGE_FILE), network),
+    }
+}
+
+/// Gets every network with its profile, the built-in networks first.
+///
+/// # Errors
+///
+/// Fails if the configuration file cannot be read or holds an invalid profile.
+pub fn list_network_profiles(storage_path: &Path) -> Result<Vec<(String, NetworkProfile)>> {
+    let config = read_config_file(storage_path)?;
+    let mut networks: Vec<String> = BUILTIN_NETWORKS.iter().map(|n| n.to_string()).collect();
+    if let Some(stored) = config["networks"].as_object() {
+        networks.extend(
+            stored
+                .iter()
+                .filter(|(name, network)| {
+                    network.get("profile").is_some() && !BUILTIN_NETWORKS.contains(&name.as_str())
+                })
+                .map(|(name, _)| name.clone()),
+        );
+    }
+    networks
+        .into_iter()
+        .map(|network| {
+            let profile = get_network_profile(storage_path, &network)?;
+            Ok((network, profile))
+        })
+        .collect()
+}
+
+/// Adds or replaces the profile of `network`, keeping its account ids.
+///
+/// # Errors
+///
+/// Fails if the configuration file cannot be read or written.
+pub fn set_network_profile(
+    storage_path: &Path,
+    network: &str,
+    profile: &NetworkProfile,
+) -> Result<()> {
+    let mut config = read_config_file(storage_path)?;
+
+    if !config.get("networks").is_some_and(|v| v.is_object()) {
+        config["networks"] = json!({});
+    }
+    if !config["networks"]
+        .get(network)
+        .is_some_and(|v| v.is_object())
+    {
+        config["networks"][network] = json!({});
+    }
+
+    config["networks"][network]["profile"] = serde_json::to_value(profile)?;
+
+    write_config_file(storage_path, &config)
+}
+
+/// Removes the profile of `network`, keeping its account ids. Returns whether
+/// a profile was stored.
+///
+/// # Errors
+///
+/// Fails if the configuration file cannot be read or written.
+pub fn remove_network_profile(storage_path: &Path, network: &str) -> Result<bool> {
+    let mut config = read_config_file(storage_path)?;
+
+    let removed = config["networks"]
+        .get_mut(network)
+        .and_then(|network| network.as_object_mut())
+        .and_then(|network| network.remove("profile"))
+        .is_some();
+    if removed {
+        write_config_file(storage_path, &config)?;
+    }
+
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile() -> NetworkProfile {
+        NetworkProfile {
+            rpc_url: "http://10.0.0.2:57291".to_string(),
+            prover_url: None,
+            timeout_ms: 5_000,
+            store_path: Some(PathBuf::from("miden_storage/mycluster.sqlite3")),
+            retry: RetryPolicy {
+                max_attempts: 5,
+                initial_backoff_ms: 100,
+                max_backoff_ms: 2_000,
+            },
+        }
+    }
+
+    #[test]
+    fn test_profiles_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pragma_miden.json");
+
+        assert!(get_network_profile(&path, "mycluster").is_err());
+
+        set_network_profile(&path, "mycluster", &profile()).unwrap();
+        assert_eq!(get_network_profile(&path, "mycluster").unwrap(), profile());
+
+        let networks: Vec<String> = list_network_profiles(&path)
+            .unwrap()
+            .into_iter()
+            .map(|(network, _)| network)
+            .collect();
+        assert_eq!(networks, ["local", "devnet", "testnet", "mycluster"]);
+
+        assert!(remove_network_profile(&path, "mycluster").unwrap());
+        assert!(!remove_network_profile(&path, "mycluster").unwrap());
+        assert!(get_network_profile(&path, "mycluster").is_err());
+    }
+
+    #[test]
+    fn test_profiles_keep_account_ids() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pragma_miden.json");
+        write_config_file(
+            &path,
+            &json!({ "networks": { "testnet": { "oracle_account_id": "0x01" } } }),
+        )
+        .unwrap();
+
+        set_network_profile(&path, "testnet", &profile()).unwrap();
+        assert_eq!(get_network_profile(&path, "testnet").unwrap(), profile());
+        remove_network_profile(&path, "testnet").unwrap();
+
+        let config = read_config_file(&path).unwrap();
+        assert_eq!(config["networks"]["testnet"]["oracle_account_id"], "0x01");
+        assert_eq!(
+            get_network_profile(&path, "testnet").unwrap(),
+            NetworkProfile::builtin("testnet").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_missing_fields_take_defaults() {
+        let profile: NetworkProfile =
+            serde_json::from_value(json!({ "rpc_url": "http://localhost:57291" })).unwrap();
+        assert_eq!(profile.prover_url, None);
+        assert_eq!(profile.timeout_ms, DEFAULT_RPC_TIMEOUT_MS);
+        assert_eq!(profile.store_path, None);
+        assert_eq!(profile.retry, RetryPolicy::default());
+
+        let profile: NetworkProfile = serde_json::from_value(json!({
+            "rpc_url": "http://localhost:57291",
+            "retry": { "max_attempts": 1 }
+        }))
+        .unwrap();
+        assert_eq!(profile.retry.max_attempts, 1);
+        assert_eq!(
+            profile.retry.max_backoff_ms,
+            RetryPolicy::default().max_backoff_ms
+        );
+    }
+
+    #[test]
+    fn test_overrides_take_precedence() {
+        override_network_profile("overridden", profile());
+        assert_eq!(resolve_network_profile("overridden").unwrap(), profile());
+        assert!(resolve_network_profile("not-overridden").is_err());
+    }
+}
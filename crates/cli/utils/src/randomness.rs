@@ -0,0 +1,90 @@
+use anyhow::{Context, Result};
+use miden_client::{account::AccountId, Felt, Word};
+use serde_json::json;
+use std::path::Path;
+
+use crate::{read_config_file, write_config_file};
+
+/// Saves the randomness a publisher committed to for `round`, so that it can
+/// be revealed later.
+///
+/// Secrets are kept per network and publisher, in the `randomness_secrets`
+/// section of the network configuration, as dot-separated felts:
+///
+/// ```json
+/// "randomness_secrets": {
+///     "0x...": { "42": "1.2.3.4" }
+/// }
+/// ```
+pub fn set_randomness_secret(
+    storage_path: &Path,
+    network: &str,
+    publisher_id: &AccountId,
+    round: u64,
+    secret: Word,
+) -> Result<()> {
+    let mut config = read_config_file(storage_path)?;
+
+    if !config.get("networks").is_some_and(|v| v.is_object()) {
+        config["networks"] = json!({});
+    }
+    if !config["networks"]
+        .get(network)
+        .is_some_and(|v| v.is_object())
+    {
+        config["networks"][network] = json!({});
+    }
+
+    let secrets = &mut config["networks"][network]["randomness_secrets"];
+    if !secrets.is_object() {
+        *secrets = json!({});
+    }
+    let publisher_secrets = &mut secrets[publisher_id.to_string()];
+    if !publisher_secrets.is_object() {
+        *publisher_secrets = json!({});
+    }
+    publisher_secrets[round.to_string()] = json!(secret
+        .iter()
+        .map(|felt| felt.as_canonical_u64().to_string())
+        .collect::<Vec<_>>()
+        .join("."));
+
+    write_config_file(storage_path, &config)
+}
+
+/// Gets the randomness a publisher committed to for `round`, saved by
+/// [`set_randomness_secret`].
+///
+/// Returns `None` if no randomness was saved for the round.
+pub fn get_randomness_secret(
+    storage_path: &Path,
+    network: &str,
+    publisher_id: &AccountId,
+    round: u64,
+) -> Result<Option<Word>> {
+    let config = read_config_file(storage_path)?;
+
+    let Some(secret) = config["networks"][network]["randomness_secrets"][publisher_id.to_string()]
+        .get(round.to_string())
+    else {
+        return Ok(None);
+    };
+    let secret = secret
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Randomness secret of round {round} is not a string"))?;
+
+    let felts = secret
+        .split('.')
+        .map(|felt| {
+            let felt = felt
+                .parse::<u64>()
+                .with_context(|| format!("Invalid randomness secret of round {round}"))?;
+            Felt::new(felt).map_err(|e| anyhow::anyhow!("Invalid randomness secret felt: {e}"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let felts: [Felt; 4] = felts.try_into().map_err(|_| {
+        anyhow::anyhow!("Invalid randomness secret of round {round}: expected 4 felts")
+    })?;
+
+    Ok(Some(felts.into()))
+}
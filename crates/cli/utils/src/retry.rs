@@ -0,0 +1,478 @@
+use std::error::Error;
+use std::future::Future;
+use std::io;
+use std::time::Duration;
+
+use miden_client::account::AccountId;
+use miden_client::keystore::Keystore;
+use miden_client::sync::SyncSummary;
+use miden_client::transaction::{TransactionId, TransactionRequest};
+use miden_client::{Client, ClientError};
+use serde::{Deserialize, Serialize};
+
+use crate::resolve_network_profile;
+
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+pub const DEFAULT_INITIAL_BACKOFF_MS: u64 = 500;
+pub const DEFAULT_MAX_BACKOFF_MS: u64 = 8_000;
+
+/// How RPC calls failing with a transient error are retried.
+///
+/// The delay before retry `n` is `initial_backoff_ms * 2^n`, capped at
+/// `max_backoff_ms`, of which a random half is dropped so that publishers
+/// failing together do not retry together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// Attempts of a call, the first one included. `1` disables retries.
+    pub max_attempts: u32,
+    /// Delay before the first retry, in milliseconds.
+    pub initial_backoff_ms: u64,
+    /// Upper bound of the delay between two attempts, in milliseconds.
+    pub max_backoff_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            initial_backoff_ms: DEFAULT_INITIAL_BACKOFF_MS,
+            max_backoff_ms: DEFAULT_MAX_BACKOFF_MS,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before retrying a call that failed `attempt + 1` times.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let ceiling = self
+            .initial_backoff_ms
+            .saturating_mul(1u64 << attempt.min(32))
+            .min(self.max_backoff_ms);
+        let half = ceiling / 2;
+        let jitter = rand::random::<u64>() % (ceiling - half + 1);
+        Duration::from_millis(half + jitter)
+    }
+}
+
+/// Retry policy of `network`, the default one when the network has no profile.
+pub fn retry_policy(network: &str) -> RetryPolicy {
+    resolve_network_profile(network)
+        .map(|profile| profile.retry)
+        .unwrap_or_default()
+}
+
+// gRPC status codes and transport failures that go away by themselves. tonic
+// is not a dependency of ours, so the errors are matched on their messages
+// rather than downcast, which keeps the classifier independent of the tonic
+// version miden-client is built with.
+const TRANSIENT_MARKERS: [&str; 10] = [
+    "unavailable",
+    "deadline exceeded",
+    "deadlineexceeded",
+    "resource exhausted",
+    "resourceexhausted",
+    "transport error",
+    "timed out",
+    "connection refused",
+    "connection reset",
+    "broken pipe",
+];
+
+/// Whether `error`, or any error it wraps, is a transient failure worth
+/// retrying: the node being unreachable, overloaded or too slow. Every other
+/// error, e.g. a transaction the node rejects, is permanent.
+pub fn is_transient(error: &(dyn Error + 'static)) -> bool {
+    let mut source = Some(error);
+    while let Some(error) = source {
+        if let Some(error) = error.downcast_ref::<io::Error>() {
+            if matches!(
+                error.kind(),
+                io::ErrorKind::ConnectionRefused
+                    | io::ErrorKind::ConnectionReset
+                    | io::ErrorKind::ConnectionAborted
+                    | io::ErrorKind::NotConnected
+                    | io::ErrorKind::BrokenPipe
+                    | io::ErrorKind::TimedOut
+                    | io::ErrorKind::UnexpectedEof
+            ) {
+                return true;
+            }
+        }
+        let message = error.to_string().to_lowercase();
+        if TRANSIENT_MARKERS
+            .iter()
+            .any(|marker| message.contains(marker))
+        {
+            return true;
+        }
+        source = error.source();
+    }
+    false
+}
+
+/// Counts the attempts of a call and waits before the next one.
+struct Attempts<'a> {
+    policy: &'a RetryPolicy,
+    what: &'a str,
+    attempt: u32,
+}
+
+impl<'a> Attempts<'a> {
+    fn new(policy: &'a RetryPolicy, what: &'a str) -> Self {
+        Self {
+            policy,
+            what,
+            attempt: 0,
+        }
+    }
+
+    /// Waits for the backoff and returns `true` if the call that failed with
+    /// `error` should be retried.
+    async fn retry_after<E: Error + 'static>(&mut self, error: &E) -> bool {
+        self.attempt += 1;
+        if self.attempt >= self.policy.max_attempts || !is_transient(error) {
+            return false;
+        }
+        let delay = self.policy.backoff(self.attempt - 1);
+        eprintln!(
+            "⚠️ {} failed (attempt {}/{}), retrying in {}ms: {error}",
+            self.what,
+            self.attempt,
+            self.policy.max_attempts,
+            delay.as_millis()
+        );
+        tokio::time::sleep(delay).await;
+        true
+    }
+}
+
+/// Runs `call` until it succeeds, fails with a permanent error or runs out of
+/// attempts.
+///
+/// # Errors
+///
+/// Returns the error of the last attempt.
+pub async fn retry<T, E, F, Fut>(policy: &RetryPolicy, what: &str, mut call: F) -> Result<T, E>
+where
+    E: Error + 'static,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut attempts = Attempts::new(policy, what);
+    loop {
+        match call().await {
+            Err(e) if attempts.retry_after(&e).await => continue,
+            result => return result,
+        }
+    }
+}
+
+/// [`Client::sync_state`], retried according to `policy`.
+///
+/// # Errors
+///
+/// Returns the error of the last attempt.
+pub async fn sync_state_with_retry<K>(
+    client: &mut Client<K>,
+    policy: &RetryPolicy,
+) -> Result<SyncSummary, ClientError>
+where
+    K: Keystore + Sync + 'static,
+{
+    let mut attempts = Attempts::new(policy, "Sync");
+    loop {
+        match client.sync_state().await {
+            Err(e) if attempts.retry_after(&e).await => continue,
+            result => return result,
+        }
+    }
+}
+
+/// [`Client::import_account_by_id`], retried according to `policy`.
+///
+/// # Errors
+///
+/// Returns the error of the last attempt.
+pub async fn import_account_with_retry<K>(
+    client: &mut Client<K>,
+    account_id: AccountId,
+    policy: &RetryPolicy,
+) -> Result<(), ClientError>
+where
+    K: Keystore + Sync + 'static,
+{
+    let mut attempts = Attempts::new(policy, "Account import");
+    loop {
+        match client.import_account_by_id(account_id).await {
+            Err(e) if attempts.retry_after(&e).await => continue,
+            result => return result,
+        }
+    }
+}
+
+/// Whether `error`, or any error it wraps, shows that the node was never
+/// reached, so that a request it failed certainly went unprocessed.
+fn is_unreached(error: &(dyn Error + 'static)) -> bool {
+    let mut source = Some(error);
+    while let Some(error) = source {
+        if let Some(error) = error.downcast_ref::<io::Error>() {
+            if error.kind() == io::ErrorKind::ConnectionRefused {
+                return true;
+            }
+        }
+        if error
+            .to_string()
+            .to_lowercase()
+            .contains("connection refused")
+        {
+            return true;
+        }
+        source = error.source();
+    }
+    false
+}
+
+/// [`Client::submit_new_transaction`], retried according to `policy`.
+///
+/// Executing and proving the transaction are retried on any transient
+/// failure, as nothing reached the node yet. The proven transaction is only
+/// submitted again if the node could not be reached at all: after a timeout
+/// or a dropped connection the node may have accepted it, and a second
+/// submission would be rejected (its nonce is consumed), reporting a failure
+/// for a transaction that landed. Such an error is returned as is, and the
+/// caller has to sync to find out whether the transaction was committed.
+///
+/// # Errors
+///
+/// Returns the error of the last attempt.
+pub async fn submit_transaction_with_retry<K>(
+    client: &mut Client<K>,
+    account_id: AccountId,
+    transaction_request: TransactionRequest,
+    policy: &RetryPolicy,
+) -> Result<TransactionId, ClientError>
+where
+    K: Keystore + Sync + 'static,
+{
+    if !transaction_request.expected_ntx_scripts().is_empty() {
+        let prover = client.prover();
+        client
+            .ensure_ntx_scripts_registered(
+                account_id,
+                transaction_request.expected_ntx_scripts(),
+                prover,
+            )
+            .await?;
+    }
+
+    let mut attempts = Attempts::new(policy, "Transaction execution");
+    let (tx_result, proven_transaction) = loop {
+        let proven = match client
+            .execute_transaction(account_id, transaction_request.clone())
+            .await
+        {
+            Ok(tx_result) => client
+                .prove_transaction(&tx_result)
+                .await
+                .map(|proven| (tx_result, proven)),
+            Err(e) => Err(e),
+        };
+        match proven {
+            Err(e) if attempts.retry_after(&e).await => continue,
+            proven => break proven?,
+        }
+    };
+
+    let mut attempts = Attempts::new(policy, "Transaction submission");
+    let submission_height = loop {
+        match client
+            .submit_proven_transaction(proven_transaction.clone(), &tx_result)
+            .await
+        {
+            Err(e) if is_unreached(&e) && attempts.retry_after(&e).await => continue,
+            result => break result?,
+        }
+    };
+    client
+        .apply_transaction(&tx_result, submission_height)
+        .await?;
+    Ok(tx_result.executed_transaction().id())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{setup_client, NetworkProfile, PragmaKeyStore};
+    use std::net::SocketAddr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    const FAST: RetryPolicy = RetryPolicy {
+        max_attempts: 4,
+        initial_backoff_ms: 1,
+        max_backoff_ms: 4,
+    };
+
+    /// Fake RPC server answering the n-th connection with `replies[n]`, or
+    /// with the last reply past the end. `None` drops the connection without
+    /// answering. Returns its address and the number of connections.
+    async fn fake_server(replies: Vec<Option<&'static str>>) -> (SocketAddr, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let connections = Arc::new(AtomicUsize::new(0));
+        let counter = connections.clone();
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut stream, _)) = listener.accept().await else {
+                    return;
+                };
+                let n = counter.fetch_add(1, Ordering::SeqCst);
+                let reply = replies[n.min(replies.len() - 1)];
+                // Read the request even when dropping the connection: closing
+                // with unread data resets it instead of closing it cleanly.
+                let mut request = [0u8; 64];
+                let _ = stream.read(&mut request).await;
+                if let Some(reply) = reply {
+                    let _ = stream.write_all(reply.as_bytes()).await;
+                }
+            }
+        });
+        (addr, connections)
+    }
+
+    /// Sends a request to the fake server. An `ERR` reply is a permanent
+    /// failure, no reply a transient one.
+    async fn call(addr: SocketAddr) -> io::Result<String> {
+        let mut stream = TcpStream::connect(addr).await?;
+        stream.write_all(b"get_entry").await?;
+        let mut reply = String::new();
+        stream.read_to_string(&mut reply).await?;
+        if reply.is_empty() {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        match reply.strip_prefix("ERR ") {
+            Some(message) => Err(io::Error::new(io::ErrorKind::InvalidData, message)),
+            None => Ok(reply),
+        }
+    }
+
+    /// Error wrapping another one, as the client errors wrap the RPC errors.
+    #[derive(Debug)]
+    struct Wrapped(io::Error);
+
+    impl std::fmt::Display for Wrapped {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "sync failed")
+        }
+    }
+
+    impl Error for Wrapped {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn test_backoff_is_exponential_and_bounded() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            initial_backoff_ms: 100,
+            max_backoff_ms: 1_000,
+        };
+        for _ in 0..100 {
+            let first = policy.backoff(0).as_millis();
+            assert!((50..=100).contains(&first), "{first}");
+            let third = policy.backoff(2).as_millis();
+            assert!((200..=400).contains(&third), "{third}");
+            let capped = policy.backoff(40).as_millis();
+            assert!((500..=1_000).contains(&capped), "{capped}");
+        }
+    }
+
+    #[test]
+    fn test_classifier() {
+        let transient = io::Error::from(io::ErrorKind::ConnectionRefused);
+        assert!(is_transient(&transient));
+        let unavailable = io::Error::other("status: Unavailable, message: \"node is syncing\"");
+        assert!(is_transient(&unavailable));
+        let wrapped = Wrapped(transient);
+        assert!(is_transient(&wrapped));
+
+        let rejected = io::Error::other("status: InvalidArgument, message: \"invalid proof\"");
+        assert!(!is_transient(&rejected));
+        assert!(!is_transient(&io::Error::from(
+            io::ErrorKind::PermissionDenied
+        )));
+    }
+
+    #[test]
+    fn test_only_unreached_nodes_are_safe_to_resubmit_to() {
+        let refused = io::Error::from(io::ErrorKind::ConnectionRefused);
+        assert!(is_unreached(&refused));
+        assert!(is_unreached(&Wrapped(refused)));
+
+        // The node may have accepted the transaction before these.
+        assert!(!is_unreached(&io::Error::from(io::ErrorKind::TimedOut)));
+        assert!(!is_unreached(&io::Error::from(
+            io::ErrorKind::ConnectionReset
+        )));
+        assert!(!is_unreached(&io::Error::other(
+            "status: DeadlineExceeded, message: \"timeout expired\""
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_retries_transient_failures() {
+        let (addr, connections) = fake_server(vec![None, None, Some("OK")]).await;
+
+        let reply = retry(&FAST, "Call", || call(addr)).await.unwrap();
+        assert_eq!(reply, "OK");
+        assert_eq!(connections.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let (addr, connections) = fake_server(vec![None]).await;
+
+        let err = retry(&FAST, "Call", || call(addr)).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        assert_eq!(connections.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_permanent_failures_are_not_retried() {
+        let (addr, connections) = fake_server(vec![Some("ERR invalid request")]).await;
+
+        let err = retry(&FAST, "Call", || call(addr)).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_client_retries_unreachable_node() {
+        let (addr, connections) = fake_server(vec![None]).await;
+        let dir = tempfile::tempdir().unwrap();
+        let profile = NetworkProfile {
+            rpc_url: format!("http://{addr}"),
+            ..NetworkProfile::builtin("local").unwrap()
+        };
+
+        let mut client = setup_client::<PragmaKeyStore>(
+            profile.endpoint().unwrap(),
+            None,
+            1_000,
+            Some(dir.path().join("store.sqlite3")),
+            None,
+        )
+        .await
+        .unwrap();
+        let before = connections.load(Ordering::SeqCst);
+
+        let err = sync_state_with_retry(&mut client, &FAST).await.unwrap_err();
+        assert!(is_transient(&err), "{err:?}");
+        assert!(connections.load(Ordering::SeqCst) - before >= 4);
+    }
+}
@@ -0,0 +1,9 @@
+//! Typed reads of the Pragma oracle for Rust services.
+//!
+//! [`OracleReader`] wraps a Miden client and performs the same reads as the
+//! `pm-oracle` CLI (publisher registry, medians, publisher entries) without
+//! shelling out to it.
+
+pub mod reader;
+
+pub use reader::*;
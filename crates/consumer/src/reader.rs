@@ -0,0 +1,843 @@
+use std::collections::BTreeMap;
+
+use anyhow::Context;
+use miden_client::account::{Account, AccountId};
+use miden_client::rpc::domain::account::AccountStorageRequirements;
+use miden_client::transaction::ForeignAccount;
+use miden_client::{auth::TransactionAuthenticator, Client, Felt, Word, ZERO};
+use miden_protocol::account::{StorageMapKey, StorageSlotName};
+use miden_protocol::vm::AdviceInputs;
+use miden_standards::code_builder::CodeBuilder;
+use pm_accounts::oracle::get_oracle_component_library;
+use pm_accounts::utils::word_to_masm;
+use pm_types::{
+    decode_value, AssetInfoWord, AssetKey, Entry, FeedConfig, FeedId, Pair, PublisherRegistryWord,
+};
+use serde::{Deserialize, Serialize};
+
+const NEXT_PUBLISHER_INDEX_SLOT: &str = "pragma::oracle::next_publisher_index";
+const PUBLISHERS_SLOT: &str = "pragma::oracle::publishers";
+const PUBLISHER_ENTRIES_SLOT: &str = "pragma::publisher::entries";
+const PUBLISHER_HISTORY_SLOT: &str = "pragma::publisher::history";
+const PUBLISHER_HISTORY_COUNTS_SLOT: &str = "pragma::publisher::history_counts";
+const NEXT_ASSET_INDEX_SLOT: &str = "pragma::oracle::next_asset_index";
+const ASSETS_SLOT: &str = "pragma::oracle::assets";
+const ASSET_INFOS_SLOT: &str = "pragma::oracle::asset_infos";
+const FEED_CONFIGS_SLOT: &str = "pragma::oracle::feed_configs";
+const PUBLISHER_FEEDS_SLOT: &str = "pragma::publisher::feeds";
+const PUBLISHER_RANDOMNESS_SLOT: &str = "pragma::publisher::randomness";
+
+/// First index of the publisher registry, see `PUBLISHERS_STORAGE_SLOT` in oracle.masm.
+const FIRST_PUBLISHER_INDEX: u64 = 2;
+
+/// Decimals of the prices returned by `get_cross_median`, see `CROSS_DECIMALS` in oracle.masm.
+pub const CROSS_PRICE_DECIMALS: u32 = 8;
+
+/// Decimals of the volatilities returned by `get_volatility`, see `VOLATILITY_DECIMALS` in oracle.masm.
+pub const VOLATILITY_DECIMALS: u32 = 8;
+
+/// Number of entries a publisher keeps in the history of an asset, see `HISTORY_LEN` in publisher.masm.
+pub const PUBLISHER_HISTORY_LEN: u64 = 16;
+
+/// Largest window `get_volatility` accepts, see `MAX_VOLATILITY_WINDOW` in oracle.masm.
+pub const MAX_VOLATILITY_WINDOW: u32 = 15;
+
+/// A slot of the oracle publisher registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublisherSlot {
+    /// Index of the slot in the registry map, starting at 2.
+    pub index: u64,
+    /// Id of the registered publisher, `None` once removed from the oracle.
+    pub publisher_id: Option<AccountId>,
+}
+
+/// An asset registered in the oracle asset registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisteredAsset {
+    pub asset_key: AssetKey,
+    pub pair: Pair,
+    pub decimals: u32,
+}
+
+/// Median of an asset as returned by the oracle `get_median` procedure.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Median {
+    pub faucet_id: String,
+    /// Whether at least one fresh entry exists for the asset.
+    pub is_tracked: bool,
+    /// Median price, 0 when the asset is not tracked.
+    pub price: u64,
+    /// Amount passed to `get_median`, which returns it along with the median.
+    pub amount: u64,
+}
+
+/// Price of a base asset in units of a quote asset, as returned by the oracle
+/// `get_cross_median` procedure.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrossMedian {
+    pub base_faucet_id: String,
+    pub quote_faucet_id: String,
+    /// Whether both the base and the quote medians are tracked.
+    pub is_tracked: bool,
+    /// Cross price with [`CROSS_PRICE_DECIMALS`] decimals, 0 when not tracked.
+    pub price: u64,
+}
+
+/// Realized volatility of an asset as returned by the oracle `get_volatility`
+/// procedure.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Volatility {
+    pub faucet_id: String,
+    /// Number of returns the volatility is computed over, per publisher.
+    pub window: u32,
+    /// Whether at least one publisher has a fresh history for the asset.
+    pub is_tracked: bool,
+    /// Volatility per return with [`VOLATILITY_DECIMALS`] decimals, 0 when not tracked.
+    pub volatility: u64,
+}
+
+/// Aggregated value of a generic feed as returned by the oracle `get_feed`
+/// procedure, decoded with the feed config.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedAggregate {
+    pub feed_id: String,
+    /// Kind of the feed, e.g. `funding-rate`.
+    pub kind: String,
+    /// Aggregation mode of the feed, e.g. `median`.
+    pub mode: String,
+    /// Whether at least one fresh value exists for the feed.
+    pub is_tracked: bool,
+    /// Aggregated value, 0 when the feed is not tracked.
+    pub value: i64,
+    pub decimals: u32,
+    /// Number of values the aggregate is computed from.
+    pub nb_of_values: u64,
+}
+
+/// Randomness of a round as returned by the oracle `get_randomness` procedure.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Randomness {
+    pub round: u64,
+    /// Number of publishers whose revealed value is combined in the randomness.
+    pub nb_of_reveals: u64,
+    /// Combined randomness as a hex word, the zero word when nothing was revealed.
+    pub value: String,
+}
+
+/// Reads the oracle state and runs its read procedures through a Miden client.
+///
+/// Every read first refreshes the oracle account from the network, so results
+/// always reflect the latest committed state.
+///
+/// The reads sign nothing, so the client can hold any keystore.
+pub struct OracleReader<'a, AUTH> {
+    client: &'a mut Client<AUTH>,
+    oracle_id: AccountId,
+}
+
+impl<'a, AUTH> OracleReader<'a, AUTH>
+where
+    AUTH: TransactionAuthenticator + Sync + 'static,
+{
+    pub fn new(client: &'a mut Client<AUTH>, oracle_id: AccountId) -> Self {
+        Self { client, oracle_id }
+    }
+
+    pub fn oracle_id(&self) -> AccountId {
+        self.oracle_id
+    }
+
+    /// Returns every slot of the publisher registry, removed ones included.
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if the oracle account cannot be imported from
+    /// the network or its storage is not laid out as expected.
+    pub async fn publishers(&mut self) -> anyhow::Result<Vec<PublisherSlot>> {
+        let oracle = self.refresh_oracle().await?;
+        read_publisher_registry(&oracle)
+    }
+
+    /// Returns the ids of the publishers currently registered.
+    pub async fn active_publishers(&mut self) -> anyhow::Result<Vec<AccountId>> {
+        Ok(self
+            .publishers()
+            .await?
+            .into_iter()
+            .filter_map(|slot| slot.publisher_id)
+            .collect())
+    }
+
+    /// Returns the assets registered in the oracle asset registry, in their
+    /// registration order.
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if the oracle account cannot be imported from
+    /// the network or its storage is not laid out as expected.
+    pub async fn assets(&mut self) -> anyhow::Result<Vec<RegisteredAsset>> {
+        let oracle = self.refresh_oracle().await?;
+        read_asset_registry(&oracle)
+    }
+
+    /// Computes the median of `asset_key`, passing `amount` to `get_median`.
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - An account cannot be imported from the network
+    /// - The `get_median` program fails to execute
+    pub async fn median(&mut self, asset_key: AssetKey, amount: u64) -> anyhow::Result<Median> {
+        let mut medians = self.medians(&[asset_key], amount).await?;
+        Ok(medians.remove(0))
+    }
+
+    /// Computes the median of several assets, passing the same `amount` to
+    /// `get_median` for each of them.
+    ///
+    /// The oracle and its publishers are fetched once for all the assets, only
+    /// the `get_median` program is run per asset. Fails on the first asset that
+    /// cannot be read.
+    pub async fn medians(
+        &mut self,
+        asset_keys: &[AssetKey],
+        amount: u64,
+    ) -> anyhow::Result<Vec<Median>> {
+        let publisher_ids = self.import_active_publishers().await?;
+
+        let mut medians = Vec::with_capacity(asset_keys.len());
+        for &asset_key in asset_keys {
+            let foreign_accounts = publisher_ids
+                .iter()
+                .map(|publisher_id| entries_foreign_account(*publisher_id, &[asset_key]))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+
+            let tx_script_code = format!(
+                "
+                use oracle_component::oracle_module
+                use miden::core::sys
+
+                begin
+                    push.0.{amount}.{suffix}.{prefix}
+                    call.oracle_module::get_median
+                    exec.sys::truncate_stack
+                end
+                ",
+                suffix = asset_key.suffix(),
+                prefix = asset_key.prefix(),
+            );
+            let output_stack = self
+                .execute_oracle_program(tx_script_code, foreign_accounts)
+                .await
+                .with_context(|| format!("Failed to execute median for faucet_id: {asset_key}"))?;
+
+            medians.push(decode_median(asset_key, &output_stack)?);
+        }
+
+        Ok(medians)
+    }
+
+    /// Computes the price of `base` in units of `quote` from their medians,
+    /// e.g. ETH/BTC from the ETH/USD and BTC/USD medians.
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - An account cannot be imported from the network
+    /// - The `get_cross_median` program fails to execute, e.g. because one of
+    ///   the assets is not in the oracle asset registry
+    pub async fn cross_median(
+        &mut self,
+        base: AssetKey,
+        quote: AssetKey,
+    ) -> anyhow::Result<CrossMedian> {
+        let mut cross_medians = self.cross_medians(&[(base, quote)]).await?;
+        Ok(cross_medians.remove(0))
+    }
+
+    /// Computes several cross prices, given as `(base, quote)` asset keys.
+    ///
+    /// Like [`OracleReader::medians`], the oracle and its publishers are
+    /// fetched once for all the pairs. Fails on the first pair that cannot be
+    /// read.
+    pub async fn cross_medians(
+        &mut self,
+        pairs: &[(AssetKey, AssetKey)],
+    ) -> anyhow::Result<Vec<CrossMedian>> {
+        let publisher_ids = self.import_active_publishers().await?;
+
+        let mut cross_medians = Vec::with_capacity(pairs.len());
+        for &(base, quote) in pairs {
+            let foreign_accounts = publisher_ids
+                .iter()
+                .map(|publisher_id| entries_foreign_account(*publisher_id, &[base, quote]))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+
+            let tx_script_code = format!(
+                "
+                use oracle_component::oracle_module
+                use miden::core::sys
+
+                begin
+                    push.{quote_suffix}.{quote_prefix}.{base_suffix}.{base_prefix}
+                    call.oracle_module::get_cross_median
+                    exec.sys::truncate_stack
+                end
+                ",
+                quote_suffix = quote.suffix(),
+                quote_prefix = quote.prefix(),
+                base_suffix = base.suffix(),
+                base_prefix = base.prefix(),
+            );
+            let output_stack = self
+                .execute_oracle_program(tx_script_code, foreign_accounts)
+                .await
+                .with_context(|| format!("Failed to execute cross median for {base}/{quote}"))?;
+
+            // Stack output: [is_tracked, cross_price]
+            if output_stack.len() < 2 {
+                return Err(anyhow::anyhow!(
+                    "Invalid output for {base}/{quote}: expected [is_tracked, cross_price]"
+                ));
+            }
+            cross_medians.push(CrossMedian {
+                base_faucet_id: base.to_string(),
+                quote_faucet_id: quote.to_string(),
+                is_tracked: output_stack[0].as_canonical_u64() != 0,
+                price: output_stack[1].as_canonical_u64(),
+            });
+        }
+
+        Ok(cross_medians)
+    }
+
+    /// Computes the realized volatility of `asset_key` over its last `window`
+    /// returns, from the entry histories of the publishers.
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - `window` is 0 or above [`MAX_VOLATILITY_WINDOW`]
+    /// - An account cannot be imported from the network
+    /// - The `get_volatility` program fails to execute
+    pub async fn volatility(
+        &mut self,
+        asset_key: AssetKey,
+        window: u32,
+    ) -> anyhow::Result<Volatility> {
+        if window == 0 || window > MAX_VOLATILITY_WINDOW {
+            anyhow::bail!("Invalid window {window}: expected 1 to {MAX_VOLATILITY_WINDOW} returns");
+        }
+        let publisher_ids = self.import_active_publishers().await?;
+        let foreign_accounts = publisher_ids
+            .iter()
+            .map(|publisher_id| history_foreign_account(*publisher_id, asset_key))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let tx_script_code = format!(
+            "
+            use oracle_component::oracle_module
+            use miden::core::sys
+
+            begin
+                push.0.{window}.{suffix}.{prefix}
+                call.oracle_module::get_volatility
+                exec.sys::truncate_stack
+            end
+            ",
+            suffix = asset_key.suffix(),
+            prefix = asset_key.prefix(),
+        );
+        let output_stack = self
+            .execute_oracle_program(tx_script_code, foreign_accounts)
+            .await
+            .with_context(|| format!("Failed to execute volatility for faucet_id: {asset_key}"))?;
+
+        // Stack output: [is_tracked, volatility]
+        if output_stack.len() < 2 {
+            return Err(anyhow::anyhow!(
+                "Invalid output for {asset_key}: expected [is_tracked, volatility]"
+            ));
+        }
+        Ok(Volatility {
+            faucet_id: asset_key.to_string(),
+            window,
+            is_tracked: output_stack[0].as_canonical_u64() != 0,
+            volatility: output_stack[1].as_canonical_u64(),
+        })
+    }
+
+    /// Returns the config of `feed_id` in the oracle, the default median of
+    /// prices when the feed is not configured.
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if the oracle account cannot be imported from
+    /// the network or the stored config cannot be decoded.
+    pub async fn feed_config(&mut self, feed_id: FeedId) -> anyhow::Result<FeedConfig> {
+        let oracle = self.refresh_oracle().await?;
+        read_feed_config(&oracle, feed_id)
+    }
+
+    /// Aggregates the values the publishers published for `feed_id`, with the
+    /// aggregation mode of its config.
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - An account cannot be imported from the network
+    /// - The `get_feed` program fails to execute
+    /// - The aggregated value does not fit the kind of the feed
+    pub async fn feed(&mut self, feed_id: FeedId) -> anyhow::Result<FeedAggregate> {
+        let config = self.feed_config(feed_id).await?;
+        let publisher_ids = self.import_active_publishers().await?;
+        let foreign_accounts = publisher_ids
+            .iter()
+            .map(|publisher_id| feeds_foreign_account(*publisher_id, feed_id))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let tx_script_code = format!(
+            "
+            use oracle_component::oracle_module
+            use miden::core::sys
+
+            begin
+                push.{feed_id}
+                call.oracle_module::get_feed
+                exec.sys::truncate_stack
+            end
+            ",
+            feed_id = word_to_masm(feed_id.to_word()),
+        );
+        let output_stack = self
+            .execute_oracle_program(tx_script_code, foreign_accounts)
+            .await
+            .with_context(|| format!("Failed to execute get_feed for feed: {feed_id}"))?;
+
+        // Stack output: [is_tracked, value, nb_of_values]
+        if output_stack.len() < 3 {
+            return Err(anyhow::anyhow!(
+                "Invalid output for {feed_id}: expected [is_tracked, value, nb_of_values]"
+            ));
+        }
+        let is_tracked = output_stack[0].as_canonical_u64() != 0;
+        let value = if is_tracked {
+            decode_value(config.kind, output_stack[1].as_canonical_u64())?
+        } else {
+            0
+        };
+        Ok(FeedAggregate {
+            feed_id: feed_id.to_string(),
+            kind: config.kind.to_string(),
+            mode: config.mode.to_string(),
+            is_tracked,
+            value,
+            decimals: config.decimals,
+            nb_of_values: output_stack[2].as_canonical_u64(),
+        })
+    }
+
+    /// Combines the randomness the publishers revealed for `round`.
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - An account cannot be imported from the network
+    /// - The `get_randomness` program fails to execute
+    pub async fn randomness(&mut self, round: u64) -> anyhow::Result<Randomness> {
+        let publisher_ids = self.import_active_publishers().await?;
+        let foreign_accounts = publisher_ids
+            .iter()
+            .map(|publisher_id| randomness_foreign_account(*publisher_id, round))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let tx_script_code = format!(
+            "
+            use oracle_component::oracle_module
+            use miden::core::sys
+
+            begin
+                push.{round}
+                call.oracle_module::get_randomness
+                exec.sys::truncate_stack
+            end
+            "
+        );
+        let output_stack = self
+            .execute_oracle_program(tx_script_code, foreign_accounts)
+            .await
+            .with_context(|| format!("Failed to execute get_randomness for round: {round}"))?;
+
+        // Stack output: [nb_of_reveals, RANDOMNESS]
+        if output_stack.len() < 5 {
+            return Err(anyhow::anyhow!(
+                "Invalid output for round {round}: expected [nb_of_reveals, RANDOMNESS]"
+            ));
+        }
+        let value: Word = [
+            output_stack[1],
+            output_stack[2],
+            output_stack[3],
+            output_stack[4],
+        ]
+        .into();
+        Ok(Randomness {
+            round,
+            nb_of_reveals: output_stack[0].as_canonical_u64(),
+            value: value.to_hex(),
+        })
+    }
+
+    /// Reads the entry `publisher_id` published for `asset_key`, through the
+    /// oracle `get_entry` procedure.
+    ///
+    /// # Errors
+    ///
+    /// This function can fail if:
+    /// - An account cannot be imported from the network
+    /// - The `get_entry` program fails to execute
+    pub async fn entry(
+        &mut self,
+        publisher_id: AccountId,
+        asset_key: AssetKey,
+    ) -> anyhow::Result<Entry> {
+        self.client.import_account_by_id(publisher_id).await?;
+        self.client.import_account_by_id(self.oracle_id).await?;
+        self.client.sync_state().await?;
+
+        let tx_script_code = format!(
+            "
+            use oracle_component::oracle_module
+            use miden::core::sys
+
+            begin
+                push.{faucet_id}
+                push.0.0
+                push.{account_id_suffix} push.{account_id_prefix}
+                call.oracle_module::get_entry
+                exec.sys::truncate_stack
+            end
+            ",
+            faucet_id = word_to_masm(asset_key.to_word()),
+            account_id_prefix = publisher_id.prefix().as_u64(),
+            account_id_suffix = publisher_id.suffix(),
+        );
+        let foreign_account = entries_foreign_account(publisher_id, &[asset_key])?;
+        let output_stack = self
+            .execute_oracle_program(tx_script_code, vec![foreign_account])
+            .await?;
+
+        decode_entry(asset_key, &output_stack)
+    }
+
+    /// Imports the active publishers so that their entries can be read through
+    /// Foreign Procedure Invocation.
+    async fn import_active_publishers(&mut self) -> anyhow::Result<Vec<AccountId>> {
+        let publisher_ids = self.active_publishers().await?;
+        for publisher_id in &publisher_ids {
+            self.client
+                .import_account_by_id(*publisher_id)
+                .await
+                .with_context(|| format!("Failed to import publisher {publisher_id}"))?;
+        }
+        self.client.sync_state().await?;
+        Ok(publisher_ids)
+    }
+
+    /// Imports the oracle account to get its latest storage.
+    ///
+    /// `sync_state` only refreshes accounts already in the tracked set, so on a
+    /// fresh store the oracle has to be imported first.
+    async fn refresh_oracle(&mut self) -> anyhow::Result<Account> {
+        self.client.import_account_by_id(self.oracle_id).await?;
+        self.client.sync_state().await?;
+        self.client
+            .get_account(self.oracle_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Oracle account {} not found", self.oracle_id))
+    }
+
+    async fn execute_oracle_program(
+        &mut self,
+        tx_script_code: String,
+        foreign_accounts: Vec<ForeignAccount>,
+    ) -> anyhow::Result<Vec<Felt>> {
+        let oracle_lib = get_oracle_component_library();
+        let script = CodeBuilder::default()
+            .with_dynamically_linked_library(&oracle_lib)
+            .map_err(|e| anyhow::anyhow!("Error while setting up the component library: {e:?}"))?
+            .compile_tx_script(tx_script_code)
+            .map_err(|e| anyhow::anyhow!("Error while compiling the script: {e:?}"))?;
+
+        let foreign_accounts: BTreeMap<AccountId, ForeignAccount> = foreign_accounts
+            .into_iter()
+            .map(|fa| (fa.account_id(), fa))
+            .collect();
+        let output_stack = self
+            .client
+            .execute_program(
+                self.oracle_id,
+                script,
+                AdviceInputs::default(),
+                foreign_accounts,
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("execute_program error: {e:?}"))?;
+
+        Ok(output_stack.to_vec())
+    }
+}
+
+/// Decodes the output stack of `get_median`: [is_tracked, median_price, amount].
+fn decode_median(asset_key: AssetKey, output_stack: &[Felt]) -> anyhow::Result<Median> {
+    if output_stack.len() < 3 {
+        return Err(anyhow::anyhow!(
+            "Invalid output for {asset_key}: expected [is_tracked, median_price, amount]"
+        ));
+    }
+    Ok(Median {
+        faucet_id: asset_key.to_string(),
+        is_tracked: output_stack[0].as_canonical_u64() != 0,
+        price: output_stack[1].as_canonical_u64(),
+        amount: output_stack[2].as_canonical_u64(),
+    })
+}
+
+/// Decodes the output stack of `get_entry`: the stored entry
+/// [timestamp, decimals, price, 0].
+fn decode_entry(asset_key: AssetKey, output_stack: &[Felt]) -> anyhow::Result<Entry> {
+    if output_stack.len() < 3 {
+        return Err(anyhow::anyhow!(
+            "Invalid output: expected [timestamp, decimals, price, 0]"
+        ));
+    }
+    Ok(Entry {
+        faucet_id: asset_key.to_string(),
+        price: output_stack[2].as_canonical_u64(),
+        decimals: output_stack[1].as_canonical_u64() as u32,
+        timestamp: output_stack[0].as_canonical_u64(),
+    })
+}
+
+/// Walks the publisher registry of an oracle account.
+///
+/// A slot zeroed out by `remove_publisher` decodes to `None`.
+fn read_publisher_registry(oracle: &Account) -> anyhow::Result<Vec<PublisherSlot>> {
+    let storage = oracle.storage();
+
+    let next_index_slot = StorageSlotName::new(NEXT_PUBLISHER_INDEX_SLOT)
+        .map_err(|e| anyhow::anyhow!("Invalid storage slot name: {e:?}"))?;
+    let next_index = storage
+        .get_item(&next_index_slot)
+        .context("Unable to retrieve publisher count")?[0]
+        .as_canonical_u64();
+
+    let publishers_slot = StorageSlotName::new(PUBLISHERS_SLOT)
+        .map_err(|e| anyhow::anyhow!("Invalid storage slot name: {e:?}"))?;
+    (FIRST_PUBLISHER_INDEX..next_index)
+        .map(|index| -> anyhow::Result<PublisherSlot> {
+            let key: [Felt; 4] = [Felt::new(index)?, ZERO, ZERO, ZERO];
+            let publisher_word = storage
+                .get_map_item(&publishers_slot, key.into())
+                .with_context(|| format!("Failed to retrieve publisher at index {index}"))?;
+            let publisher_id = PublisherRegistryWord::from_word(publisher_word)
+                .publisher_id()
+                .with_context(|| format!("Failed to decode publisher at index {index}"))?;
+            Ok(PublisherSlot {
+                index,
+                publisher_id,
+            })
+        })
+        .collect()
+}
+
+/// Walks the asset registry of an oracle account.
+fn read_asset_registry(oracle: &Account) -> anyhow::Result<Vec<RegisteredAsset>> {
+    let storage = oracle.storage();
+
+    let next_index_slot = StorageSlotName::new(NEXT_ASSET_INDEX_SLOT)
+        .map_err(|e| anyhow::anyhow!("Invalid storage slot name: {e:?}"))?;
+    let next_index = storage
+        .get_item(&next_index_slot)
+        .context("Unable to retrieve asset count")?[0]
+        .as_canonical_u64();
+
+    let assets_slot = StorageSlotName::new(ASSETS_SLOT)
+        .map_err(|e| anyhow::anyhow!("Invalid storage slot name: {e:?}"))?;
+    let asset_infos_slot = StorageSlotName::new(ASSET_INFOS_SLOT)
+        .map_err(|e| anyhow::anyhow!("Invalid storage slot name: {e:?}"))?;
+    (0..next_index)
+        .map(|index| -> anyhow::Result<RegisteredAsset> {
+            let key: [Felt; 4] = [Felt::new(index)?, ZERO, ZERO, ZERO];
+            let asset_key_word = storage
+                .get_map_item(&assets_slot, key.into())
+                .with_context(|| format!("Failed to retrieve asset at index {index}"))?;
+            let asset_key = AssetKey::from_storage_key(asset_key_word)
+                .with_context(|| format!("Failed to decode asset at index {index}"))?;
+            let info = AssetInfoWord::from_word(
+                storage
+                    .get_map_item(&asset_infos_slot, asset_key_word)
+                    .with_context(|| format!("Failed to retrieve asset {asset_key}"))?,
+            );
+            Ok(RegisteredAsset {
+                asset_key,
+                pair: info
+                    .pair()
+                    .with_context(|| format!("Failed to decode the name of asset {asset_key}"))?,
+                decimals: info.decimals()?,
+            })
+        })
+        .collect()
+}
+
+/// Reads the config of a feed from an oracle account.
+fn read_feed_config(oracle: &Account, feed_id: FeedId) -> anyhow::Result<FeedConfig> {
+    let feed_configs_slot = StorageSlotName::new(FEED_CONFIGS_SLOT)
+        .map_err(|e| anyhow::anyhow!("Invalid storage slot name: {e:?}"))?;
+    let config_word = oracle
+        .storage()
+        .get_map_item(&feed_configs_slot, feed_id.to_storage_key())
+        .with_context(|| format!("Failed to retrieve the config of feed {feed_id}"))?;
+    FeedConfig::from_word(config_word)
+        .with_context(|| format!("Failed to decode the config of feed {feed_id}"))
+}
+
+/// Foreign account of a publisher, with the history of `asset_key` required.
+fn history_foreign_account(
+    publisher_id: AccountId,
+    asset_key: AssetKey,
+) -> anyhow::Result<ForeignAccount> {
+    let history_slot = StorageSlotName::new(PUBLISHER_HISTORY_SLOT)
+        .map_err(|e| anyhow::anyhow!("Invalid storage slot name: {e:?}"))?;
+    let history_counts_slot = StorageSlotName::new(PUBLISHER_HISTORY_COUNTS_SLOT)
+        .map_err(|e| anyhow::anyhow!("Invalid storage slot name: {e:?}"))?;
+    // Keys in the same pushed form as the entries keys.
+    let history_keys = (0..PUBLISHER_HISTORY_LEN)
+        .map(|position| -> anyhow::Result<StorageMapKey> {
+            let key: [Felt; 4] = [
+                ZERO,
+                Felt::new(position)?,
+                Felt::new(asset_key.suffix())?,
+                Felt::new(asset_key.prefix())?,
+            ];
+            Ok(StorageMapKey::new(key.into()))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    Ok(ForeignAccount::public(
+        publisher_id,
+        AccountStorageRequirements::new([
+            (history_slot, history_keys.as_slice()),
+            (
+                history_counts_slot,
+                &[StorageMapKey::new(asset_key.to_word())],
+            ),
+        ]),
+    )?)
+}
+
+/// Foreign account of a publisher, with the entries of `asset_keys` required.
+fn entries_foreign_account(
+    publisher_id: AccountId,
+    asset_keys: &[AssetKey],
+) -> anyhow::Result<ForeignAccount> {
+    let entries_slot = StorageSlotName::new(PUBLISHER_ENTRIES_SLOT)
+        .map_err(|e| anyhow::anyhow!("Invalid storage slot name: {e:?}"))?;
+    let map_keys: Vec<StorageMapKey> = asset_keys
+        .iter()
+        .map(|asset_key| StorageMapKey::new(asset_key.to_word()))
+        .collect();
+    Ok(ForeignAccount::public(
+        publisher_id,
+        AccountStorageRequirements::new([(entries_slot, map_keys.as_slice())]),
+    )?)
+}
+
+/// Foreign account of a publisher, with the value of `feed_id` required.
+fn feeds_foreign_account(
+    publisher_id: AccountId,
+    feed_id: FeedId,
+) -> anyhow::Result<ForeignAccount> {
+    let feeds_slot = StorageSlotName::new(PUBLISHER_FEEDS_SLOT)
+        .map_err(|e| anyhow::anyhow!("Invalid storage slot name: {e:?}"))?;
+    let map_keys = [StorageMapKey::new(feed_id.to_word())];
+    Ok(ForeignAccount::public(
+        publisher_id,
+        AccountStorageRequirements::new([(feeds_slot, map_keys.as_slice())]),
+    )?)
+}
+
+/// Foreign account of a publisher, with its randomness of `round` required.
+fn randomness_foreign_account(
+    publisher_id: AccountId,
+    round: u64,
+) -> anyhow::Result<ForeignAccount> {
+    let randomness_slot = StorageSlotName::new(PUBLISHER_RANDOMNESS_SLOT)
+        .map_err(|e| anyhow::anyhow!("Invalid storage slot name: {e:?}"))?;
+    let round_key: Word = [ZERO, ZERO, ZERO, Felt::new(round)?].into();
+    let map_keys = [StorageMapKey::new(round_key)];
+    Ok(ForeignAccount::public(
+        publisher_id,
+        AccountStorageRequirements::new([(randomness_slot, map_keys.as_slice())]),
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn felts(values: &[u64]) -> Vec<Felt> {
+        values.iter().map(|v| Felt::new(*v).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_decode_median() {
+        let asset_key: AssetKey = "1:0".parse().unwrap();
+        let median = decode_median(asset_key, &felts(&[1, 76_436_215_000, 3, 0])).unwrap();
+        assert_eq!(
+            median,
+            Median {
+                faucet_id: "1:0".to_string(),
+                is_tracked: true,
+                price: 76_436_215_000,
+                amount: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_untracked_median() {
+        let asset_key: AssetKey = "2:0".parse().unwrap();
+        let median = decode_median(asset_key, &felts(&[0, 0, 5])).unwrap();
+        assert!(!median.is_tracked);
+        assert_eq!(median.price, 0);
+        assert_eq!(median.amount, 5);
+    }
+
+    #[test]
+    fn test_decode_median_rejects_a_short_stack() {
+        let asset_key: AssetKey = "1:0".parse().unwrap();
+        assert!(decode_median(asset_key, &felts(&[1, 76_436_215_000])).is_err());
+    }
+
+    #[test]
+    fn test_decode_entry() {
+        let asset_key: AssetKey = "1:0".parse().unwrap();
+        let entry =
+            decode_entry(asset_key, &felts(&[1_700_000_000, 6, 68_199_000_000, 0])).unwrap();
+        assert_eq!(
+            entry,
+            Entry {
+                faucet_id: "1:0".to_string(),
+                price: 68_199_000_000,
+                decimals: 6,
+                timestamp: 1_700_000_000,
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_entry_rejects_a_short_stack() {
+        let asset_key: AssetKey = "1:0".parse().unwrap();
+        assert!(decode_entry(asset_key, &felts(&[1_700_000_000, 6])).is_err());
+    }
+}
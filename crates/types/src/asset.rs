@@ -0,0 +1,256 @@
+use std::fmt;
+use std::str::FromStr;
+
+use miden_client::{account::AccountId, Felt, Word, ZERO};
+
+use crate::{Pair, TryIntoDecimalFelts};
+
+/// Key identifying an asset in the publisher entries and the oracle.
+///
+/// An asset is identified by the prefix and suffix of its faucet id. It can be
+/// written as `"PREFIX:SUFFIX"` (e.g. `"1:0"` for BTC/USD) or as a real Miden
+/// faucet account id, in hex or bech32.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AssetKey {
+    prefix: u64,
+    suffix: u64,
+}
+
+impl AssetKey {
+    /// # Errors
+    ///
+    /// Fails if the prefix or the suffix is not a valid field element.
+    pub fn new(prefix: u64, suffix: u64) -> anyhow::Result<Self> {
+        Felt::new(prefix).map_err(|e| anyhow::anyhow!("Invalid asset key prefix {prefix}: {e}"))?;
+        Felt::new(suffix).map_err(|e| anyhow::anyhow!("Invalid asset key suffix {suffix}: {e}"))?;
+        Ok(Self { prefix, suffix })
+    }
+
+    pub fn prefix(&self) -> u64 {
+        self.prefix
+    }
+
+    pub fn suffix(&self) -> u64 {
+        self.suffix
+    }
+
+    /// Word pushed by the scripts before calling into the publisher or the
+    /// oracle: `[0, 0, suffix, prefix]`.
+    pub fn to_word(&self) -> Word {
+        [ZERO, ZERO, self.suffix_felt(), self.prefix_felt()].into()
+    }
+
+    /// Decodes a word built by [`AssetKey::to_word`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if the word is not `[0, 0, suffix, prefix]`.
+    pub fn from_word(word: Word) -> anyhow::Result<Self> {
+        let [padding_0, padding_1, suffix, prefix]: [Felt; 4] = word.into();
+        if padding_0 != ZERO || padding_1 != ZERO {
+            anyhow::bail!("Invalid asset key word {word:?}: expected [0, 0, suffix, prefix]");
+        }
+        Ok(Self {
+            prefix: prefix.as_canonical_u64(),
+            suffix: suffix.as_canonical_u64(),
+        })
+    }
+
+    /// Key under which the asset is stored in the storage maps:
+    /// `[prefix, suffix, 0, 0]`, i.e. [`AssetKey::to_word`] once pushed.
+    pub fn to_storage_key(&self) -> Word {
+        [self.prefix_felt(), self.suffix_felt(), ZERO, ZERO].into()
+    }
+
+    /// Decodes a key built by [`AssetKey::to_storage_key`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if the word is not `[prefix, suffix, 0, 0]`.
+    pub fn from_storage_key(word: Word) -> anyhow::Result<Self> {
+        let mut elements: [Felt; 4] = word.into();
+        elements.reverse();
+        Self::from_word(elements.into())
+    }
+
+    fn prefix_felt(&self) -> Felt {
+        Felt::new(self.prefix).expect("asset key prefix is validated on construction")
+    }
+
+    fn suffix_felt(&self) -> Felt {
+        Felt::new(self.suffix).expect("asset key suffix is validated on construction")
+    }
+}
+
+/// Word under which the oracle asset registry stores the name and decimals of
+/// an asset: `[name_0, name_1, name_2, decimals]`.
+///
+/// The name is the `BASE/QUOTE` string packed 8 characters per felt (see
+/// [`Pair::to_decimal_felts`]), padded with zeros. A zero word means that the
+/// asset is not registered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AssetInfoWord(Word);
+
+impl AssetInfoWord {
+    /// # Errors
+    ///
+    /// Fails if the pair name does not fit in 3 felts (24 characters).
+    pub fn new(pair: &Pair, decimals: u32) -> anyhow::Result<Self> {
+        let [name_0, name_1, name_2]: [Felt; 3] = pair.try_into_decimal_felts()?;
+        Ok(Self([name_0, name_1, name_2, Felt::from(decimals)].into()))
+    }
+
+    /// Wraps a word as stored in the asset registry.
+    pub fn from_word(word: Word) -> Self {
+        Self(word)
+    }
+
+    /// The word as stored in the asset registry.
+    pub fn word(&self) -> Word {
+        self.0
+    }
+
+    /// Word to push before calling `register_asset` so that it is stored as
+    /// [`AssetInfoWord::word`].
+    pub fn to_push_word(&self) -> Word {
+        let mut elements: [Felt; 4] = self.0.into();
+        elements.reverse();
+        elements.into()
+    }
+
+    /// Returns true if no asset is registered under this word.
+    pub fn is_empty(&self) -> bool {
+        self.0 == Word::default()
+    }
+
+    /// Decodes the pair name.
+    pub fn pair(&self) -> anyhow::Result<Pair> {
+        let [name_0, name_1, name_2, _decimals]: [Felt; 4] = self.0.into();
+        Pair::from_felts([name_0, name_1, name_2, ZERO].into())
+    }
+
+    pub fn decimals(&self) -> anyhow::Result<u32> {
+        let decimals = self.0[3].as_canonical_u64();
+        u32::try_from(decimals).map_err(|_| anyhow::anyhow!("Invalid asset decimals {decimals}"))
+    }
+}
+
+impl From<AccountId> for AssetKey {
+    fn from(faucet_id: AccountId) -> Self {
+        Self {
+            prefix: faucet_id.prefix().as_u64(),
+            suffix: faucet_id.suffix().as_canonical_u64(),
+        }
+    }
+}
+
+impl FromStr for AssetKey {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some((prefix, suffix)) = s.split_once(':') {
+            let prefix = prefix
+                .parse::<u64>()
+                .map_err(|_| anyhow::anyhow!("Invalid asset key prefix: {prefix}"))?;
+            let suffix = suffix
+                .parse::<u64>()
+                .map_err(|_| anyhow::anyhow!("Invalid asset key suffix: {suffix}"))?;
+            return Self::new(prefix, suffix);
+        }
+
+        let faucet_id = if s.starts_with("0x") {
+            AccountId::from_hex(s).map_err(|e| anyhow::anyhow!("Invalid faucet id {s}: {e}"))?
+        } else {
+            AccountId::from_bech32(s)
+                .map(|(_, account_id)| account_id)
+                .map_err(|_| {
+                    anyhow::anyhow!(
+                        "Invalid asset key: {s}. Expected PREFIX:SUFFIX (e.g., 1:0) or a faucet account id"
+                    )
+                })?
+        };
+        // Account ids carry no account type in Miden 0.15, so whether the
+        // account is a faucet can only be told from its on-chain code.
+        Ok(faucet_id.into())
+    }
+}
+
+impl fmt::Display for AssetKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.prefix, self.suffix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_prefix_suffix() {
+        let asset_key: AssetKey = "1:0".parse().unwrap();
+        assert_eq!(asset_key, AssetKey::new(1, 0).unwrap());
+        assert_eq!(asset_key.to_string(), "1:0");
+    }
+
+    #[test]
+    fn test_invalid_strings_are_rejected() {
+        for s in [
+            "",
+            "1",
+            "1:",
+            ":0",
+            "1:0:0",
+            "a:0",
+            "1:-1",
+            "0xzz",
+            "not-an-id",
+        ] {
+            assert!(s.parse::<AssetKey>().is_err(), "{s} should not parse");
+        }
+    }
+
+    #[test]
+    fn test_word_round_trip() {
+        let asset_key = AssetKey::new(6, 42).unwrap();
+        let word = asset_key.to_word();
+        assert_eq!(
+            word,
+            [ZERO, ZERO, Felt::new(42).unwrap(), Felt::new(6).unwrap()].into()
+        );
+        assert_eq!(AssetKey::from_word(word).unwrap(), asset_key);
+    }
+
+    #[test]
+    fn test_storage_key_is_the_reversed_word() {
+        let asset_key = AssetKey::new(6, 42).unwrap();
+        let mut elements: [Felt; 4] = asset_key.to_word().into();
+        elements.reverse();
+        assert_eq!(asset_key.to_storage_key(), elements.into());
+    }
+
+    #[test]
+    fn test_storage_key_round_trip() {
+        let asset_key = AssetKey::new(6, 42).unwrap();
+        assert_eq!(
+            AssetKey::from_storage_key(asset_key.to_storage_key()).unwrap(),
+            asset_key
+        );
+    }
+
+    #[test]
+    fn test_asset_info_word_round_trip() {
+        let pair = Pair::from_str("BITCOIN2/USD").unwrap();
+        let info = AssetInfoWord::new(&pair, 8).unwrap();
+        assert!(!info.is_empty());
+        assert_eq!(info.pair().unwrap(), pair);
+        assert_eq!(info.decimals().unwrap(), 8);
+        assert!(AssetInfoWord::from_word(Word::default()).is_empty());
+    }
+
+    #[test]
+    fn test_non_zero_padding_is_rejected() {
+        let word: Word = [Felt::new(1).unwrap(), ZERO, ZERO, Felt::new(1).unwrap()].into();
+        assert!(AssetKey::from_word(word).is_err());
+    }
+}
@@ -1,28 +1,53 @@
 use std::str::FromStr;
 
+use miden_client::Felt;
+
+/// Maximum length of a currency that can be encoded into a Felt.
+pub const MAX_CURRENCY_LEN: usize = 12;
+
+/// Number of symbols a ticker character can take: `A-Z` and `0-9`.
+pub(crate) const TICKER_SYMBOLS: u64 = 36;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Currency(pub String);
 
 impl Currency {
     pub fn new(currency: &str) -> anyhow::Result<Self> {
-        if !currency.chars().all(|c| c.is_ascii_alphabetic()) {
-            anyhow::bail!("Currency must contain only letters");
+        if currency.is_empty() {
+            anyhow::bail!("Currency must not be empty");
+        }
+        if !currency.chars().all(|c| c.is_ascii_alphanumeric()) {
+            anyhow::bail!("Currency must contain only letters and digits");
         }
         Ok(Self(currency.to_ascii_uppercase()))
     }
 
-    pub fn encode(&self) -> Option<u32> {
-        let mut result: u32 = 0;
-
-        for (i, c) in self.0.chars().enumerate() {
-            let value = match c {
-                'A'..='Z' => (c as u32) - ('A' as u32),
-                _ => return None,
-            };
-            result |= value << (i * 5);
+    /// Encodes the currency as a base-37 number, one digit per character.
+    ///
+    /// Digits are in `1..=36` (see [`ticker_digit`]), so no two currencies
+    /// share an encoding. Returns `None` if the currency is longer than
+    /// [`MAX_CURRENCY_LEN`].
+    pub fn encode(&self) -> Option<u64> {
+        if self.0.len() > MAX_CURRENCY_LEN {
+            return None;
         }
+        encode_digits(self.0.chars().map(ticker_digit), TICKER_SYMBOLS + 1)
+    }
 
-        Some(result)
+    /// Decodes a currency encoded by [`Currency::encode`].
+    pub fn decode(encoded: u64) -> anyhow::Result<Self> {
+        let digits = decode_digits(encoded, TICKER_SYMBOLS + 1)?;
+        if digits.len() > MAX_CURRENCY_LEN {
+            anyhow::bail!("Invalid currency encoding {encoded}: too many characters");
+        }
+        let chars = digits
+            .into_iter()
+            .map(|digit| {
+                ticker_char(digit)
+                    .ok_or_else(|| anyhow::anyhow!("Invalid currency encoding {encoded}"))
+            })
+            .collect::<anyhow::Result<String>>()?;
+        Self::new(&chars)
     }
 }
 
@@ -33,3 +58,114 @@ impl FromStr for Currency {
         Currency::new(s)
     }
 }
+
+impl TryFrom<&Currency> for Felt {
+    type Error = anyhow::Error;
+
+    fn try_from(currency: &Currency) -> anyhow::Result<Self> {
+        let encoded = currency.encode().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Currency {} is longer than {MAX_CURRENCY_LEN} characters",
+                currency.0
+            )
+        })?;
+        Ok(Felt::new(encoded)?)
+    }
+}
+
+impl TryFrom<Felt> for Currency {
+    type Error = anyhow::Error;
+
+    fn try_from(felt: Felt) -> anyhow::Result<Self> {
+        Currency::decode(felt.as_canonical_u64())
+    }
+}
+
+/// Digit of an uppercase ticker character: `A-Z` map to `1..=26` and `0-9` to
+/// `27..=36`. Zero is never used, so it can mark the end of an encoding.
+pub(crate) fn ticker_digit(c: char) -> Option<u64> {
+    match c {
+        'A'..='Z' => Some(c as u64 - 'A' as u64 + 1),
+        '0'..='9' => Some(c as u64 - '0' as u64 + 27),
+        _ => None,
+    }
+}
+
+/// Inverse of [`ticker_digit`].
+pub(crate) fn ticker_char(digit: u64) -> Option<char> {
+    match digit {
+        1..=26 => char::from_u32(('A' as u64 + digit - 1) as u32),
+        27..=36 => char::from_u32(('0' as u64 + digit - 27) as u32),
+        _ => None,
+    }
+}
+
+/// Packs non-zero digits into a number, the first digit being the least
+/// significant one. Returns `None` on a zero digit or on overflow.
+pub(crate) fn encode_digits(digits: impl Iterator<Item = Option<u64>>, base: u64) -> Option<u64> {
+    let mut encoded: u64 = 0;
+    let mut weight: u64 = 1;
+    for digit in digits {
+        let digit = digit.filter(|digit| (1..base).contains(digit))?;
+        encoded = encoded.checked_add(digit.checked_mul(weight)?)?;
+        weight = weight.saturating_mul(base);
+    }
+    Some(encoded)
+}
+
+/// Inverse of [`encode_digits`].
+pub(crate) fn decode_digits(mut encoded: u64, base: u64) -> anyhow::Result<Vec<u64>> {
+    let mut digits = Vec::new();
+    while encoded != 0 {
+        let digit = encoded % base;
+        if digit == 0 {
+            anyhow::bail!("Invalid encoding: unexpected zero digit");
+        }
+        digits.push(digit);
+        encoded /= base;
+    }
+    Ok(digits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_currency_accepts_digits() {
+        let currency = Currency::new("1inch").unwrap();
+        assert_eq!(currency.0, "1INCH");
+        assert_eq!(
+            Currency::decode(currency.encode().unwrap()).unwrap(),
+            currency
+        );
+    }
+
+    #[test]
+    fn test_invalid_currencies_are_rejected() {
+        assert!(Currency::new("").is_err());
+        assert!(Currency::new("US-D").is_err());
+        assert!(Currency::new("ABCDEFGHIJKLM").unwrap().encode().is_none());
+    }
+
+    #[test]
+    fn test_longest_currency_fits_in_a_felt() {
+        let currency = Currency::new(&"9".repeat(MAX_CURRENCY_LEN)).unwrap();
+        let felt = Felt::try_from(&currency).unwrap();
+        assert_eq!(Currency::try_from(felt).unwrap(), currency);
+    }
+
+    #[test]
+    fn test_too_many_characters_are_rejected() {
+        // MAX_CURRENCY_LEN + 1 times "A".
+        let encoded = (0..=MAX_CURRENCY_LEN as u32).map(|i| 37u64.pow(i)).sum();
+        assert!(Currency::decode(encoded).is_err());
+    }
+
+    #[test]
+    fn test_zero_digit_is_rejected() {
+        // Digits [1, 0, 1]: a zero digit between two "A".
+        assert!(Currency::decode(37 * 37 + 1).is_err());
+        assert!(Currency::decode(0).is_err());
+    }
+}
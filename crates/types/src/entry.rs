@@ -1,6 +1,7 @@
 use miden_client::{Felt, Word};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Entry {
     pub faucet_id: String,
     pub price: u64,
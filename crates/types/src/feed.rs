@@ -0,0 +1,544 @@
+use std::fmt;
+use std::str::FromStr;
+
+use miden_client::{Felt, Word, ZERO};
+
+/// Maximum length of a feed name, packed 7 characters per felt in a word.
+pub const MAX_FEED_NAME_LEN: usize = 28;
+
+/// Number of name characters packed in each felt of a [`FeedId`]. 7 bytes
+/// always fit in a felt, whatever their value.
+const FEED_NAME_CHARS_PER_FELT: usize = 7;
+
+/// Offset added to the values of signed feeds, so that they are stored as
+/// felts while keeping their order: `-2^62` is stored as 0.
+const SIGNED_VALUE_BIAS: i64 = 1 << 62;
+
+/// Type of the values published for a feed.
+///
+/// The tag is stored with every published value, so that consumers know how
+/// to decode it. Price feeds have tag 0, which makes a price [`FeedValue`]
+/// laid out exactly like a publisher [`crate::Entry`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FeedKind {
+    Price,
+    /// Signed rate, e.g. a perpetual funding rate.
+    FundingRate,
+    /// Cumulative interest-rate index, e.g. a lending market borrow index.
+    InterestRateIndex,
+    /// Reserves backing an asset, e.g. the BTC held in custody for WBTC.
+    ProofOfReserve,
+}
+
+impl FeedKind {
+    pub fn tag(&self) -> u64 {
+        match self {
+            Self::Price => 0,
+            Self::FundingRate => 1,
+            Self::InterestRateIndex => 2,
+            Self::ProofOfReserve => 3,
+        }
+    }
+
+    /// Returns true if the values of the feed can be negative.
+    pub fn is_signed(&self) -> bool {
+        matches!(self, Self::FundingRate)
+    }
+
+    /// Aggregation used when a feed of this kind is configured without an
+    /// explicit mode.
+    ///
+    /// Reserves are aggregated conservatively with the lowest reported figure,
+    /// every other kind with the median.
+    pub fn default_aggregation(&self) -> AggregationMode {
+        match self {
+            Self::ProofOfReserve => AggregationMode::Min,
+            _ => AggregationMode::Median,
+        }
+    }
+}
+
+impl TryFrom<u64> for FeedKind {
+    type Error = anyhow::Error;
+
+    fn try_from(tag: u64) -> anyhow::Result<Self> {
+        match tag {
+            0 => Ok(Self::Price),
+            1 => Ok(Self::FundingRate),
+            2 => Ok(Self::InterestRateIndex),
+            3 => Ok(Self::ProofOfReserve),
+            _ => anyhow::bail!("Unknown feed kind tag {tag}"),
+        }
+    }
+}
+
+impl FromStr for FeedKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "price" => Ok(Self::Price),
+            "funding-rate" => Ok(Self::FundingRate),
+            "interest-rate-index" => Ok(Self::InterestRateIndex),
+            "proof-of-reserve" => Ok(Self::ProofOfReserve),
+            _ => anyhow::bail!(
+                "Unknown feed kind {s}. Expected price, funding-rate, interest-rate-index or proof-of-reserve"
+            ),
+        }
+    }
+}
+
+impl fmt::Display for FeedKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            Self::Price => "price",
+            Self::FundingRate => "funding-rate",
+            Self::InterestRateIndex => "interest-rate-index",
+            Self::ProofOfReserve => "proof-of-reserve",
+        };
+        f.write_str(kind)
+    }
+}
+
+/// How the oracle aggregates the values published for a feed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AggregationMode {
+    Median,
+    Mean,
+    Min,
+    Max,
+}
+
+impl AggregationMode {
+    /// Mode as stored in the oracle feed configs, see `AGGREGATION_*` in oracle.masm.
+    pub fn tag(&self) -> u64 {
+        match self {
+            Self::Median => 0,
+            Self::Mean => 1,
+            Self::Min => 2,
+            Self::Max => 3,
+        }
+    }
+}
+
+impl TryFrom<u64> for AggregationMode {
+    type Error = anyhow::Error;
+
+    fn try_from(tag: u64) -> anyhow::Result<Self> {
+        match tag {
+            0 => Ok(Self::Median),
+            1 => Ok(Self::Mean),
+            2 => Ok(Self::Min),
+            3 => Ok(Self::Max),
+            _ => anyhow::bail!("Unknown aggregation mode {tag}"),
+        }
+    }
+}
+
+impl FromStr for AggregationMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "median" => Ok(Self::Median),
+            "mean" => Ok(Self::Mean),
+            "min" => Ok(Self::Min),
+            "max" => Ok(Self::Max),
+            _ => anyhow::bail!("Unknown aggregation mode {s}. Expected median, mean, min or max"),
+        }
+    }
+}
+
+impl fmt::Display for AggregationMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mode = match self {
+            Self::Median => "median",
+            Self::Mean => "mean",
+            Self::Min => "min",
+            Self::Max => "max",
+        };
+        f.write_str(mode)
+    }
+}
+
+/// Key identifying a generic feed in the publishers and the oracle.
+///
+/// A feed is identified by a name (e.g. `BTC-PERP/funding`), packed 7 ASCII
+/// characters per felt into the key under which its values are stored:
+/// `[name_0, name_1, name_2, name_3]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FeedId(Word);
+
+impl FeedId {
+    /// # Errors
+    ///
+    /// Fails if the name is empty, longer than [`MAX_FEED_NAME_LEN`] or
+    /// contains characters that are not printable ASCII.
+    pub fn from_name(name: &str) -> anyhow::Result<Self> {
+        let name = name.trim();
+        if name.is_empty() {
+            anyhow::bail!("Feed name must not be empty");
+        }
+        if name.len() > MAX_FEED_NAME_LEN {
+            anyhow::bail!("Feed name {name} is longer than {MAX_FEED_NAME_LEN} characters");
+        }
+        if !name.bytes().all(|b| b.is_ascii_graphic()) {
+            anyhow::bail!("Feed name {name} must contain only printable ASCII characters");
+        }
+
+        let mut elements = [ZERO; 4];
+        for (element, chunk) in elements
+            .iter_mut()
+            .zip(name.as_bytes().chunks(FEED_NAME_CHARS_PER_FELT))
+        {
+            let packed = chunk
+                .iter()
+                .enumerate()
+                .fold(0u64, |packed, (i, byte)| packed | (*byte as u64) << (i * 8));
+            *element = Felt::new(packed).expect("7 packed bytes always fit in the field");
+        }
+        Ok(Self(elements.into()))
+    }
+
+    /// Decodes a key built by [`FeedId::to_storage_key`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if the word is not a packed feed name.
+    pub fn from_storage_key(word: Word) -> anyhow::Result<Self> {
+        let feed_id = Self(word);
+        feed_id.name()?;
+        Ok(feed_id)
+    }
+
+    /// Word pushed by the scripts before calling into the publisher or the
+    /// oracle, i.e. the storage key reversed.
+    pub fn to_word(&self) -> Word {
+        let mut elements: [Felt; 4] = self.0.into();
+        elements.reverse();
+        elements.into()
+    }
+
+    /// Key under which the values of the feed are stored in the storage maps.
+    pub fn to_storage_key(&self) -> Word {
+        self.0
+    }
+
+    /// Decodes the feed name.
+    pub fn name(&self) -> anyhow::Result<String> {
+        let mut bytes = Vec::with_capacity(MAX_FEED_NAME_LEN);
+        for element in self.0.iter() {
+            let packed = element.as_canonical_u64();
+            if packed >> (FEED_NAME_CHARS_PER_FELT * 8) != 0 {
+                anyhow::bail!(
+                    "Invalid feed id {:?}: felt {packed} is not a packed name",
+                    self.0
+                );
+            }
+            bytes.extend(
+                (0..FEED_NAME_CHARS_PER_FELT)
+                    .map(|i| (packed >> (i * 8)) as u8)
+                    .take_while(|byte| *byte != 0),
+            );
+        }
+        let name = String::from_utf8(bytes)
+            .map_err(|_| anyhow::anyhow!("Invalid feed id {:?}: name is not ASCII", self.0))?;
+        if name.is_empty() {
+            anyhow::bail!("Invalid feed id {:?}: empty name", self.0);
+        }
+        Ok(name)
+    }
+}
+
+impl FromStr for FeedId {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_name(s)
+    }
+}
+
+impl fmt::Display for FeedId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name() {
+            Ok(name) => f.write_str(&name),
+            Err(_) => write!(f, "{:?}", self.0),
+        }
+    }
+}
+
+/// A value published for a feed.
+///
+/// Stored by the publishers as `[timestamp, decimals, raw_value, kind]`, i.e.
+/// like an [`crate::Entry`] with the kind tag in place of its zero felt. The
+/// values of signed kinds are stored biased by `2^62`, so that the oracle can
+/// order and average them as plain felts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeedValue {
+    pub kind: FeedKind,
+    pub value: i64,
+    pub decimals: u32,
+    pub timestamp: u64,
+}
+
+impl FeedValue {
+    /// Decodes a value as stored by the publishers.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the kind tag is unknown or the value does not fit its kind.
+    pub fn from_word(word: Word) -> anyhow::Result<Self> {
+        let [timestamp, decimals, raw_value, kind]: [Felt; 4] = word.into();
+        let kind = FeedKind::try_from(kind.as_canonical_u64())?;
+        let decimals = decimals.as_canonical_u64();
+        Ok(Self {
+            kind,
+            value: decode_value(kind, raw_value.as_canonical_u64())?,
+            decimals: u32::try_from(decimals)
+                .map_err(|_| anyhow::anyhow!("Invalid feed decimals {decimals}"))?,
+            timestamp: timestamp.as_canonical_u64(),
+        })
+    }
+
+    /// The value as stored by the publishers.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the value does not fit its kind, e.g. a negative price.
+    pub fn to_word(&self) -> anyhow::Result<Word> {
+        Ok([
+            Felt::new(self.timestamp)?,
+            Felt::from(self.decimals),
+            Felt::new(encode_value(self.kind, self.value)?)?,
+            Felt::new(self.kind.tag())?,
+        ]
+        .into())
+    }
+
+    /// Word to push before calling `publish_feed` so that it is stored as
+    /// [`FeedValue::to_word`].
+    pub fn to_push_word(&self) -> anyhow::Result<Word> {
+        let mut elements: [Felt; 4] = self.to_word()?.into();
+        elements.reverse();
+        Ok(elements.into())
+    }
+}
+
+/// Encodes a value of the given kind into the felt stored on-chain.
+///
+/// # Errors
+///
+/// Fails if an unsigned kind gets a negative value, or a signed value is
+/// outside of `[-2^62, 2^62)`.
+pub fn encode_value(kind: FeedKind, value: i64) -> anyhow::Result<u64> {
+    if kind.is_signed() {
+        value
+            .checked_add(SIGNED_VALUE_BIAS)
+            .filter(|raw| *raw >= 0)
+            .map(|raw| raw as u64)
+            .ok_or_else(|| anyhow::anyhow!("Value {value} is out of range for a {kind} feed"))
+    } else {
+        u64::try_from(value)
+            .map_err(|_| anyhow::anyhow!("A {kind} feed value must not be negative"))
+    }
+}
+
+/// Inverse of [`encode_value`], also used to decode the aggregates returned by
+/// the oracle.
+///
+/// # Errors
+///
+/// Fails if the raw value is out of range for the kind.
+pub fn decode_value(kind: FeedKind, raw_value: u64) -> anyhow::Result<i64> {
+    let value = i64::try_from(raw_value)
+        .map_err(|_| anyhow::anyhow!("Raw value {raw_value} is out of range for a {kind} feed"))?;
+    if kind.is_signed() {
+        Ok(value - SIGNED_VALUE_BIAS)
+    } else {
+        Ok(value)
+    }
+}
+
+/// Configuration of a feed in the oracle, stored as
+/// `[mode, max_age, kind, decimals]`.
+///
+/// A feed without configuration is read as a zero word: a median over the
+/// prices published in the last `MAX_ENTRY_AGE_SECONDS`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeedConfig {
+    pub kind: FeedKind,
+    pub mode: AggregationMode,
+    /// Maximum age of the aggregated values in seconds, 0 for the oracle default.
+    pub max_age: u64,
+    /// Decimals of the values, informative for consumers.
+    pub decimals: u32,
+}
+
+impl FeedConfig {
+    /// Decodes a config as stored in the oracle.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the mode or the kind is unknown.
+    pub fn from_word(word: Word) -> anyhow::Result<Self> {
+        let [mode, max_age, kind, decimals]: [Felt; 4] = word.into();
+        let decimals = decimals.as_canonical_u64();
+        Ok(Self {
+            kind: FeedKind::try_from(kind.as_canonical_u64())?,
+            mode: AggregationMode::try_from(mode.as_canonical_u64())?,
+            max_age: max_age.as_canonical_u64(),
+            decimals: u32::try_from(decimals)
+                .map_err(|_| anyhow::anyhow!("Invalid feed decimals {decimals}"))?,
+        })
+    }
+
+    /// The config as stored in the oracle.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `max_age` is not a valid field element.
+    pub fn to_word(&self) -> anyhow::Result<Word> {
+        Ok([
+            Felt::new(self.mode.tag())?,
+            Felt::new(self.max_age)?,
+            Felt::new(self.kind.tag())?,
+            Felt::from(self.decimals),
+        ]
+        .into())
+    }
+
+    /// Word to push before calling `set_feed_config` so that it is stored as
+    /// [`FeedConfig::to_word`].
+    pub fn to_push_word(&self) -> anyhow::Result<Word> {
+        let mut elements: [Felt; 4] = self.to_word()?.into();
+        elements.reverse();
+        Ok(elements.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feed_id_round_trip() {
+        for name in ["BTC-PERP/funding", "a", "usdc-reserves.0123456789_abc"] {
+            let feed_id = FeedId::from_name(name).unwrap();
+            assert_eq!(feed_id.name().unwrap(), name);
+            assert_eq!(
+                FeedId::from_storage_key(feed_id.to_storage_key()).unwrap(),
+                feed_id
+            );
+            assert_eq!(feed_id.to_string(), name);
+        }
+    }
+
+    #[test]
+    fn test_invalid_feed_names_are_rejected() {
+        assert!(FeedId::from_name("").is_err());
+        assert!(FeedId::from_name("with space").is_err());
+        assert!(FeedId::from_name("é").is_err());
+        assert!(FeedId::from_name(&"a".repeat(MAX_FEED_NAME_LEN + 1)).is_err());
+        assert!(FeedId::from_storage_key(Word::default()).is_err());
+    }
+
+    #[test]
+    fn test_push_word_is_the_reversed_storage_key() {
+        let feed_id = FeedId::from_name("ETH-PERP/funding").unwrap();
+        let mut elements: [Felt; 4] = feed_id.to_word().into();
+        elements.reverse();
+        assert_eq!(feed_id.to_storage_key(), elements.into());
+    }
+
+    #[test]
+    fn test_signed_values_keep_their_order() {
+        let kind = FeedKind::FundingRate;
+        let values = [-SIGNED_VALUE_BIAS, -42, 0, 42, SIGNED_VALUE_BIAS - 1];
+        let raw_values: Vec<u64> = values
+            .iter()
+            .map(|value| encode_value(kind, *value).unwrap())
+            .collect();
+        assert!(raw_values.windows(2).all(|pair| pair[0] < pair[1]));
+        for (value, raw_value) in values.iter().zip(raw_values) {
+            assert_eq!(decode_value(kind, raw_value).unwrap(), *value);
+        }
+        assert!(encode_value(kind, SIGNED_VALUE_BIAS).is_err());
+        assert!(encode_value(kind, -SIGNED_VALUE_BIAS - 1).is_err());
+    }
+
+    #[test]
+    fn test_unsigned_values_must_not_be_negative() {
+        assert!(encode_value(FeedKind::ProofOfReserve, -1).is_err());
+        assert_eq!(encode_value(FeedKind::Price, 42).unwrap(), 42);
+    }
+
+    #[test]
+    fn test_feed_value_round_trip() {
+        let value = FeedValue {
+            kind: FeedKind::FundingRate,
+            value: -1_250,
+            decimals: 6,
+            timestamp: 1_700_000_000,
+        };
+        assert_eq!(
+            FeedValue::from_word(value.to_word().unwrap()).unwrap(),
+            value
+        );
+    }
+
+    #[test]
+    fn test_price_feed_value_is_laid_out_like_an_entry() {
+        let value = FeedValue {
+            kind: FeedKind::Price,
+            value: 6_819_900_000_000,
+            decimals: 8,
+            timestamp: 1_700_000_000,
+        };
+        let [timestamp, decimals, price, zero]: [Felt; 4] = value.to_word().unwrap().into();
+        assert_eq!(timestamp.as_canonical_u64(), 1_700_000_000);
+        assert_eq!(decimals.as_canonical_u64(), 8);
+        assert_eq!(price.as_canonical_u64(), 6_819_900_000_000);
+        assert_eq!(zero, ZERO);
+    }
+
+    #[test]
+    fn test_feed_config_round_trip() {
+        let config = FeedConfig {
+            kind: FeedKind::ProofOfReserve,
+            mode: FeedKind::ProofOfReserve.default_aggregation(),
+            max_age: 86_400,
+            decimals: 8,
+        };
+        assert_eq!(
+            FeedConfig::from_word(config.to_word().unwrap()).unwrap(),
+            config
+        );
+        let default = FeedConfig::from_word(Word::default()).unwrap();
+        assert_eq!(default.kind, FeedKind::Price);
+        assert_eq!(default.mode, AggregationMode::Median);
+    }
+
+    #[test]
+    fn test_kinds_and_modes_parse_their_display() {
+        for kind in [
+            FeedKind::Price,
+            FeedKind::FundingRate,
+            FeedKind::InterestRateIndex,
+            FeedKind::ProofOfReserve,
+        ] {
+            assert_eq!(kind.to_string().parse::<FeedKind>().unwrap(), kind);
+            assert_eq!(FeedKind::try_from(kind.tag()).unwrap(), kind);
+        }
+        for mode in [
+            AggregationMode::Median,
+            AggregationMode::Mean,
+            AggregationMode::Min,
+            AggregationMode::Max,
+        ] {
+            assert_eq!(mode.to_string().parse::<AggregationMode>().unwrap(), mode);
+            assert_eq!(AggregationMode::try_from(mode.tag()).unwrap(), mode);
+        }
+        assert!("rate".parse::<FeedKind>().is_err());
+        assert!(AggregationMode::try_from(4).is_err());
+    }
+}
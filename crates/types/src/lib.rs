@@ -1,7 +1,15 @@
+pub mod asset;
 pub mod currency;
 pub mod entry;
+pub mod feed;
 pub mod pair;
+pub mod publisher;
+pub mod registry;
 
+pub use asset::*;
 pub use currency::*;
 pub use entry::*;
+pub use feed::*;
 pub use pair::*;
+pub use publisher::*;
+pub use registry::*;
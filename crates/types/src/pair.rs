@@ -1,8 +1,17 @@
 use std::str::FromStr;
 
-use crate::currency::Currency;
+use crate::currency::{
+    decode_digits, encode_digits, ticker_char, ticker_digit, Currency, TICKER_SYMBOLS,
+};
 use miden_client::{Felt, Word, ZERO};
 
+/// Maximum length of the `BASE/QUOTE` string of a pair encoded into a Felt,
+/// e.g. an 8-character base with a 3-character quote.
+pub const MAX_PAIR_LEN: usize = 12;
+
+/// Digit of the `/` separator in the pair encoding, after the ticker digits.
+const SEPARATOR_DIGIT: u64 = TICKER_SYMBOLS + 1;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Pair {
     pub base: Currency,
@@ -10,18 +19,59 @@ pub struct Pair {
 }
 
 impl Pair {
+    /// Builds the pair of `base` and `quote`.
+    ///
+    /// Each currency may have up to 8 characters, but the pair is only
+    /// encoded into a Felt (see [`Pair::encode`]) if `BASE/QUOTE` has at most
+    /// [`MAX_PAIR_LEN`] characters, e.g. `BITCOIN2/USDT` cannot be.
     pub fn new(base: Currency, quote: Currency) -> Self {
         Self { base, quote }
     }
 
-    pub fn encode(&self) -> Option<u32> {
-        let base_encoded = self.base.encode()?;
-        let quote_encoded = self.quote.encode()?;
-        Some(base_encoded | (quote_encoded << 15))
+    /// Encodes the `BASE/QUOTE` string as a base-38 number, one digit per
+    /// character.
+    ///
+    /// Ticker characters use the digits `1..=36` and the separator `37`, so
+    /// every pair has its own encoding. Returns `None` if the pair is longer
+    /// than [`MAX_PAIR_LEN`] characters.
+    pub fn encode(&self) -> Option<u64> {
+        let pair_str = self.to_string();
+        if pair_str.len() > MAX_PAIR_LEN {
+            return None;
+        }
+        encode_digits(
+            pair_str.chars().map(|c| match c {
+                '/' => Some(SEPARATOR_DIGIT),
+                _ => ticker_digit(c),
+            }),
+            SEPARATOR_DIGIT + 1,
+        )
     }
 
-    pub fn to_word(&self) -> Word {
-        [ZERO, ZERO, ZERO, self.try_into().unwrap()].into()
+    /// Decodes a pair encoded by [`Pair::encode`].
+    pub fn decode(encoded: u64) -> anyhow::Result<Self> {
+        let digits = decode_digits(encoded, SEPARATOR_DIGIT + 1)?;
+        if digits.len() > MAX_PAIR_LEN {
+            anyhow::bail!("Invalid pair encoding {encoded}: too many characters");
+        }
+        let pair_str = digits
+            .into_iter()
+            .map(|digit| match digit {
+                SEPARATOR_DIGIT => Some('/'),
+                _ => ticker_char(digit),
+            })
+            .collect::<Option<String>>()
+            .ok_or_else(|| anyhow::anyhow!("Invalid pair encoding {encoded}"))?;
+        Self::from_str(&pair_str)
+    }
+
+    /// Word `[0, 0, 0, encoded_pair]` of the pair.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the pair is longer than [`MAX_PAIR_LEN`] characters.
+    pub fn to_word(&self) -> anyhow::Result<Word> {
+        Ok([ZERO, ZERO, ZERO, self.try_into()?].into())
     }
 
     /// Converts the Pair to a decimal string representation "{base}/{quote}" and
@@ -167,12 +217,7 @@ impl TryFrom<Pair> for Felt {
     type Error = anyhow::Error;
 
     fn try_from(value: Pair) -> anyhow::Result<Self> {
-        let encoded = value
-            .encode()
-            .ok_or_else(|| anyhow::anyhow!("Invalid asset pair format"))?;
-
-        let value = u64::from(encoded);
-        Ok(Felt::new(value)?)
+        Felt::try_from(&value)
     }
 }
 
@@ -180,12 +225,10 @@ impl TryFrom<&Pair> for Felt {
     type Error = anyhow::Error;
 
     fn try_from(value: &Pair) -> anyhow::Result<Self> {
-        let encoded = value
-            .encode()
-            .ok_or_else(|| anyhow::anyhow!("Invalid asset pair format"))?;
-
-        let value = u64::from(encoded);
-        Ok(Felt::new(value)?)
+        let encoded = value.encode().ok_or_else(|| {
+            anyhow::anyhow!("Pair {value} is longer than {MAX_PAIR_LEN} characters")
+        })?;
+        Ok(Felt::new(encoded)?)
     }
 }
 
@@ -206,54 +249,11 @@ impl FromStr for Pair {
     }
 }
 
-impl From<Felt> for Pair {
-    fn from(felt: Felt) -> Self {
-        // Convert Felt to u32
-        let value = felt.as_canonical_u64() as u32;
-
-        // Extract base and quote portions
-        let base_encoded = value & 0x7FFF; // Lower 15 bits
-        let quote_encoded = (value >> 15) & 0x7FFF; // Upper 15 bits
-
-        // Decode each currency string
-        let base = decode_currency(base_encoded).unwrap();
-        let quote = decode_currency(quote_encoded).unwrap();
-
-        // Create currencies
-        let base_currency = Currency::new(&base).unwrap();
-        let quote_currency = Currency::new(&quote).unwrap();
-
-        Pair::new(base_currency, quote_currency)
-    }
-}
-
-fn decode_currency(encoded: u32) -> Option<String> {
-    let mut result = String::new();
-    let mut remaining = encoded;
-
-    // Each character uses 5 bits (A-Z = 0-25)
-    for _ in 0..3 {
-        // Assuming max 3 characters per currency
-        if remaining == 0 {
-            break;
-        }
-
-        let char_value = remaining & 0x1F; // Get lowest 5 bits
-        if char_value >= 26 {
-            // Invalid character value
-            return None;
-        }
-
-        let decoded_char = char::from_u32('A' as u32 + char_value)?;
-        result.push(decoded_char);
-
-        remaining >>= 5;
-    }
+impl TryFrom<Felt> for Pair {
+    type Error = anyhow::Error;
 
-    if result.is_empty() {
-        None
-    } else {
-        Some(result)
+    fn try_from(felt: Felt) -> anyhow::Result<Self> {
+        Pair::decode(felt.as_canonical_u64())
     }
 }
 
@@ -283,7 +283,7 @@ mod tests {
         assert_ne!(encoded_btc_usd, encoded_eth_usdt);
 
         // Make sure it's reversible
-        let pair_from_felt = Pair::from(Felt::new(encoded_btc_usd as u64).unwrap());
+        let pair_from_felt = Pair::try_from(Felt::new(encoded_btc_usd).unwrap()).unwrap();
         assert_eq!(pair_from_felt.base.0, "BTC");
         assert_eq!(pair_from_felt.quote.0, "USD");
     }
@@ -291,7 +291,7 @@ mod tests {
     #[test]
     fn test_to_word() {
         let pair = Pair::new(Currency::new("BTC").unwrap(), Currency::new("USD").unwrap());
-        let word = pair.to_word();
+        let word = pair.to_word().unwrap();
 
         // First 3 elements should be ZERO
         assert_eq!(word[0], ZERO);
@@ -300,7 +300,14 @@ mod tests {
 
         // Last element should be the encoded pair
         let encoded = pair.encode().unwrap();
-        assert_eq!(word[3], Felt::new(encoded as u64).unwrap());
+        assert_eq!(word[3], Felt::new(encoded).unwrap());
+    }
+
+    #[test]
+    fn test_to_word_rejects_pairs_too_long_to_encode() {
+        let pair = Pair::from_str("BITCOIN2/USDT").unwrap();
+        assert!(pair.encode().is_none());
+        assert!(pair.to_word().is_err());
     }
 
     #[test]
@@ -323,7 +330,11 @@ mod tests {
         assert!(result.is_err());
 
         // Invalid currency
-        let result = Pair::from_str("BTC/123");
+        let result = Pair::from_str("BTC/U-D");
+        assert!(result.is_err());
+
+        // Empty currency
+        let result = Pair::from_str("BTC/");
         assert!(result.is_err());
     }
 
@@ -467,7 +478,7 @@ mod tests {
 
         // Convert to Felt and back
         let felt: Felt = (&original_pair).try_into().unwrap();
-        let round_trip_pair = Pair::from(felt);
+        let round_trip_pair = Pair::try_from(felt).unwrap();
 
         assert_eq!(original_pair.base.0, round_trip_pair.base.0);
         assert_eq!(original_pair.quote.0, round_trip_pair.quote.0);
@@ -499,16 +510,27 @@ mod tests {
     }
 
     #[test]
-    fn test_decode_currency() {
-        // Test valid encoding
-        let btc_encoded = Currency::new("BTC").unwrap().encode().unwrap();
-        let btc_decoded = decode_currency(btc_encoded).unwrap();
-        assert_eq!(btc_decoded, "BTC");
-
-        // Test invalid encoding (value too large)
-        let invalid_encoded = 0x1FFFFF; // All bits set in a 21-bit value
-        let result = decode_currency(invalid_encoded);
-        assert!(result.is_none());
+    fn test_long_tickers_do_not_collide() {
+        // With 5 bits per letter and 15 bits per currency, USDT overflowed into
+        // the quote bits.
+        let usdt_usd = Pair::from_str("USDT/USD").unwrap();
+        let felt = Felt::try_from(&usdt_usd).unwrap();
+        assert_eq!(Pair::try_from(felt).unwrap(), usdt_usd);
+
+        let long_pair = Pair::from_str("BITCOIN2/USD").unwrap();
+        let felt = Felt::try_from(&long_pair).unwrap();
+        assert_eq!(Pair::try_from(felt).unwrap(), long_pair);
+
+        assert!(Pair::from_str("BITCOIN21/USD").unwrap().encode().is_none());
+    }
+
+    #[test]
+    fn test_try_from_invalid_felt() {
+        // A single ticker character has no separator.
+        assert!(Pair::try_from(Felt::new(1).unwrap()).is_err());
+        // A pair starting with the separator has an empty base.
+        assert!(Pair::try_from(Felt::new(37 + 38).unwrap()).is_err());
+        assert!(Pair::try_from(ZERO).is_err());
     }
 
     #[test]
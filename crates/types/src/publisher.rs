@@ -0,0 +1,93 @@
+use miden_client::{account::AccountId, Felt, Word, ZERO};
+
+/// Word under which the oracle registry stores a publisher id.
+///
+/// `register_publisher` writes the id as `[prefix, suffix, 0, 0]`, and
+/// `remove_publisher` soft-deletes a publisher by overwriting its word with
+/// `[0, 0, 0, 0]`. Every reader of the `pragma::oracle::publishers` map decodes
+/// the word through this type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublisherRegistryWord(Word);
+
+impl PublisherRegistryWord {
+    pub fn from_word(word: Word) -> Self {
+        Self(word)
+    }
+
+    pub fn word(&self) -> Word {
+        self.0
+    }
+
+    /// Returns true if the publisher stored in this slot was removed.
+    pub fn is_removed(&self) -> bool {
+        self.0 == Word::default()
+    }
+
+    /// Decodes the publisher id, `None` if the publisher was removed.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the word is not `[prefix, suffix, 0, 0]` for a valid account id.
+    pub fn publisher_id(&self) -> anyhow::Result<Option<AccountId>> {
+        if self.is_removed() {
+            return Ok(None);
+        }
+        let [prefix, suffix, padding_0, padding_1]: [Felt; 4] = self.0.into();
+        if padding_0 != ZERO || padding_1 != ZERO {
+            anyhow::bail!(
+                "Invalid publisher registry word {:?}: expected [prefix, suffix, 0, 0]",
+                self.0
+            );
+        }
+        let publisher_id = AccountId::try_from_elements(suffix, prefix).map_err(|e| {
+            anyhow::anyhow!("Invalid publisher id in registry word {:?}: {e}", self.0)
+        })?;
+        Ok(Some(publisher_id))
+    }
+}
+
+impl From<AccountId> for PublisherRegistryWord {
+    fn from(publisher_id: AccountId) -> Self {
+        Self(
+            [
+                publisher_id.prefix().as_felt(),
+                publisher_id.suffix(),
+                ZERO,
+                ZERO,
+            ]
+            .into(),
+        )
+    }
+}
+
+impl From<PublisherRegistryWord> for Word {
+    fn from(registry_word: PublisherRegistryWord) -> Self {
+        registry_word.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_removed_word_decodes_to_none() {
+        let registry_word = PublisherRegistryWord::from_word([ZERO, ZERO, ZERO, ZERO].into());
+        assert!(registry_word.is_removed());
+        assert_eq!(registry_word.publisher_id().unwrap(), None);
+    }
+
+    #[test]
+    fn test_non_zero_padding_is_rejected() {
+        let word: Word = [
+            Felt::new(1).unwrap(),
+            Felt::new(2).unwrap(),
+            Felt::new(3).unwrap(),
+            ZERO,
+        ]
+        .into();
+        assert!(PublisherRegistryWord::from_word(word)
+            .publisher_id()
+            .is_err());
+    }
+}
@@ -0,0 +1,197 @@
+use std::str::FromStr;
+
+use crate::{AssetKey, Pair};
+
+/// An asset known by its pair symbol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetInfo {
+    pub pair: Pair,
+    pub asset_key: AssetKey,
+    /// Number of decimals the prices of the asset are scaled by.
+    pub decimals: u32,
+    /// Human readable name of the base asset (e.g. "Bitcoin").
+    pub name: String,
+}
+
+/// Assets served by the Pragma price pusher, as `(pair, asset key, decimals, name)`.
+///
+/// Must mirror the explorer's `faucet-config.ts`.
+const BUILTIN_ASSETS: &[(&str, &str, u32, &str)] = &[
+    ("BTC/USD", "1:0", 8, "Bitcoin"),
+    ("ETH/USD", "2:0", 8, "Ethereum"),
+    ("WBTC/USD", "3:0", 8, "Wrapped Bitcoin"),
+    ("USDT/USD", "4:0", 6, "Tether"),
+    ("DAI/USD", "5:0", 8, "Dai"),
+];
+
+/// Maps pair symbols (e.g. `BTC/USD`) to their asset keys.
+///
+/// Lets every command accept either a symbol or a raw asset key, and display
+/// the symbol of the assets it knows about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetRegistry {
+    assets: Vec<AssetInfo>,
+}
+
+impl AssetRegistry {
+    /// Returns an empty registry, in which only raw asset keys resolve.
+    pub fn empty() -> Self {
+        Self { assets: Vec::new() }
+    }
+
+    /// Returns the registry of the assets served by the Pragma price pusher.
+    pub fn builtin() -> Self {
+        let mut registry = Self::empty();
+        for (pair, asset_key, decimals, name) in BUILTIN_ASSETS {
+            registry.register(AssetInfo {
+                pair: Pair::from_str(pair).expect("builtin pairs are valid"),
+                asset_key: AssetKey::from_str(asset_key).expect("builtin asset keys are valid"),
+                decimals: *decimals,
+                name: name.to_string(),
+            });
+        }
+        registry
+    }
+
+    /// Registers an asset, replacing any asset with the same pair or asset key.
+    pub fn register(&mut self, asset: AssetInfo) {
+        self.assets
+            .retain(|known| known.pair != asset.pair && known.asset_key != asset.asset_key);
+        self.assets.push(asset);
+    }
+
+    pub fn assets(&self) -> &[AssetInfo] {
+        &self.assets
+    }
+
+    pub fn get(&self, pair: &Pair) -> Option<&AssetInfo> {
+        self.assets.iter().find(|asset| &asset.pair == pair)
+    }
+
+    pub fn get_by_key(&self, asset_key: AssetKey) -> Option<&AssetInfo> {
+        self.assets
+            .iter()
+            .find(|asset| asset.asset_key == asset_key)
+    }
+
+    /// Resolves a pair symbol (e.g. `BTC/USD`) or a raw asset key (e.g. `1:0`
+    /// or a faucet account id) into an asset key.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the symbol is not registered or the asset key is invalid.
+    pub fn resolve(&self, asset: &str) -> anyhow::Result<AssetKey> {
+        if !asset.contains('/') {
+            return asset.parse();
+        }
+        let pair = Pair::from_str(asset.trim())?;
+        self.get(&pair).map(|known| known.asset_key).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unknown asset {pair}. Use a registered pair or a raw asset key (e.g., 1:0)"
+            )
+        })
+    }
+
+    /// Resolves a cross pair into the asset keys of its base and its quote.
+    ///
+    /// A cross pair is either two raw asset keys (e.g. `2:0/1:0`) or a pair
+    /// symbol (e.g. `ETH/BTC`) whose base and quote are both registered
+    /// against a common currency (e.g. `ETH/USD` and `BTC/USD`).
+    ///
+    /// # Errors
+    ///
+    /// Fails if the cross pair is malformed or no common quote currency is
+    /// registered for its base and its quote.
+    pub fn resolve_cross(&self, cross: &str) -> anyhow::Result<(AssetKey, AssetKey)> {
+        let (base, quote) = cross
+            .trim()
+            .split_once('/')
+            .ok_or_else(|| anyhow::anyhow!("Invalid cross pair {cross}: expected BASE/QUOTE"))?;
+        if let (Ok(base), Ok(quote)) = (base.parse(), quote.parse()) {
+            return Ok((base, quote));
+        }
+
+        let cross = Pair::from_str(cross.trim())?;
+        self.assets
+            .iter()
+            .filter(|base| base.pair.base == cross.base)
+            .find_map(|base| {
+                self.get(&Pair::new(cross.quote.clone(), base.pair.quote.clone()))
+                    .map(|quote| (base.asset_key, quote.asset_key))
+            })
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Unknown cross pair {cross}: {} and {} must be registered against a common currency (e.g., {}/USD and {}/USD)",
+                    cross.base.0,
+                    cross.quote.0,
+                    cross.base.0,
+                    cross.quote.0
+                )
+            })
+    }
+
+    /// Returns the pair symbol of `asset_key`, or the key itself when unknown.
+    pub fn label(&self, asset_key: AssetKey) -> String {
+        self.get_by_key(asset_key)
+            .map(|asset| asset.pair.to_string())
+            .unwrap_or_else(|| asset_key.to_string())
+    }
+}
+
+impl Default for AssetRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_symbol_or_raw_key() {
+        let registry = AssetRegistry::builtin();
+        let btc = AssetKey::new(1, 0).unwrap();
+        assert_eq!(registry.resolve("BTC/USD").unwrap(), btc);
+        assert_eq!(registry.resolve("btc/usd").unwrap(), btc);
+        assert_eq!(registry.resolve("1:0").unwrap(), btc);
+        assert_eq!(
+            registry.resolve("42:0").unwrap(),
+            AssetKey::new(42, 0).unwrap()
+        );
+        assert!(registry.resolve("DOGE/USD").is_err());
+    }
+
+    #[test]
+    fn test_resolve_cross() {
+        let registry = AssetRegistry::builtin();
+        let btc = AssetKey::new(1, 0).unwrap();
+        let eth = AssetKey::new(2, 0).unwrap();
+        assert_eq!(registry.resolve_cross("ETH/BTC").unwrap(), (eth, btc));
+        assert_eq!(registry.resolve_cross("btc/eth").unwrap(), (btc, eth));
+        assert_eq!(registry.resolve_cross("2:0/1:0").unwrap(), (eth, btc));
+        assert!(registry.resolve_cross("ETH/DOGE").is_err());
+        assert!(registry.resolve_cross("ETH").is_err());
+    }
+
+    #[test]
+    fn test_label() {
+        let registry = AssetRegistry::builtin();
+        assert_eq!(registry.label(AssetKey::new(2, 0).unwrap()), "ETH/USD");
+        assert_eq!(registry.label(AssetKey::new(42, 0).unwrap()), "42:0");
+    }
+
+    #[test]
+    fn test_register_replaces_pair_and_key() {
+        let mut registry = AssetRegistry::builtin();
+        registry.register(AssetInfo {
+            pair: Pair::from_str("SOL/USD").unwrap(),
+            asset_key: AssetKey::new(3, 0).unwrap(),
+            decimals: 6,
+            name: "Solana".to_string(),
+        });
+        assert_eq!(registry.label(AssetKey::new(3, 0).unwrap()), "SOL/USD");
+        assert!(registry.resolve("WBTC/USD").is_err());
+        assert_eq!(registry.assets().len(), BUILTIN_ASSETS.len());
+    }
+}
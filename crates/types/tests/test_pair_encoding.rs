@@ -0,0 +1,93 @@
+//! Round-trip tests for the currency and pair Felt encodings.
+//!
+//! Short encodings are checked exhaustively: every value decodes either to an
+//! error or to the one currency/pair that encodes back to it. Longer tickers
+//! are covered by property tests.
+
+use std::str::FromStr;
+
+use miden_client::Felt;
+use pm_types::{Currency, Pair, MAX_CURRENCY_LEN, MAX_PAIR_LEN};
+use proptest::prelude::*;
+
+#[test]
+fn test_every_short_currency_encoding_round_trips() {
+    let mut decoded = 0;
+    for encoded in 0..37u64.pow(3) {
+        if let Ok(currency) = Currency::decode(encoded) {
+            assert_eq!(currency.encode(), Some(encoded), "{}", currency.0);
+            decoded += 1;
+        }
+    }
+    // Every ticker of 1 to 3 characters.
+    assert_eq!(decoded, 36 + 36usize.pow(2) + 36usize.pow(3));
+}
+
+#[test]
+fn test_every_short_pair_encoding_round_trips() {
+    let mut decoded = 0;
+    for encoded in 0..38u64.pow(4) {
+        if let Ok(pair) = Pair::decode(encoded) {
+            assert_eq!(pair.encode(), Some(encoded), "{pair}");
+            decoded += 1;
+        }
+    }
+    // Every pair of 3 characters (X/Y) and of 4 characters (XY/Z and X/YZ).
+    assert_eq!(decoded, 36usize.pow(2) + 2 * 36usize.pow(3));
+}
+
+fn ticker(max_len: usize) -> impl Strategy<Value = String> {
+    proptest::string::string_regex(&format!("[A-Z0-9]{{1,{max_len}}}")).unwrap()
+}
+
+fn pair() -> impl Strategy<Value = Pair> {
+    (ticker(8), ticker(3)).prop_map(|(base, quote)| {
+        Pair::new(
+            Currency::new(&base).unwrap(),
+            Currency::new(&quote).unwrap(),
+        )
+    })
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(1024))]
+
+    #[test]
+    fn prop_currency_round_trips(currency in ticker(MAX_CURRENCY_LEN)) {
+        let currency = Currency::new(&currency).unwrap();
+        let felt = Felt::try_from(&currency).unwrap();
+        prop_assert_eq!(Currency::try_from(felt).unwrap(), currency);
+    }
+
+    #[test]
+    fn prop_pair_round_trips(pair in pair()) {
+        let felt = Felt::try_from(&pair).unwrap();
+        prop_assert_eq!(Pair::try_from(felt).unwrap(), pair);
+    }
+
+    #[test]
+    fn prop_distinct_pairs_never_collide(a in pair(), b in pair()) {
+        prop_assume!(a != b);
+        prop_assert_ne!(a.encode(), b.encode());
+    }
+
+    #[test]
+    fn prop_pair_string_round_trips(pair in pair()) {
+        let pair_str = pair.to_string();
+        prop_assert!(pair_str.len() <= MAX_PAIR_LEN);
+        prop_assert_eq!(Pair::from_str(&pair_str).unwrap(), pair);
+    }
+
+    #[test]
+    fn prop_any_felt_decodes_to_its_own_encoding(value in any::<u64>()) {
+        let Ok(felt) = Felt::new(value) else {
+            return Ok(());
+        };
+        if let Ok(pair) = Pair::try_from(felt) {
+            prop_assert_eq!(pair.encode(), Some(value));
+        }
+        if let Ok(currency) = Currency::try_from(felt) {
+            prop_assert_eq!(currency.encode(), Some(value));
+        }
+    }
+}
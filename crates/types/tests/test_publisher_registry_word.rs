@@ -0,0 +1,72 @@
+//! Property tests for the publisher registry word codec.
+//!
+//! Real account ids are built from random seeds, so the round trip is checked
+//! against ids the network would actually accept, not hand-picked felts.
+
+use miden_client::{
+    account::{
+        component::{AuthScheme, AuthSingleSig, BasicWallet},
+        AccountBuilder, AccountId, AccountType,
+    },
+    auth::AuthSecretKey,
+    Felt, Word, ZERO,
+};
+use pm_types::PublisherRegistryWord;
+use proptest::prelude::*;
+
+fn account_id_from_seed(init_seed: [u8; 32], account_type: AccountType) -> AccountId {
+    let auth_key = AuthSecretKey::new_ecdsa_k256_keccak();
+    let auth_component = AuthSingleSig::new(
+        auth_key.public_key().to_commitment(),
+        AuthScheme::EcdsaK256Keccak,
+    );
+    AccountBuilder::new(init_seed)
+        .account_type(account_type)
+        .with_auth_component(auth_component)
+        .with_component(BasicWallet)
+        .build()
+        .expect("account should build")
+        .id()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn registry_word_round_trips_real_account_ids(
+        init_seed in any::<[u8; 32]>(),
+        public in any::<bool>(),
+    ) {
+        let account_type = if public { AccountType::Public } else { AccountType::Private };
+        let account_id = account_id_from_seed(init_seed, account_type);
+
+        let word: Word = PublisherRegistryWord::from(account_id).into();
+        prop_assert_eq!(word[0], account_id.prefix().as_felt());
+        prop_assert_eq!(word[1], account_id.suffix());
+        prop_assert_eq!(word[2], ZERO);
+        prop_assert_eq!(word[3], ZERO);
+
+        let decoded = PublisherRegistryWord::from_word(word).publisher_id().unwrap();
+        prop_assert_eq!(decoded, Some(account_id));
+    }
+
+    /// Arbitrary words either fail to decode or decode to an id that encodes
+    /// back to the very same word: no word silently decodes to another id.
+    #[test]
+    fn registry_word_never_decodes_to_another_id(
+        prefix in any::<u64>(),
+        suffix in any::<u64>(),
+    ) {
+        let word: Word = [
+            Felt::new(prefix >> 1).unwrap(),
+            Felt::new(suffix >> 1).unwrap(),
+            ZERO,
+            ZERO,
+        ]
+        .into();
+
+        if let Ok(Some(account_id)) = PublisherRegistryWord::from_word(word).publisher_id() {
+            prop_assert_eq!(Word::from(PublisherRegistryWord::from(account_id)), word);
+        }
+    }
+}
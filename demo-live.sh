@@ -445,9 +445,9 @@ oracle_loop() {
         
         for faucet_id in "${FAUCET_IDS[@]}"; do
             display_name=$(get_display_name "$faucet_id")
-            median_output=$(timeout 30 "${ROOT_DIR}/target/release/pm-oracle-cli" median "$faucet_id" --network $NETWORK 2>&1)
+            median_output=$(timeout 30 "${ROOT_DIR}/target/release/pm-oracle-cli" median "$faucet_id" --network $NETWORK --output plain 2>&1)
             median_exit=$?
-            median_value=$(echo "$median_output" | grep -oE 'Median value: [0-9]+' | awk '{print $3}' || echo "")
+            median_value=$(echo "$median_output" | cut -s -f4 | grep -E '^[0-9]+$' || echo "")
             
             if [[ -n "$median_value" ]]; then
                 median_display=$(echo "scale=2; $median_value / 1000000" | bc)
@@ -58,13 +58,13 @@ fetch_median() {
     
     while [ $retry -le $max_retries ]; do
         local start=$(date +%s.%N)
-        local output=$(timeout 30 "${ROOT_DIR}/target/release/pm-oracle-cli" median "$pair" --network $NETWORK 2>&1)
+        local output=$(timeout 30 "${ROOT_DIR}/target/release/pm-oracle-cli" median "$pair" --network $NETWORK --output plain 2>&1)
         local exit_code=$?
         local end=$(date +%s.%N)
         local duration=$(echo "$end - $start" | bc)
         
         if [ $exit_code -eq 0 ]; then
-            local median_value=$(echo "$output" | grep -oE 'Median value: [0-9]+' | awk '{print $3}')
+            local median_value=$(echo "$output" | cut -s -f4 | grep -E '^[0-9]+$')
             if [[ -n "$median_value" ]]; then
                 local median_display=$(echo "scale=2; $median_value / 1000000" | bc)
                 if [ $retry -gt 0 ]; then
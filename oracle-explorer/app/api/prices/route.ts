@@ -17,40 +17,39 @@ const MAX_RETRIES = 3;
 // Lock to prevent multiple concurrent CLI spawns (cache stampede prevention)
 const pendingRequests = new Map<string, Promise<Asset[]>>();
 
+// Schema of `pm-oracle-cli median-batch --output json`, see the CLI README.
 interface MedianResult {
   faucet_id: string;
+  pair?: string;
   is_tracked: boolean;
   median: number;
   amount: number;
+  decimals?: number;
 }
 
 async function fetchAllMediansWithRetry(faucetIds: string[]): Promise<Map<string, number>> {
   for (let attempt = 0; attempt < MAX_RETRIES; attempt++) {
     try {
       const { stdout } = await execAsync(
-        `cd ${ORACLE_WORKSPACE} && ${CLI_PATH}/pm-oracle-cli median-batch ${faucetIds.join(' ')} --network ${NETWORK} --json`,
+        `cd ${ORACLE_WORKSPACE} && ${CLI_PATH}/pm-oracle-cli median-batch ${faucetIds.join(' ')} --network ${NETWORK} --output json`,
         { timeout: 30000 }
       );
       
-      const lines = stdout.trim().split('\n');
-      const jsonLine = lines.find(line => line.startsWith('['));
+      // Progress goes to stderr, stdout only holds the JSON array.
+      const results: MedianResult[] = JSON.parse(stdout);
+      const priceMap = new Map<string, number>();
       
-      if (jsonLine) {
-        const results: MedianResult[] = JSON.parse(jsonLine);
-        const priceMap = new Map<string, number>();
+      results.forEach(({ faucet_id, is_tracked, median }) => {
+        if (!is_tracked) return;
         
-        results.forEach(({ faucet_id, is_tracked, median }) => {
-          if (!is_tracked) return;
-          
-          const pair = FAUCET_ID_TO_PAIR.get(faucet_id);
-          const decimals = FAUCET_ID_TO_DECIMALS.get(faucet_id);
-          if (pair && decimals !== undefined) {
-            priceMap.set(pair, median / Math.pow(10, decimals));
-          }
-        });
-        
-        return priceMap;
-      }
+        const pair = FAUCET_ID_TO_PAIR.get(faucet_id);
+        const decimals = FAUCET_ID_TO_DECIMALS.get(faucet_id);
+        if (pair && decimals !== undefined) {
+          priceMap.set(pair, median / Math.pow(10, decimals));
+        }
+      });
+      
+      return priceMap;
     } catch (error) {
       if (attempt === MAX_RETRIES - 1) {
         throw error;
@@ -216,13 +216,13 @@ export default function Home() {
             </div>
 
             {consumerTab === "single" ? (
-              <CodeBlock code={`./target/release/pm-oracle-cli median 1:0 --network testnet\n# Output: Median value: 76436215000`} />
+              <CodeBlock code={`./target/release/pm-oracle-cli median 1:0 --network testnet --output plain\n# Output: 1:0\tBTC/USD\ttrue\t76436215000\t0\t-`} />
             ) : (
-              <CodeBlock code={`./target/release/pm-oracle-cli median-batch 1:0 2:0 3:0 --network testnet --json\n# Output: [{"faucet_id":"1:0","is_tracked":true,"median":76436215000},...]`} />
+              <CodeBlock code={`./target/release/pm-oracle-cli median-batch 1:0 2:0 3:0 --network testnet --output json\n# Output: [{"faucet_id":"1:0","pair":"BTC/USD","is_tracked":true,"median":76436215000,"amount":0},...]`} />
             )}
 
             <p className="text-[rgba(255,255,255,0.25)] text-xs mt-4 font-mono leading-relaxed">
-              Returns: <span className="text-[rgba(255,255,255,0.4)]">is_tracked</span> &middot; <span className="text-[rgba(255,255,255,0.4)]">median_price</span> (6 decimals) &middot; <span className="text-[rgba(255,255,255,0.4)]">amount</span> (optional)
+              Returns: <span className="text-[rgba(255,255,255,0.4)]">is_tracked</span> &middot; <span className="text-[rgba(255,255,255,0.4)]">median_price</span> (decimals of the asset) &middot; <span className="text-[rgba(255,255,255,0.4)]">amount</span> (optional)
             </p>
           </div>
 